@@ -1,11 +1,34 @@
+use std::path::Path;
+
 use crate::{
     model::{
-        format::ass::{
-            ass_error::AssRes, ass_processor::AssProcessor, parser::parser_error::ParserError,
+        audio::{offset_finder::OffsetFinder, voice_activity::VoiceActivity, wav::WavAudio},
+        format::{
+            ass::{
+                ass_error::AssRes,
+                ass_processor::AssProcessor,
+                document::attachments::{Attachment, AttachmentKind},
+                exporter::ExportTarget,
+                importer::ImportSource,
+            },
+            srt::srt_processor::SrtProcessor,
+            ttml::document::{TtmlProfile, TtmlTiming},
+            vtt::vtt_processor::VttProcessor,
+        },
+        parse_error::ParserError,
+        repository::{
+            SubtitleRepository,
+            encoding::{FileEncoding, TextEncoding},
+        },
+        retiming::{
+            anchors::Anchor,
+            keyframes::{FrameTimes, KeyframeSnap, Keyframes},
         },
-        repository::SubtitleRepository,
         subtitle_persistence::SubtitlePersistence,
         subtitle_processor::SubtitleProcessor,
+        sync_options::SyncOptions,
+        sync_report::SyncReport,
+        timestamp::Timestamp,
     },
     view::{AppConfig, AppPaths, AppStatus, View},
 };
@@ -18,15 +41,23 @@ pub struct App<V: View, R: SubtitleRepository> {
 }
 
 impl<V: View, R: SubtitleRepository> App<V, R> {
+    const REPORT_EXTENSION: &'static str = "sync.json";
+
     pub fn new(view: V, repository: R) -> Self {
         view.display_status(AppStatus::Welcome);
         let persistence = SubtitlePersistence::new(repository);
         let ext = view.get_format();
-        let path_a = Self::get_validated_path_a(&view, &persistence, &ext);
+        let source_ext = view.get_source_format(&ext);
+        let path_a = Self::get_validated_path_a(&view, &persistence, &source_ext);
         let ouput_path = view.request_path_result(&ext);
         let sync_enabled = view.get_sync_enabled();
         let path_b = Self::get_opt_b(&view, &persistence, &ext, sync_enabled);
-        let options = view.get_options(&ouput_path, &ext, sync_enabled);
+        let sync = Self::load_sync_options(&view, &persistence);
+        let mut options = view.get_options(&ouput_path, &ext, sync_enabled, sync);
+        let frame_based =
+            source_ext == "sub" || options.export.as_deref() == Some("3") || options.export_frames;
+        options.frame_rate = frame_based.then(|| view.get_frame_rate());
+        options.import_format = (source_ext != ext).then_some(source_ext);
         Self {
             view,
             persistence,
@@ -37,12 +68,31 @@ impl<V: View, R: SubtitleRepository> App<V, R> {
         }
     }
 
+    fn load_sync_options(view: &V, persistence: &SubtitlePersistence<R>) -> SyncOptions {
+        let config_path = SubtitlePersistence::<R>::CONFIG_PATH;
+        let lines = match persistence.load_config() {
+            Ok(lines) => lines,
+            Err(e) => {
+                view.display_error(&e.to_string(), None);
+                return SyncOptions::default();
+            }
+        };
+        match SyncOptions::default().with_config(&lines) {
+            Ok(sync) => sync,
+            Err(e) => {
+                let e = e.with_path(config_path);
+                view.display_error(&e.to_string(), e.location());
+                SyncOptions::default()
+            }
+        }
+    }
+
     fn get_validated_path_a(view: &V, persistence: &SubtitlePersistence<R>, ext: &str) -> String {
         loop {
             let path = view.request_path_a(ext);
             match persistence.check_availability(&path) {
                 Ok(_) => return path,
-                Err(e) => view.display_error(&e.to_string()),
+                Err(e) => view.display_error(&e.to_string(), None),
             }
         }
     }
@@ -59,7 +109,7 @@ impl<V: View, R: SubtitleRepository> App<V, R> {
             let path = view.request_path_b(ext);
             match persistence.check_availability(&path) {
                 Ok(_) => return path,
-                Err(e) => view.display_error(&e.to_string()),
+                Err(e) => view.display_error(&e.to_string(), None),
             }
         }
     }
@@ -77,7 +127,7 @@ impl<V: View, R: SubtitleRepository> App<V, R> {
     }
 
     fn handle_translation_error(&self, error: impl std::fmt::Display) -> Option<Vec<String>> {
-        self.view.display_error(&error.to_string());
+        self.view.display_error(&error.to_string(), None);
         None
     }
 
@@ -93,14 +143,81 @@ impl<V: View, R: SubtitleRepository> App<V, R> {
         }
     }
 
-    fn step_read_a(&self) -> AssRes<Vec<String>> {
+    fn step_read_a(&self) -> AssRes<(Vec<String>, FileEncoding)> {
         self.view.display_status(AppStatus::ReadingA);
-        Ok(self.persistence.load_subs(&self.config.paths.path_a)?)
+        let path_a = &self.config.paths.path_a;
+        Ok(self.persistence.load_subs_with_encoding(path_a)?)
+    }
+
+    fn step_import(&self, p: &mut Processor, lines: &mut Vec<String>) -> AssRes<()> {
+        let opt = &self.config.options;
+        let source = match opt.import_format.as_deref() {
+            Some("srt") => ImportSource::Srt,
+            Some("vtt") => ImportSource::Vtt,
+            Some("ttml" | "dfxp") => ImportSource::Ttml,
+            Some(_) => {
+                ImportSource::sniff_sub(lines, opt.frame_rate.unwrap_or(Timestamp::FILM_FRAME_RATE))
+            }
+            None => return Ok(()),
+        };
+        self.view.display_status(AppStatus::Importing);
+        *lines = p.import(lines, source)?;
+        Ok(())
+    }
+
+    fn step_retime(&self, p: &mut Processor, lines: &mut Vec<String>) -> AssRes<()> {
+        let opt = &self.config.options;
+        let (entries, path) = match opt.retime.as_deref() {
+            Some("1") => (opt.anchor_entries.clone(), ""),
+            Some(_) => {
+                let path = opt.anchor_entries.first().map_or("", |p| p);
+                (self.persistence.load_anchors(path)?, path)
+            }
+            None => return Ok(()),
+        };
+        self.view.display_status(AppStatus::Retiming);
+        let anchors = Anchor::parse_lines(&entries).map_err(|e| e.with_path(path))?;
+        *lines = p.retime(lines, &anchors)?;
+        Ok(())
+    }
+
+    fn step_audio_sync(&self, p: &mut Processor, lines: &mut Vec<String>) -> AssRes<()> {
+        let Some(path) = self.config.options.audio_path.as_deref() else {
+            return Ok(());
+        };
+        self.view.display_status(AppStatus::ReadingAudio);
+        let bytes = self.persistence.load_audio(path)?;
+        let audio = WavAudio::parse(&bytes)?;
+        self.view.display_status(AppStatus::AnalyzingAudio);
+        let activity = VoiceActivity::from_wav(&audio);
+        let timings = p.timings(lines)?;
+        let result = OffsetFinder::new().run(&timings, &activity, |done, total| {
+            self.view.display_progress(done, total)
+        });
+        self.view.display_audio_sync(&result);
+        if !result.reliable {
+            self.view.display_status(AppStatus::LowAudioConfidence);
+            return Ok(());
+        }
+        *lines = p.retime(lines, &result.anchors())?;
+        Ok(())
+    }
+
+    fn output_encoding(&self, source: FileEncoding) -> FileEncoding {
+        match self.config.options.encoding.as_deref() {
+            Some("1") => source.with_text(TextEncoding::Utf8),
+            Some("2") => source.with_text(TextEncoding::Utf8Bom),
+            Some("3") => source.with_text(TextEncoding::Utf16Le),
+            Some("4") => source.with_text(TextEncoding::Utf16Be),
+            Some(_) => source.with_text(TextEncoding::Windows1252),
+            None => source,
+        }
     }
 
     fn step_preprocessing(&self, p: &mut Processor, lines: &mut Vec<String>) -> AssRes<()> {
         self.view.display_status(AppStatus::Preprocessing);
-        *lines = p.preprocessing(lines)?;
+        let path_b = self.config.paths.path_b.as_deref().unwrap_or_default();
+        *lines = p.preprocessing(lines).map_err(|e| e.with_path(path_b))?;
         Ok(())
     }
 
@@ -123,7 +240,49 @@ impl<V: View, R: SubtitleRepository> App<V, R> {
     fn sync(&self, p: &mut Processor, l_a: &mut Vec<String>, l_b: &mut Vec<String>) -> AssRes<()> {
         self.step_preprocessing(p, l_b)?;
         self.view.display_status(AppStatus::Processing);
-        *l_a = p.synchronize(l_a, l_b)?;
+        let result = p.synchronize(l_a, l_b)?;
+        self.view.display_sync_report(&result.report);
+        self.save_sync_report(&result.report)?;
+        *l_a = result.output;
+        Ok(())
+    }
+
+    fn save_sync_report(&self, report: &SyncReport) -> AssRes<()> {
+        let opt = &self.config.options;
+        if !opt.sync_report {
+            return Ok(());
+        }
+        let path = Path::new(&opt.output_path).with_extension(Self::REPORT_EXTENSION);
+        self.persistence
+            .save_report(&path.to_string_lossy(), &report.to_json())?;
+        Ok(())
+    }
+
+    fn step_snap(&self, p: &mut Processor, lines: &mut Vec<String>) -> AssRes<()> {
+        let opt = &self.config.options;
+        let Some(path) = opt.keyframes_path.as_deref() else {
+            return Ok(());
+        };
+        self.view.display_status(AppStatus::Snapping);
+        let entries = self.persistence.load_keyframes(path)?;
+        let keyframes = Keyframes::parse(&entries).map_err(|e| e.with_path(path))?;
+        let times = match opt.timecodes_path.as_deref() {
+            Some(timecodes) => {
+                let entries = self.persistence.load_timecodes(timecodes)?;
+                FrameTimes::parse_timecodes(&entries).map_err(|e| e.with_path(timecodes))?
+            }
+            None => match keyframes.fps() {
+                Some(fps) if fps != opt.keyframe_fps => {
+                    self.view.display_status(AppStatus::KeyframeFrameRate);
+                    FrameTimes::new(fps)
+                }
+                _ => FrameTimes::new(opt.keyframe_fps),
+            },
+        };
+        let snap = KeyframeSnap::new(&keyframes, &times).with_window(opt.snap_window);
+        let (snapped, summary) = p.snap_keyframes(lines, &snap)?;
+        self.view.display_snap_summary(&summary);
+        *lines = snapped;
         Ok(())
     }
 
@@ -169,24 +328,124 @@ impl<V: View, R: SubtitleRepository> App<V, R> {
         Ok(())
     }
 
+    fn step_attachments(&self, p: &mut Processor, lines: &mut Vec<String>) -> AssRes<()> {
+        let action = self.config.options.attachments.as_deref();
+        if action.is_some() {
+            self.view.display_status(AppStatus::Attachments);
+        }
+        match action {
+            Some("1") => self.list_attachments(p, lines),
+            Some("2") => self.extract_attachments(p, lines),
+            Some(_) => self.embed_attachments(p, lines),
+            None => Ok(()),
+        }
+    }
+
+    fn list_attachments(&self, p: &mut Processor, lines: &[String]) -> AssRes<()> {
+        self.view.display_attachments(&p.attachments(lines)?);
+        Ok(())
+    }
+
+    fn extract_attachments(&self, p: &mut Processor, lines: &[String]) -> AssRes<()> {
+        let paths = &self.config.options.attachment_paths;
+        let dir = paths.first().filter(|d| !d.is_empty()).map_or(".", |d| d);
+        let attachments = p.attachments(lines)?;
+        for attachment in &attachments {
+            let bytes = attachment.decode()?;
+            self.persistence
+                .save_attachment(dir, &attachment.name, &bytes)?;
+        }
+        self.view.display_attachments(&attachments);
+        Ok(())
+    }
+
+    fn embed_attachments(&self, p: &mut Processor, lines: &mut Vec<String>) -> AssRes<()> {
+        let mut attachments = Vec::new();
+        for path in &self.config.options.attachment_paths {
+            let bytes = self.persistence.load_attachment(path)?;
+            let name = Path::new(path)
+                .file_name()
+                .map_or(path.clone(), |n| n.to_string_lossy().into_owned());
+            let kind = AttachmentKind::from_file_name(&name);
+            attachments.push(Attachment::from_bytes(kind, &name, &bytes));
+        }
+        *lines = p.embed_attachments(lines, &attachments)?;
+        self.view.display_attachments(&attachments);
+        Ok(())
+    }
+
+    fn step_export(&self, p: &mut Processor, lines: &[String], enc: FileEncoding) -> AssRes<()> {
+        let opt = &self.config.options;
+        let fps = opt.frame_rate.unwrap_or(Timestamp::FILM_FRAME_RATE);
+        let timing = match opt.export_frames {
+            true => TtmlTiming::Frames(fps),
+            false => TtmlTiming::Clock,
+        };
+        let (target, ext) = match opt.export.as_deref() {
+            Some("1") => (ExportTarget::Srt, "srt"),
+            Some("2") => (ExportTarget::Vtt, "vtt"),
+            Some("3") => (ExportTarget::MicroDvd(fps), "sub"),
+            Some("4") => (ExportTarget::SubViewer, "sub"),
+            Some("5") => (ExportTarget::Ttml(TtmlProfile::Dfxp, timing), "ttml"),
+            Some(_) => (ExportTarget::Ttml(TtmlProfile::EbuTt, timing), "xml"),
+            None => return Ok(()),
+        };
+        let enc = match target {
+            ExportTarget::Ttml(..) => enc.with_text(TextEncoding::Utf8),
+            _ => enc,
+        };
+        self.view.display_status(AppStatus::Exporting);
+        let exported = p.export(lines, target, &opt.export_styles)?;
+        let path = Path::new(&opt.output_path).with_extension(ext);
+        self.persistence
+            .save_subs(&path.to_string_lossy(), &exported, enc)?;
+        Ok(())
+    }
+
+    fn create_processor(&self) -> Processor {
+        let opt = &self.config.options;
+        match opt.format_type.as_str() {
+            "srt" => Box::new(SrtProcessor::new().with_sync(opt.sync)),
+            "vtt" => Box::new(
+                VttProcessor::new()
+                    .with_style(opt.style.clone())
+                    .with_sync(opt.sync),
+            ),
+            _ => Box::new(
+                AssProcessor::new()
+                    .with_style(opt.style.clone())
+                    .with_sync(opt.sync)
+                    .with_comments(opt.keep_comments),
+            ),
+        }
+    }
+
     fn execute_workflow(&mut self) -> AssRes<()> {
         let output_path = &self.config.options.output_path;
         self.view.display_status(AppStatus::Reading);
-        let mut lines_a = self.step_read_a()?;
-        let style_name = self.config.options.style.clone();
-        let mut processor: Processor = Box::new(AssProcessor::new().with_style(style_name));
+        let (mut lines_a, encoding) = self.step_read_a()?;
+        let mut processor = self.create_processor();
+        self.step_import(&mut processor, &mut lines_a)?;
+        self.step_retime(&mut processor, &mut lines_a)?;
+        self.step_audio_sync(&mut processor, &mut lines_a)?;
         self.step_synchronize(&mut processor, &mut lines_a)?;
+        self.step_snap(&mut processor, &mut lines_a)?;
         self.step_translate(&mut processor, &mut lines_a)?;
         self.step_style(&mut processor, &mut lines_a)?;
+        self.step_attachments(&mut processor, &mut lines_a)?;
         self.view.display_status(AppStatus::Writing);
-        self.persistence.save_subs(output_path, &lines_a)?;
+        let encoding = self.output_encoding(encoding);
+        self.persistence
+            .save_subs(output_path, &lines_a, encoding)?;
+        self.step_export(&mut processor, &lines_a, encoding)?;
         Ok(())
     }
 
     pub fn run(&mut self) {
-        match self.execute_workflow() {
+        let path_a = self.config.paths.path_a.clone();
+        match self.execute_workflow().map_err(|e| e.with_path(&path_a)) {
             Ok(()) => self.view.display_status(AppStatus::Success),
-            Err(e) => self.view.display_error(&e.to_string()),
+            Err(e) => self.view.display_error(&e.to_string(), e.location()),
         }
     }
 }
@@ -0,0 +1,274 @@
+use crate::model::{
+    line::Line,
+    sync_options::{SyncMode, SyncOptions},
+    sync_report::SyncReport,
+    sync_result::{BlockPlacement, PlacedBlock},
+    timestamp::Timestamp,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AlignStep {
+    Match(usize, usize),
+    OnlyA(usize),
+    OnlyB(usize),
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Trace {
+    Diagonal,
+    Up,
+    Left,
+}
+
+struct Features {
+    duration: Timestamp,
+    gap: Timestamp,
+    words: Vec<String>,
+}
+
+pub struct Aligner {
+    options: SyncOptions,
+}
+
+impl Aligner {
+    const GAP_PENALTY: f64 = -0.6;
+    const DELTA_CHANGE_RATIO: i64 = 4;
+
+    pub fn new(options: SyncOptions) -> Self {
+        Self { options }
+    }
+
+    fn uses_text(&self) -> bool {
+        self.options.mode == SyncMode::TimingAndText
+    }
+
+    fn words(text: &str) -> Vec<String> {
+        let mut plain = String::with_capacity(text.len());
+        let mut depth = 0usize;
+        for c in text.replace("\\N", " ").chars() {
+            match c {
+                '{' | '<' => depth += 1,
+                '}' | '>' => depth = depth.saturating_sub(1),
+                _ if depth == 0 => plain.push(c),
+                _ => {}
+            }
+        }
+        plain
+            .to_lowercase()
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(String::from)
+            .collect()
+    }
+
+    fn features<L: Line>(&self, lines: &[&L]) -> Vec<Features> {
+        let mut previous_end = None;
+        lines
+            .iter()
+            .map(|line| {
+                let gap = previous_end.map_or(Timestamp::ZERO, |end| line.start() - end);
+                previous_end = Some(line.end());
+                Features {
+                    duration: line.end() - line.start(),
+                    gap,
+                    words: match self.uses_text() {
+                        true => Self::words(line.text()),
+                        false => Vec::new(),
+                    },
+                }
+            })
+            .collect()
+    }
+
+    fn text_similarity(a: &[String], b: &[String]) -> f64 {
+        if a.is_empty() && b.is_empty() {
+            return 1.0;
+        }
+        let shared = a.iter().filter(|word| b.contains(word)).count();
+        let total = a.len() + b.len() - shared;
+        shared as f64 / total.max(1) as f64
+    }
+
+    fn score(&self, a: &Features, b: &Features) -> f64 {
+        let tolerance = self.options.tolerance.millis().max(1) as f64;
+        let drift = ((a.duration - b.duration).abs() + (a.gap - b.gap).abs()).millis() as f64;
+        let timing = 1.0 - (drift / (2.0 * tolerance)).min(1.0);
+        let similarity = match self.uses_text() {
+            true => (timing + Self::text_similarity(&a.words, &b.words)) / 2.0,
+            false => timing,
+        };
+        2.0 * similarity - 1.0
+    }
+
+    fn fill(&self, a: &[Features], b: &[Features]) -> Vec<Vec<Trace>> {
+        let columns = b.len() + 1;
+        let mut previous: Vec<f64> = (0..columns).map(|j| j as f64 * Self::GAP_PENALTY).collect();
+        let mut traces = vec![vec![Trace::Left; columns]; a.len() + 1];
+        for (i, features_a) in a.iter().enumerate() {
+            let mut current = vec![(i + 1) as f64 * Self::GAP_PENALTY; columns];
+            traces[i + 1][0] = Trace::Up;
+            for (j, features_b) in b.iter().enumerate() {
+                let diagonal = previous[j] + self.score(features_a, features_b);
+                let up = previous[j + 1] + Self::GAP_PENALTY;
+                let left = current[j] + Self::GAP_PENALTY;
+                let (best, trace) = match (diagonal >= up, diagonal >= left, up >= left) {
+                    (true, true, _) => (diagonal, Trace::Diagonal),
+                    (_, _, true) => (up, Trace::Up),
+                    _ => (left, Trace::Left),
+                };
+                current[j + 1] = best;
+                traces[i + 1][j + 1] = trace;
+            }
+            previous = current;
+        }
+        traces
+    }
+
+    fn trace_back(traces: &[Vec<Trace>], len_a: usize, len_b: usize) -> Vec<AlignStep> {
+        let (mut i, mut j) = (len_a, len_b);
+        let mut steps = Vec::with_capacity(len_a.max(len_b));
+        while i > 0 || j > 0 {
+            match (i, j, traces[i][j]) {
+                (0, _, _) | (_, _, Trace::Left) if j > 0 => {
+                    j -= 1;
+                    steps.push(AlignStep::OnlyB(j));
+                }
+                (_, 0, _) | (_, _, Trace::Up) => {
+                    i -= 1;
+                    steps.push(AlignStep::OnlyA(i));
+                }
+                _ => {
+                    i -= 1;
+                    j -= 1;
+                    steps.push(AlignStep::Match(i, j));
+                }
+            }
+        }
+        steps.reverse();
+        steps
+    }
+
+    pub fn run<L: Line>(&self, a: &[&L], b: &[&L]) -> Vec<AlignStep> {
+        let features_a = self.features(a);
+        let features_b = self.features(b);
+        let traces = self.fill(&features_a, &features_b);
+        Self::trace_back(&traces, a.len(), b.len())
+    }
+
+    fn deltas<L: Line>(steps: &[AlignStep], a: &[&L], b: &[&L]) -> Vec<Option<Timestamp>> {
+        let mut deltas = vec![None; a.len()];
+        for step in steps {
+            if let AlignStep::Match(i, j) = *step {
+                deltas[i] = Some(b[j].start() - a[i].start());
+            }
+        }
+        deltas
+    }
+
+    pub fn timings<L: Line>(
+        &self,
+        steps: &[AlignStep],
+        a: &[&L],
+        b: &[&L],
+    ) -> Vec<(Timestamp, Timestamp)> {
+        let matched = Self::deltas(steps, a, b);
+        let first = matched.iter().flatten().next().copied();
+        let mut delta = first.unwrap_or(Timestamp::ZERO);
+        let mut timings = Vec::with_capacity(a.len());
+        for (line, matched_delta) in a.iter().zip(matched) {
+            delta = matched_delta.unwrap_or(delta);
+            timings.push(self.options.shifted(line.start(), line.end(), delta));
+        }
+        for step in steps {
+            if let AlignStep::Match(i, j) = *step
+                && self.options.shift_end
+            {
+                timings[i] = (b[j].start(), b[j].end());
+            }
+        }
+        timings
+    }
+
+    fn placement(step: &AlignStep) -> Option<BlockPlacement> {
+        match step {
+            AlignStep::Match(..) => None,
+            AlignStep::OnlyA(_) => Some(BlockPlacement::Unmatched),
+            AlignStep::OnlyB(_) => Some(BlockPlacement::Inserted),
+        }
+    }
+
+    fn is_delta_change(&self, delta: Timestamp, current: Timestamp) -> bool {
+        (delta - current).abs().millis() * Self::DELTA_CHANGE_RATIO
+            >= self.options.tolerance.millis()
+    }
+
+    fn similarity(&self, a: &[Features], b: &[Features], step: Option<&AlignStep>) -> Option<f64> {
+        match step {
+            Some(&AlignStep::Match(i, j)) => Some((self.score(&a[i], &b[j]) + 1.0) / 2.0),
+            _ => None,
+        }
+    }
+
+    fn run_block(placement: BlockPlacement, times: &[(Timestamp, Timestamp)]) -> PlacedBlock {
+        let start = times.iter().map(|t| t.0).min().unwrap_or(Timestamp::ZERO);
+        let end = times.iter().map(|t| t.1).max().unwrap_or(Timestamp::ZERO);
+        PlacedBlock::new(placement, start, end, times.len())
+    }
+
+    pub fn report<L: Line>(
+        &self,
+        steps: &[AlignStep],
+        timings: &[(Timestamp, Timestamp)],
+        a: &[&L],
+        b: &[&L],
+    ) -> SyncReport {
+        let features_a = self.features(a);
+        let features_b = self.features(b);
+        let matched = Self::deltas(steps, a, b);
+        let initial = matched.iter().flatten().next().copied();
+        let mut report = SyncReport::new(initial.unwrap_or(Timestamp::ZERO));
+        for (line, delta) in matched.into_iter().enumerate() {
+            if let Some(delta) = delta
+                && self.is_delta_change(delta, report.current_delta())
+            {
+                report.record_delta(line, delta);
+            }
+        }
+        let mut offset = 0usize;
+        let mut after_line = None;
+        for run in steps.chunk_by(|x, y| Self::placement(x) == Self::placement(y)) {
+            if let Some(placement) = Self::placement(&run[0]) {
+                let times: Vec<(Timestamp, Timestamp)> = run
+                    .iter()
+                    .map(|step| match *step {
+                        AlignStep::Match(i, _) | AlignStep::OnlyA(i) => timings[i],
+                        AlignStep::OnlyB(j) => (b[j].start(), b[j].end()),
+                    })
+                    .collect();
+                let neighbours: Vec<f64> = [offset.checked_sub(1), Some(offset + run.len())]
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|k| self.similarity(&features_a, &features_b, steps.get(k)))
+                    .collect();
+                let confidence = neighbours.iter().sum::<f64>() / neighbours.len().max(1) as f64;
+                let block = Self::run_block(placement, &times)
+                    .with_after_line(after_line)
+                    .with_confidence(confidence);
+                report.add_block(block);
+            }
+            after_line = run
+                .iter()
+                .rev()
+                .find_map(|step| match *step {
+                    AlignStep::Match(i, _) | AlignStep::OnlyA(i) => Some(i),
+                    AlignStep::OnlyB(_) => None,
+                })
+                .or(after_line);
+            offset += run.len();
+        }
+        report.with_overlaps(timings)
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,120 @@
+use crate::model::format::srt::line_srt::SrtLine;
+
+use super::*;
+
+struct TestCase {
+    name: &'static str,
+    mode: SyncMode,
+    input_a: &'static [(i64, i64, &'static str)],
+    input_b: &'static [(i64, i64, &'static str)],
+    expected: &'static [AlignStep],
+}
+
+static EXTRA_LINES_CASE: TestCase = TestCase {
+    name: "detects untagged lines that only exist in B",
+    mode: SyncMode::Timing,
+    input_a: &[
+        (1000, 2000, "Hola"),
+        (3000, 5000, "¿Qué tal?"),
+        (6000, 6500, "Bien"),
+        (8000, 11000, "Adiós"),
+    ],
+    input_b: &[
+        (1500, 2500, "Hello"),
+        (3500, 5500, "How are you?"),
+        (6500, 7000, "Fine"),
+        (7500, 9000, "Extra scene"),
+        (9500, 9800, "Extra continuation"),
+        (11000, 14000, "Bye"),
+    ],
+    expected: &[
+        AlignStep::Match(0, 0),
+        AlignStep::Match(1, 1),
+        AlignStep::Match(2, 2),
+        AlignStep::OnlyB(3),
+        AlignStep::OnlyB(4),
+        AlignStep::Match(3, 5),
+    ],
+};
+
+static MISSING_LINE_CASE: TestCase = TestCase {
+    name: "uses text similarity to find a line that only exists in A",
+    mode: SyncMode::TimingAndText,
+    input_a: &[
+        (1000, 2000, "Hello there"),
+        (2500, 3500, "{\\i1}How are you?{\\i0}"),
+        (4000, 5000, "Fine, thanks"),
+    ],
+    input_b: &[
+        (11000, 12000, "Hello there"),
+        (14000, 15000, "<i>Fine</i>, thanks"),
+    ],
+    expected: &[
+        AlignStep::Match(0, 0),
+        AlignStep::OnlyA(1),
+        AlignStep::Match(2, 1),
+    ],
+};
+
+fn to_cues(input: &[(i64, i64, &str)]) -> Vec<SrtLine> {
+    input
+        .iter()
+        .map(|&(start, end, text)| SrtLine {
+            start: Timestamp::from_millis(start),
+            end: Timestamp::from_millis(end),
+            name: String::new(),
+            text: text.to_string(),
+        })
+        .collect()
+}
+
+fn run_test_case(test_case: &TestCase) -> Vec<AlignStep> {
+    let cues_a = to_cues(test_case.input_a);
+    let cues_b = to_cues(test_case.input_b);
+    let a: Vec<&SrtLine> = cues_a.iter().collect();
+    let b: Vec<&SrtLine> = cues_b.iter().collect();
+    let aligner = Aligner::new(SyncOptions::new().with_mode(test_case.mode));
+    let steps = aligner.run(&a, &b);
+    assert_eq!(
+        steps, test_case.expected,
+        "Failed at case: {}",
+        test_case.name
+    );
+    steps
+}
+
+#[test]
+fn test_detects_lines_only_in_b() {
+    run_test_case(&EXTRA_LINES_CASE);
+}
+
+#[test]
+fn test_detects_lines_only_in_a() {
+    run_test_case(&MISSING_LINE_CASE);
+}
+
+#[test]
+fn test_transfers_timing_and_reports_runs() {
+    let steps = run_test_case(&MISSING_LINE_CASE);
+    let cues_a = to_cues(MISSING_LINE_CASE.input_a);
+    let cues_b = to_cues(MISSING_LINE_CASE.input_b);
+    let a: Vec<&SrtLine> = cues_a.iter().collect();
+    let b: Vec<&SrtLine> = cues_b.iter().collect();
+    let timings = Aligner::new(SyncOptions::default()).timings(&steps, &a, &b);
+    let expected: Vec<(Timestamp, Timestamp)> = [(11000, 12000), (12500, 13500), (14000, 15000)]
+        .iter()
+        .map(|&(start, end)| (Timestamp::from_millis(start), Timestamp::from_millis(end)))
+        .collect();
+    assert_eq!(timings, expected);
+    let report = Aligner::new(SyncOptions::default()).report(&steps, &timings, &a, &b);
+    assert_eq!(report.initial_delta, Timestamp::from_millis(10000));
+    assert!(report.delta_changes.is_empty());
+    let [unmatched] = report.blocks.as_slice() else {
+        panic!("expected a single block, got {:?}", report.blocks);
+    };
+    assert_eq!(unmatched.placement, BlockPlacement::Unmatched);
+    assert_eq!(unmatched.start, Timestamp::from_millis(12500));
+    assert_eq!(unmatched.end, Timestamp::from_millis(13500));
+    assert_eq!(unmatched.after_line, Some(0));
+    assert!((unmatched.confidence - 0.625).abs() < 1e-9);
+}
@@ -0,0 +1,3 @@
+pub mod offset_finder;
+pub mod voice_activity;
+pub mod wav;
@@ -0,0 +1,226 @@
+use crate::model::{
+    audio::voice_activity::VoiceActivity,
+    retiming::anchors::{Anchor, AnchorSource},
+    timestamp::Timestamp,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SegmentOffset {
+    pub start: Timestamp,
+    pub end: Timestamp,
+    pub lines: usize,
+    pub offset: Timestamp,
+    pub confidence: f64,
+    pub refined: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AudioSyncResult {
+    pub offset: Timestamp,
+    pub confidence: f64,
+    pub reliable: bool,
+    pub speech_ratio: f64,
+    pub segments: Vec<SegmentOffset>,
+}
+
+impl AudioSyncResult {
+    pub fn anchors(&self) -> Vec<Anchor> {
+        let mut anchors: Vec<Anchor> = Vec::with_capacity(self.segments.len() * 2);
+        for segment in &self.segments {
+            for time in [segment.start, segment.end] {
+                let target = time + segment.offset;
+                if anchors.last().is_none_or(|last| last.target < target) {
+                    anchors.push(Anchor::new(AnchorSource::Time(time), target));
+                }
+            }
+        }
+        anchors
+    }
+}
+
+struct Search {
+    offset: i64,
+    score: f64,
+    confidence: f64,
+}
+
+struct Pattern {
+    lines: Vec<(i64, i64)>,
+    gaps: Vec<(i64, i64)>,
+}
+
+pub struct OffsetFinder {
+    max_offset: Timestamp,
+    local_range: Timestamp,
+    segment_gap: Timestamp,
+    min_confidence: f64,
+}
+
+impl OffsetFinder {
+    const DEFAULT_MAX_OFFSET: Timestamp = Timestamp::from_millis(120_000);
+    const DEFAULT_LOCAL_RANGE: Timestamp = Timestamp::from_millis(3_000);
+    const DEFAULT_SEGMENT_GAP: Timestamp = Timestamp::from_millis(8_000);
+    const DEFAULT_MIN_CONFIDENCE: f64 = 0.3;
+    const MIN_SEGMENT_LINES: usize = 8;
+
+    pub fn new() -> Self {
+        Self {
+            max_offset: Self::DEFAULT_MAX_OFFSET,
+            local_range: Self::DEFAULT_LOCAL_RANGE,
+            segment_gap: Self::DEFAULT_SEGMENT_GAP,
+            min_confidence: Self::DEFAULT_MIN_CONFIDENCE,
+        }
+    }
+
+    pub fn with_max_offset(mut self, max_offset: Timestamp) -> Self {
+        self.max_offset = max_offset;
+        self
+    }
+
+    pub fn with_local_range(mut self, local_range: Timestamp) -> Self {
+        self.local_range = local_range;
+        self
+    }
+
+    fn frames(activity: &VoiceActivity, time: Timestamp) -> i64 {
+        time.millis() / activity.frame().millis().max(1)
+    }
+
+    fn speech_in(sums: &[usize], start: i64, end: i64) -> usize {
+        let clamp = |frame: i64| frame.clamp(0, sums.len() as i64 - 1) as usize;
+        sums[clamp(end)] - sums[clamp(start)]
+    }
+
+    fn coverage(sums: &[usize], ranges: &[(i64, i64)], offset: i64) -> f64 {
+        let (speech, total) = ranges
+            .iter()
+            .fold((0, 0), |(speech, total), &(start, end)| {
+                let found = Self::speech_in(sums, start + offset, end + offset);
+                (speech + found, total + (end - start).max(0) as usize)
+            });
+        speech as f64 / total.max(1) as f64
+    }
+
+    fn pattern(activity: &VoiceActivity, timings: &[(Timestamp, Timestamp)]) -> Pattern {
+        let mut lines: Vec<(i64, i64)> = timings
+            .iter()
+            .map(|&(start, end)| (Self::frames(activity, start), Self::frames(activity, end)))
+            .collect();
+        lines.sort();
+        let gaps = lines
+            .windows(2)
+            .filter(|pair| pair[1].0 > pair[0].1)
+            .map(|pair| (pair[0].1, pair[1].0))
+            .collect();
+        Pattern { lines, gaps }
+    }
+
+    fn score(sums: &[usize], pattern: &Pattern, offset: i64) -> f64 {
+        Self::coverage(sums, &pattern.lines, offset) - Self::coverage(sums, &pattern.gaps, offset)
+    }
+
+    fn search(sums: &[usize], pattern: &Pattern, from: i64, to: i64) -> Search {
+        let scores: Vec<(i64, f64)> = (from..=to)
+            .map(|offset| (offset, Self::score(sums, pattern, offset)))
+            .collect();
+        let (offset, score) = scores
+            .iter()
+            .copied()
+            .min_by(|x, y| y.1.total_cmp(&x.1).then(x.0.abs().cmp(&y.0.abs())))
+            .unwrap_or((0, 0.0));
+        let mut sorted: Vec<f64> = scores.iter().map(|score| score.1).collect();
+        sorted.sort_by(f64::total_cmp);
+        let median = sorted.get(sorted.len() / 2).copied().unwrap_or(0.0);
+        let confidence = match median < 1.0 {
+            true => ((score - median) / (1.0 - median)).clamp(0.0, 1.0),
+            false => 0.0,
+        };
+        Search {
+            offset,
+            score,
+            confidence,
+        }
+    }
+
+    fn segments(&self, timings: &[(Timestamp, Timestamp)]) -> Vec<Vec<(Timestamp, Timestamp)>> {
+        let mut sorted = timings.to_vec();
+        sorted.sort_by_key(|timing| timing.0);
+        let mut segments: Vec<Vec<(Timestamp, Timestamp)>> = Vec::new();
+        let mut last_end = None;
+        for timing in sorted {
+            let split = last_end.is_some_and(|end| timing.0 - end > self.segment_gap);
+            match segments.last_mut() {
+                Some(segment) if !split || segment.len() < Self::MIN_SEGMENT_LINES => {
+                    segment.push(timing)
+                }
+                _ => segments.push(vec![timing]),
+            }
+            last_end = Some(last_end.map_or(timing.1, |end: Timestamp| end.max(timing.1)));
+        }
+        if segments.len() > 1
+            && segments
+                .last()
+                .is_some_and(|s| s.len() < Self::MIN_SEGMENT_LINES)
+            && let Some(short) = segments.pop()
+            && let Some(previous) = segments.last_mut()
+        {
+            previous.extend(short);
+        }
+        segments
+    }
+
+    pub fn run(
+        &self,
+        timings: &[(Timestamp, Timestamp)],
+        activity: &VoiceActivity,
+        mut progress: impl FnMut(usize, usize),
+    ) -> AudioSyncResult {
+        let sums = activity.prefix_sums();
+        let segments = self.segments(timings);
+        let total = segments.len() + 1;
+        let range = Self::frames(activity, self.max_offset);
+        let global = Self::search(&sums, &Self::pattern(activity, timings), -range, range);
+        progress(1, total);
+        let local = Self::frames(activity, self.local_range);
+        let frame = activity.frame().millis();
+        let mut results = Vec::with_capacity(segments.len());
+        for (i, segment) in segments.iter().enumerate() {
+            let found = Self::search(
+                &sums,
+                &Self::pattern(activity, segment),
+                global.offset - local,
+                global.offset + local,
+            );
+            let refined = found.confidence >= self.min_confidence && found.score > 0.0;
+            let offset = match refined {
+                true => found.offset,
+                false => global.offset,
+            };
+            results.push(SegmentOffset {
+                start: segment.first().map_or(Timestamp::ZERO, |t| t.0),
+                end: segment.iter().map(|t| t.1).max().unwrap_or(Timestamp::ZERO),
+                lines: segment.len(),
+                offset: Timestamp::from_millis(offset * frame),
+                confidence: found.confidence,
+                refined,
+            });
+            progress(i + 2, total);
+        }
+        AudioSyncResult {
+            offset: Timestamp::from_millis(global.offset * frame),
+            confidence: global.confidence,
+            reliable: global.confidence >= self.min_confidence,
+            speech_ratio: activity.speech_ratio(),
+            segments: results,
+        }
+    }
+}
+
+impl Default for OffsetFinder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,148 @@
+use super::*;
+
+struct TestCase {
+    name: &'static str,
+    offsets: &'static [i64],
+    expected: &'static [i64],
+}
+
+static OFFSET_CASES: &[TestCase] = &[
+    TestCase {
+        name: "constant delay",
+        offsets: &[2_350],
+        expected: &[2_350],
+    },
+    TestCase {
+        name: "constant advance",
+        offsets: &[-41_200],
+        expected: &[-41_200],
+    },
+    TestCase {
+        name: "cut shifts the second half",
+        offsets: &[1_000, 2_500],
+        expected: &[1_000, 2_500],
+    },
+];
+
+fn timings(parts: usize) -> Vec<(Timestamp, Timestamp)> {
+    let lengths = [1_800, 2_600, 1_200, 3_100, 2_200];
+    let pauses = [700, 1_500, 400, 2_300, 900, 1_100];
+    let mut at = 60_000;
+    let mut timings = Vec::new();
+    for part in 0..parts {
+        for i in 0..30 {
+            let length = lengths[(i + part) % lengths.len()];
+            timings.push((
+                Timestamp::from_millis(at),
+                Timestamp::from_millis(at + length),
+            ));
+            at += length + pauses[(i * 3 + part) % pauses.len()];
+        }
+        at += 20_000;
+    }
+    timings
+}
+
+fn activity(timings: &[(Timestamp, Timestamp)], offsets: &[i64]) -> VoiceActivity {
+    let per_part = timings.len() / offsets.len();
+    let mut speech = vec![false; 60_000];
+    for (i, &(start, end)) in timings.iter().enumerate() {
+        let offset = offsets[i / per_part];
+        let from = ((start.millis() + offset) / 10) as usize;
+        let to = ((end.millis() + offset) / 10) as usize;
+        speech[from..to].fill(true);
+    }
+    VoiceActivity::new(VoiceActivity::FRAME, speech)
+}
+
+fn run_test_case(test_case: &TestCase) {
+    let timings = timings(test_case.offsets.len());
+    let activity = activity(&timings, test_case.offsets);
+    let mut steps = Vec::new();
+    let result =
+        OffsetFinder::new().run(&timings, &activity, |done, total| steps.push((done, total)));
+    let offsets: Vec<i64> = result.segments.iter().map(|s| s.offset.millis()).collect();
+    assert_eq!(
+        offsets, test_case.expected,
+        "Failed at case: {}",
+        test_case.name
+    );
+    assert!(result.reliable, "Failed at case: {}", test_case.name);
+    assert!(
+        result
+            .segments
+            .iter()
+            .all(|s| s.refined && s.confidence > 0.5),
+        "Failed at case: {}",
+        test_case.name
+    );
+    assert_eq!(
+        steps.last(),
+        Some(&(offsets.len() + 1, offsets.len() + 1)),
+        "Failed at case: {}",
+        test_case.name
+    );
+}
+
+#[test]
+fn test_finds_offsets() {
+    for test_case in OFFSET_CASES {
+        run_test_case(test_case);
+    }
+}
+
+fn segment(start: i64, end: i64, offset: i64) -> SegmentOffset {
+    SegmentOffset {
+        start: Timestamp::from_millis(start),
+        end: Timestamp::from_millis(end),
+        lines: 10,
+        offset: Timestamp::from_millis(offset),
+        confidence: 0.9,
+        refined: true,
+    }
+}
+
+fn audio_result(segments: Vec<SegmentOffset>) -> AudioSyncResult {
+    AudioSyncResult {
+        offset: Timestamp::from_millis(500),
+        confidence: 0.9,
+        reliable: true,
+        speech_ratio: 0.4,
+        segments,
+    }
+}
+
+fn anchor(source: i64, target: i64) -> Anchor {
+    Anchor::new(
+        AnchorSource::Time(Timestamp::from_millis(source)),
+        Timestamp::from_millis(target),
+    )
+}
+
+#[test]
+fn test_anchors_follow_segments() {
+    let anchors = audio_result(vec![segment(1_000, 9_000, 500)]).anchors();
+    assert_eq!(anchors, vec![anchor(1_000, 1_500), anchor(9_000, 9_500)]);
+}
+
+#[test]
+fn test_anchors_stay_increasing_across_segments() {
+    let segments = vec![segment(1_000, 9_000, 3_000), segment(10_000, 20_000, 0)];
+    let anchors = audio_result(segments).anchors();
+    assert_eq!(
+        anchors,
+        vec![
+            anchor(1_000, 4_000),
+            anchor(9_000, 12_000),
+            anchor(20_000, 20_000)
+        ]
+    );
+}
+
+#[test]
+fn test_silent_track_is_not_reliable() {
+    let timings = timings(1);
+    let activity = VoiceActivity::new(VoiceActivity::FRAME, vec![false; 60_000]);
+    let result = OffsetFinder::new().run(&timings, &activity, |_, _| {});
+    assert!(!result.reliable);
+}
@@ -0,0 +1,96 @@
+use crate::model::{audio::wav::WavAudio, timestamp::Timestamp};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct VoiceActivity {
+    frame: Timestamp,
+    speech: Vec<bool>,
+}
+
+impl VoiceActivity {
+    pub const FRAME: Timestamp = Timestamp::from_millis(10);
+    const FLOOR_PERCENTILE: f64 = 0.1;
+    const SPEECH_MARGIN_DB: f64 = 12.0;
+    const SILENCE_DB: f64 = -100.0;
+    const MAX_PAUSE: Timestamp = Timestamp::from_millis(200);
+    const MIN_SPEECH: Timestamp = Timestamp::from_millis(100);
+
+    pub fn new(frame: Timestamp, speech: Vec<bool>) -> Self {
+        Self { frame, speech }
+    }
+
+    fn decibels(energy: f64) -> f64 {
+        match energy > 0.0 {
+            true => (10.0 * energy.log10()).max(Self::SILENCE_DB),
+            false => Self::SILENCE_DB,
+        }
+    }
+
+    fn threshold(levels: &[f64]) -> f64 {
+        let mut sorted = levels.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        let idx = (sorted.len() as f64 * Self::FLOOR_PERCENTILE) as usize;
+        sorted.get(idx).copied().unwrap_or(Self::SILENCE_DB) + Self::SPEECH_MARGIN_DB
+    }
+
+    fn frames(&self, duration: Timestamp) -> usize {
+        (duration.millis() / self.frame.millis().max(1)) as usize
+    }
+
+    fn fill_runs(&mut self, value: bool, max_len: usize) {
+        let mut i = 0;
+        while i < self.speech.len() {
+            let len = self.speech[i..]
+                .iter()
+                .take_while(|&&frame| frame == value)
+                .count();
+            let inside = i > 0 && i + len < self.speech.len();
+            if len > 0 && len <= max_len && inside {
+                self.speech[i..i + len].fill(!value);
+            }
+            i += len.max(1);
+        }
+    }
+
+    pub fn from_energies(energies: &[f64], frame: Timestamp) -> Self {
+        let levels: Vec<f64> = energies.iter().map(|&e| Self::decibels(e)).collect();
+        let threshold = Self::threshold(&levels);
+        let speech = levels.iter().map(|&level| level > threshold).collect();
+        let mut activity = Self::new(frame, speech);
+        activity.fill_runs(false, activity.frames(Self::MAX_PAUSE));
+        activity.fill_runs(true, activity.frames(Self::MIN_SPEECH));
+        activity
+    }
+
+    pub fn from_wav(audio: &WavAudio) -> Self {
+        Self::from_energies(&audio.frame_energies(Self::FRAME), Self::FRAME)
+    }
+
+    pub fn frame(&self) -> Timestamp {
+        self.frame
+    }
+
+    pub fn len(&self) -> usize {
+        self.speech.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.speech.is_empty()
+    }
+
+    pub fn speech_ratio(&self) -> f64 {
+        let speech = self.speech.iter().filter(|&&frame| frame).count();
+        speech as f64 / self.speech.len().max(1) as f64
+    }
+
+    pub fn prefix_sums(&self) -> Vec<usize> {
+        let mut sums = Vec::with_capacity(self.speech.len() + 1);
+        sums.push(0);
+        for &frame in &self.speech {
+            sums.push(sums[sums.len() - 1] + frame as usize);
+        }
+        sums
+    }
+}
+
+#[cfg(test)]
+mod tests;
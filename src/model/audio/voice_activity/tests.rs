@@ -0,0 +1,54 @@
+use super::*;
+
+struct TestCase {
+    name: &'static str,
+    energies: &'static [f64],
+    expected: &'static [bool],
+}
+
+const QUIET: f64 = 1e-8;
+const LOUD: f64 = 1e-2;
+
+static ACTIVITY_CASES: &[TestCase] = &[
+    TestCase {
+        name: "speech above the noise floor",
+        energies: &[QUIET, QUIET, LOUD, LOUD, LOUD, QUIET, QUIET],
+        expected: &[false, false, true, true, true, false, false],
+    },
+    TestCase {
+        name: "short pause inside speech is filled",
+        energies: &[QUIET, LOUD, LOUD, QUIET, LOUD, LOUD, QUIET],
+        expected: &[false, true, true, true, true, true, false],
+    },
+    TestCase {
+        name: "short blip inside silence is dropped",
+        energies: &[QUIET, QUIET, QUIET, LOUD, QUIET, QUIET, QUIET],
+        expected: &[false, false, false, false, false, false, false],
+    },
+    TestCase {
+        name: "digital silence",
+        energies: &[0.0, 0.0, 0.0],
+        expected: &[false, false, false],
+    },
+];
+
+fn run_test_case(test_case: &TestCase) {
+    let activity = VoiceActivity::from_energies(test_case.energies, Timestamp::from_millis(100));
+    let expected = VoiceActivity::new(Timestamp::from_millis(100), test_case.expected.to_vec());
+    assert_eq!(activity, expected, "Failed at case: {}", test_case.name);
+}
+
+#[test]
+fn test_voice_activity() {
+    for test_case in ACTIVITY_CASES {
+        run_test_case(test_case);
+    }
+}
+
+#[test]
+fn test_prefix_sums_and_ratio() {
+    let activity = VoiceActivity::new(Timestamp::from_millis(10), vec![false, true, true, false]);
+    assert_eq!(activity.prefix_sums(), vec![0, 0, 1, 2, 2]);
+    assert_eq!(activity.speech_ratio(), 0.5);
+    assert_eq!(activity.len(), 4);
+}
@@ -0,0 +1,162 @@
+use crate::model::{
+    parse_error::{ParseRes, ParserError},
+    timestamp::Timestamp,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SampleEncoding {
+    Pcm8,
+    Pcm16,
+    Pcm24,
+    Pcm32,
+    Float32,
+}
+
+#[derive(Debug)]
+pub struct WavAudio<'a> {
+    channels: usize,
+    sample_rate: u32,
+    encoding: SampleEncoding,
+    data: &'a [u8],
+}
+
+impl<'a> WavAudio<'a> {
+    const FORMAT_PCM: u16 = 1;
+    const FORMAT_FLOAT: u16 = 3;
+    const FORMAT_EXTENSIBLE: u16 = 0xFFFE;
+    const PRE_EMPHASIS: f64 = 0.97;
+
+    fn u16_at(bytes: &[u8], at: usize) -> u16 {
+        u16::from_le_bytes([bytes[at], bytes[at + 1]])
+    }
+
+    fn u32_at(bytes: &[u8], at: usize) -> u32 {
+        u32::from_le_bytes([bytes[at], bytes[at + 1], bytes[at + 2], bytes[at + 3]])
+    }
+
+    fn chunks(bytes: &'a [u8]) -> impl Iterator<Item = (&'a [u8], &'a [u8])> {
+        let mut at = 12;
+        std::iter::from_fn(move || {
+            let header = bytes.get(at..at + 8)?;
+            let size = Self::u32_at(header, 4) as usize;
+            let body = &bytes[at + 8..(at + 8 + size).min(bytes.len())];
+            at += 8 + size + size % 2;
+            Some((&header[..4], body))
+        })
+    }
+
+    fn encoding(fmt: &[u8]) -> ParseRes<SampleEncoding> {
+        let mut format = Self::u16_at(fmt, 0);
+        if format == Self::FORMAT_EXTENSIBLE && fmt.len() >= 26 {
+            format = Self::u16_at(fmt, 24);
+        }
+        match (format, Self::u16_at(fmt, 14)) {
+            (Self::FORMAT_PCM, 8) => Ok(SampleEncoding::Pcm8),
+            (Self::FORMAT_PCM, 16) => Ok(SampleEncoding::Pcm16),
+            (Self::FORMAT_PCM, 24) => Ok(SampleEncoding::Pcm24),
+            (Self::FORMAT_PCM, 32) => Ok(SampleEncoding::Pcm32),
+            (Self::FORMAT_FLOAT, 32) => Ok(SampleEncoding::Float32),
+            (format, bits) => Err(ParserError::invalid_audio(&format!(
+                "unsupported sample format {} with {} bits",
+                format, bits
+            ))),
+        }
+    }
+
+    pub fn parse(bytes: &'a [u8]) -> ParseRes<Self> {
+        if bytes.len() < 12 || &bytes[..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+            return Err(ParserError::invalid_audio("file is not a RIFF/WAVE file"));
+        }
+        let fmt = Self::chunks(bytes)
+            .find(|(id, body)| id == b"fmt " && body.len() >= 16)
+            .map(|(_, body)| body)
+            .ok_or_else(|| ParserError::invalid_audio("missing 'fmt ' chunk"))?;
+        let data = Self::chunks(bytes)
+            .find(|(id, _)| id == b"data")
+            .map(|(_, body)| body)
+            .ok_or_else(|| ParserError::invalid_audio("missing 'data' chunk"))?;
+        let channels = Self::u16_at(fmt, 2) as usize;
+        let sample_rate = Self::u32_at(fmt, 4);
+        if channels == 0 || sample_rate == 0 {
+            return Err(ParserError::invalid_audio(
+                "no channels or zero sample rate",
+            ));
+        }
+        Ok(Self {
+            channels,
+            sample_rate,
+            encoding: Self::encoding(fmt)?,
+            data,
+        })
+    }
+
+    fn sample_width(&self) -> usize {
+        match self.encoding {
+            SampleEncoding::Pcm8 => 1,
+            SampleEncoding::Pcm16 => 2,
+            SampleEncoding::Pcm24 => 3,
+            SampleEncoding::Pcm32 | SampleEncoding::Float32 => 4,
+        }
+    }
+
+    fn sample(&self, bytes: &[u8]) -> f64 {
+        match self.encoding {
+            SampleEncoding::Pcm8 => (bytes[0] as f64 - 128.0) / 128.0,
+            SampleEncoding::Pcm16 => i16::from_le_bytes([bytes[0], bytes[1]]) as f64 / 32_768.0,
+            SampleEncoding::Pcm24 => {
+                i32::from_le_bytes([0, bytes[0], bytes[1], bytes[2]]) as f64 / 2_147_483_648.0
+            }
+            SampleEncoding::Pcm32 => {
+                i32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as f64
+                    / 2_147_483_648.0
+            }
+            SampleEncoding::Float32 => {
+                f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as f64
+            }
+        }
+    }
+
+    fn block_align(&self) -> usize {
+        self.sample_width() * self.channels
+    }
+
+    pub fn duration(&self) -> Timestamp {
+        let samples = self.data.len() / self.block_align();
+        Timestamp::from_secs_f64(samples as f64 / self.sample_rate as f64)
+    }
+
+    fn mono(&self) -> impl Iterator<Item = f64> + '_ {
+        let width = self.sample_width();
+        self.data
+            .chunks_exact(self.block_align())
+            .map(move |block| {
+                let sum: f64 = block.chunks_exact(width).map(|s| self.sample(s)).sum();
+                sum / self.channels as f64
+            })
+    }
+
+    pub fn frame_energies(&self, frame: Timestamp) -> Vec<f64> {
+        let per_frame = (self.sample_rate as f64 * frame.as_secs_f64())
+            .round()
+            .max(1.0) as usize;
+        let mut energies = Vec::with_capacity(self.data.len() / self.block_align() / per_frame + 1);
+        let (mut previous, mut sum, mut count) = (0.0, 0.0, 0);
+        for sample in self.mono() {
+            let emphasized = sample - Self::PRE_EMPHASIS * previous;
+            previous = sample;
+            sum += emphasized * emphasized;
+            count += 1;
+            if count == per_frame {
+                energies.push(sum / count as f64);
+                (sum, count) = (0.0, 0);
+            }
+        }
+        if count > 0 {
+            energies.push(sum / count as f64);
+        }
+        energies
+    }
+}
+
+#[cfg(test)]
+mod tests;
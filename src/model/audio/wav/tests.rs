@@ -0,0 +1,104 @@
+use super::*;
+
+struct TestCase {
+    name: &'static str,
+    bytes: fn() -> Vec<u8>,
+    expected: Option<Timestamp>,
+}
+
+static PARSE_CASES: &[TestCase] = &[
+    TestCase {
+        name: "mono 16-bit PCM",
+        bytes: || wav(1, 16, 1, &[0; 16_000]),
+        expected: Some(Timestamp::from_millis(1_000)),
+    },
+    TestCase {
+        name: "stereo 16-bit PCM",
+        bytes: || wav(1, 16, 2, &[0; 32_000]),
+        expected: Some(Timestamp::from_millis(1_000)),
+    },
+    TestCase {
+        name: "extensible 24-bit PCM",
+        bytes: || wav(0xFFFE, 24, 1, &[0; 8_000]),
+        expected: Some(Timestamp::from_millis(500)),
+    },
+    TestCase {
+        name: "unsupported compressed format",
+        bytes: || wav(2, 4, 1, &[0; 100]),
+        expected: None,
+    },
+    TestCase {
+        name: "not a wave file",
+        bytes: || b"ID3\x04\x00\x00\x00\x00\x00\x00\x00\x00".to_vec(),
+        expected: None,
+    },
+];
+
+fn wav(format: u16, bits: u16, channels: u16, samples: &[i16]) -> Vec<u8> {
+    let width = (bits / 8) as usize;
+    let data: Vec<u8> = samples
+        .iter()
+        .flat_map(|sample| sample.to_le_bytes().repeat(2)[..width].to_vec())
+        .collect();
+    let mut fmt = Vec::new();
+    fmt.extend(format.to_le_bytes());
+    fmt.extend(channels.to_le_bytes());
+    fmt.extend(16_000u32.to_le_bytes());
+    fmt.extend((16_000 * width as u32 * channels as u32).to_le_bytes());
+    fmt.extend((width as u16 * channels).to_le_bytes());
+    fmt.extend(bits.to_le_bytes());
+    if format == 0xFFFE {
+        fmt.extend(22u16.to_le_bytes());
+        fmt.extend(bits.to_le_bytes());
+        fmt.extend(0u32.to_le_bytes());
+        fmt.extend(1u16.to_le_bytes());
+        fmt.extend([0; 14]);
+    }
+    let mut bytes = b"RIFF\0\0\0\0WAVE".to_vec();
+    for (id, body) in [(b"fmt ", fmt), (b"data", data)] {
+        bytes.extend(id);
+        bytes.extend((body.len() as u32).to_le_bytes());
+        bytes.extend(body);
+    }
+    bytes
+}
+
+fn run_test_case(test_case: &TestCase) {
+    let bytes = (test_case.bytes)();
+    let duration = WavAudio::parse(&bytes).ok().map(|audio| audio.duration());
+    assert_eq!(
+        duration, test_case.expected,
+        "Failed at case: {}",
+        test_case.name
+    );
+}
+
+#[test]
+fn test_parse_wav() {
+    for test_case in PARSE_CASES {
+        run_test_case(test_case);
+    }
+}
+
+#[test]
+fn test_rejects_non_riff_as_audio_error() {
+    let result = WavAudio::parse(b"not audio at all");
+    assert!(matches!(result, Err(ParserError::InvalidAudio { .. })));
+}
+
+#[test]
+fn test_frame_energies() {
+    let mut samples = vec![0i16; 1_600];
+    for (i, sample) in samples.iter_mut().enumerate().skip(800) {
+        *sample = match i % 2 == 0 {
+            true => 16_000,
+            false => -16_000,
+        };
+    }
+    let bytes = wav(1, 16, 1, &samples);
+    let audio = WavAudio::parse(&bytes).unwrap();
+    let energies = audio.frame_energies(Timestamp::from_millis(10));
+    assert_eq!(energies.len(), 10);
+    assert!(energies[..5].iter().all(|&energy| energy == 0.0));
+    assert!(energies[6..].iter().all(|&energy| energy > 0.5));
+}
@@ -1,8 +1,9 @@
-use crate::model::format::ass::parser::{parser::Parser, parser_error::ParseRes};
+use crate::model::format::ass::{document::AssDocument, line_ass::AssLine, parser::parser::Parser};
 
 pub struct SceneApplier {
     parser: Parser,
     idx: usize,
+    keep_comments: bool,
 }
 
 impl SceneApplier {
@@ -10,33 +11,40 @@ impl SceneApplier {
         Self {
             parser: Parser::new(),
             idx: 0,
+            keep_comments: true,
         }
     }
 
-    fn translate(&mut self, l: &str, ts: &[String]) -> ParseRes<String> {
-        let res = self.parser.replace_text(l, &ts[self.idx])?;
-        self.idx += 1;
-        Ok(res)
+    pub fn with_comments(mut self, keep_comments: bool) -> Self {
+        self.keep_comments = keep_comments;
+        self
     }
 
-    fn keep(&self, l: &str) -> String {
-        l.to_string()
+    fn translate(&mut self, l: &mut AssLine, ts: &[String]) {
+        l.text = ts[self.idx].clone();
+        self.idx += 1;
     }
 
-    fn proc_line(&mut self, l: &str, ts: &[String]) -> ParseRes<String> {
-        match self.parser.is_scene_line(l)? && self.idx < ts.len() {
-            true => self.translate(l, ts),
-            false => Ok(self.keep(l)),
+    fn proc_line(&mut self, l: &mut AssLine, ts: &[String]) {
+        if self.parser.is_scene_line(l) && self.idx < ts.len() {
+            self.translate(l, ts);
         }
     }
 
-    fn proc(&mut self, ls: &[String], ts: &[String]) -> ParseRes<Vec<String>> {
-        ls.iter().map(|l| self.proc_line(l, ts)).collect()
+    pub fn run(&mut self, doc: &mut AssDocument, ts: &[String]) {
+        self.idx = 0;
+        for line in doc.dialogues_mut() {
+            self.proc_line(line, ts);
+        }
+        if !self.keep_comments {
+            doc.remove_comments();
+        }
     }
+}
 
-    pub fn run(&mut self, ls: &[String], ts: &[String]) -> ParseRes<Vec<String>> {
-        self.idx = 0;
-        self.proc(ls, ts)
+impl Default for SceneApplier {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
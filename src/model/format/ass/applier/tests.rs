@@ -1,3 +1,5 @@
+use crate::model::parse_error::ParseRes;
+
 use super::*;
 
 struct TestCase {
@@ -43,9 +45,15 @@ fn run_test_case(test_case: &TestCase) -> ParseRes<()> {
         .map(|s| s.to_string())
         .collect();
     let expected: Vec<String> = test_case.expected.iter().map(|s| s.to_string()).collect();
+    let mut doc = AssDocument::parse(&input)?;
     let mut applier = SceneApplier::new();
-    let result = applier.run(&input, &translations)?;
-    assert_eq!(result, expected, "Failed at case: {}", test_case.name);
+    applier.run(&mut doc, &translations);
+    assert_eq!(
+        doc.serialize(),
+        expected,
+        "Failed at case: {}",
+        test_case.name
+    );
     Ok(())
 }
 
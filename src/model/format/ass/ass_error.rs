@@ -1,6 +1,6 @@
 use std::fmt::{Display, Formatter, Result};
 
-use crate::model::format::ass::parser::parser_error::ParserError;
+use crate::model::parse_error::{ParserError, SourceLocation};
 use crate::model::repository::repository_error::RepositoryError;
 
 #[derive(Debug)]
@@ -21,6 +21,22 @@ impl From<ParserError> for AssError {
     }
 }
 
+impl AssError {
+    pub fn location(&self) -> Option<&SourceLocation> {
+        match self {
+            AssError::Parser(parser_err) => parser_err.location(),
+            AssError::Repository(_) => None,
+        }
+    }
+
+    pub fn with_path(self, path: &str) -> Self {
+        match self {
+            AssError::Parser(parser_err) => AssError::Parser(parser_err.with_path(path)),
+            other => other,
+        }
+    }
+}
+
 impl Display for AssError {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         match self {
@@ -2,23 +2,45 @@ use crate::model::{
     format::ass::{
         applier::SceneApplier,
         cleaner::Cleaner,
+        document::{AssDocument, attachments::Attachment},
+        exporter::{ExportTarget, Exporter},
         extractor::SceneExtractor,
-        parser::parser_error::ParserError,
+        importer::{ImportSource, Importer},
+        line_ass::AssLine,
         sorter::Sorter,
         stylist::{Stylist, style_type::StyleType},
         synchronizer::Synchronizer,
     },
+    parse_error::ParserError,
+    retiming::{
+        anchors::{Anchor, AnchorMap},
+        keyframes::{KeyframeSnap, SnapSummary},
+    },
     subtitle_processor::{ProcRes, SubtitleProcessor},
+    sync_options::SyncOptions,
+    sync_result::SyncResult,
+    timestamp::Timestamp,
     translator::{instructor::Instructor, translator::Translator},
 };
 
 pub struct AssProcessor {
     style_type: Option<StyleType>,
+    keep_comments: bool,
+    sync: SyncOptions,
 }
 
 impl AssProcessor {
     pub fn new() -> Self {
-        Self { style_type: None }
+        Self {
+            style_type: None,
+            keep_comments: true,
+            sync: SyncOptions::default(),
+        }
+    }
+
+    pub fn with_comments(mut self, keep_comments: bool) -> Self {
+        self.keep_comments = keep_comments;
+        self
     }
 
     fn identify_style(&self, s: &str) -> StyleType {
@@ -32,17 +54,61 @@ impl AssProcessor {
         self.style_type = style_name.map(|s| self.identify_style(&s));
         self
     }
+
+    pub fn with_sync(mut self, sync: SyncOptions) -> Self {
+        self.sync = sync;
+        self
+    }
+}
+
+impl Default for AssProcessor {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl SubtitleProcessor for AssProcessor {
     type Error = ParserError;
 
-    fn synchronize(&self, l_a: &[String], l_b: &[String]) -> ProcRes<Vec<String>, Self::Error> {
-        Synchronizer::new().run(l_a, l_b)
+    fn synchronize(
+        &self,
+        l_a: &[String],
+        l_b: &[String],
+    ) -> ProcRes<SyncResult<Vec<String>>, Self::Error> {
+        let doc_a = AssDocument::parse(l_a)?;
+        let doc_b = AssDocument::parse(l_b)?;
+        Ok(Synchronizer::new(self.sync)
+            .with_comments(self.keep_comments)
+            .run(&doc_a, &doc_b)
+            .map(|doc| doc.serialize()))
+    }
+
+    fn retime(&self, lines: &[String], anchors: &[Anchor]) -> ProcRes<Vec<String>, Self::Error> {
+        let mut doc = AssDocument::parse(lines)?;
+        let dialogues: Vec<&AssLine> = doc.dialogues().collect();
+        let map = AnchorMap::new(anchors, &dialogues)?;
+        map.retime(doc.lines_mut());
+        Ok(doc.serialize())
+    }
+
+    fn timings(&self, lines: &[String]) -> ProcRes<Vec<(Timestamp, Timestamp)>, Self::Error> {
+        let doc = AssDocument::parse(lines)?;
+        Ok(doc.dialogues().map(|line| (line.start, line.end)).collect())
+    }
+
+    fn snap_keyframes(
+        &self,
+        lines: &[String],
+        snap: &KeyframeSnap,
+    ) -> ProcRes<(Vec<String>, SnapSummary), Self::Error> {
+        let mut doc = AssDocument::parse(lines)?;
+        let summary = snap.retime(doc.lines_mut());
+        Ok((doc.serialize(), summary))
     }
 
     fn get_lines_to_translate(&self, lines: &mut Vec<String>) -> ProcRes<Vec<String>, Self::Error> {
-        let additional_scenes = SceneExtractor::new().run(&lines)?;
+        let doc = AssDocument::parse(lines)?;
+        let additional_scenes = SceneExtractor::new().run(&doc);
         Ok(Instructor::new().run(&additional_scenes))
     }
 
@@ -51,27 +117,81 @@ impl SubtitleProcessor for AssProcessor {
         lines: &mut Vec<String>,
         translations: &[String],
     ) -> ProcRes<Vec<String>, Self::Error> {
-        SceneApplier::new().run(lines, &translations)
+        let mut doc = AssDocument::parse(lines)?;
+        SceneApplier::new()
+            .with_comments(self.keep_comments)
+            .run(&mut doc, translations);
+        Ok(doc.serialize())
     }
 
     fn translate_internal(&self, lines: &mut Vec<String>) -> ProcRes<Vec<String>, Self::Error> {
-        let to_translate = SceneExtractor::new().run(&lines)?;
+        let mut doc = AssDocument::parse(lines)?;
+        let to_translate = SceneExtractor::new().run(&doc);
         let translations = Translator::new().run(&to_translate);
-        Ok(self.apply_translation(lines, &translations)?)
+        SceneApplier::new()
+            .with_comments(self.keep_comments)
+            .run(&mut doc, &translations);
+        Ok(doc.serialize())
     }
 
-    fn apply_style(&self, lines: &Vec<String>) -> ProcRes<Vec<String>, Self::Error> {
+    fn apply_style(&self, lines: &[String]) -> ProcRes<Vec<String>, Self::Error> {
         let style = self.style_type.as_ref().unwrap_or(&StyleType::Main);
-        Stylist::new(style).run(lines)
+        let mut doc = AssDocument::parse(lines)?;
+        Stylist::new(style)
+            .with_comments(self.keep_comments)
+            .run(&mut doc);
+        Ok(doc.serialize())
     }
 
     fn preprocessing(&self, lines: &mut Vec<String>) -> ProcRes<Vec<String>, Self::Error> {
-        Cleaner::new().run(lines)?;
-        Ok(Sorter::new().run(lines)?)
+        let mut doc = AssDocument::parse(lines)?;
+        Cleaner::new()
+            .with_comments(self.keep_comments)
+            .run(&mut doc);
+        Sorter::new()
+            .with_comments(self.keep_comments)
+            .run(&mut doc);
+        Ok(doc.serialize())
+    }
+
+    fn has_additional_scene(&self, lines: &[String]) -> ProcRes<bool, Self::Error> {
+        let doc = AssDocument::parse(lines)?;
+        let additional_scenes = SceneExtractor::new().run(&doc);
+        Ok(!additional_scenes.is_empty())
+    }
+
+    fn attachments(&self, lines: &[String]) -> ProcRes<Vec<Attachment>, Self::Error> {
+        let doc = AssDocument::parse(lines)?;
+        Ok(doc.attachments().cloned().collect())
+    }
+
+    fn embed_attachments(
+        &self,
+        lines: &[String],
+        attachments: &[Attachment],
+    ) -> ProcRes<Vec<String>, Self::Error> {
+        let mut doc = AssDocument::parse(lines)?;
+        for attachment in attachments {
+            doc.embed(attachment.clone());
+        }
+        Ok(doc.serialize())
+    }
+
+    fn export(
+        &self,
+        lines: &[String],
+        target: ExportTarget,
+        styles: &[String],
+    ) -> ProcRes<Vec<String>, Self::Error> {
+        let doc = AssDocument::parse(lines)?;
+        Ok(Exporter::new(target).with_styles(styles.to_vec()).run(&doc))
     }
 
-    fn has_additional_scene(&self, lines: &Vec<String>) -> ProcRes<bool, Self::Error> {
-        let additional_scenes = SceneExtractor::new().run(lines)?;
-        Ok(additional_scenes.len() != 0)
+    fn import(&self, lines: &[String], source: ImportSource) -> ProcRes<Vec<String>, Self::Error> {
+        let style = self.style_type.unwrap_or(StyleType::Main);
+        Ok(Importer::new(source)
+            .with_style(style)
+            .run(lines)?
+            .serialize())
     }
 }
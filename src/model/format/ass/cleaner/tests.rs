@@ -1,4 +1,4 @@
-use crate::model::format::ass::parser::parser_error::ParserError;
+use crate::model::parse_error::{ParseRes, ParserError};
 
 use super::*;
 struct TestCase {
@@ -8,7 +8,7 @@ struct TestCase {
 }
 
 static DUPLICATE_CASE: TestCase = TestCase {
-    name: "removes basic duplicate lines",
+    name: "removes basic duplicate lines and keeps headers",
     input: &[
         "[Events]",
         "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text",
@@ -17,6 +17,8 @@ static DUPLICATE_CASE: TestCase = TestCase {
         "Dialogue: 0,0:00:02.00,0:00:05.00,Default,,0000,0000,0000,,Line B",
     ],
     expected: &[
+        "[Events]",
+        "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text",
         "Dialogue: 0,0:00:01.00,0:00:04.00,Default,,0000,0000,0000,,Line A",
         "Dialogue: 0,0:00:02.00,0:00:05.00,Default,,0000,0000,0000,,Line B",
     ],
@@ -34,6 +36,8 @@ static FILTER_EFFECTS_CASE: TestCase = TestCase {
         "Dialogue: 0,0:00:09.00,0:00:11.00,Default,,0,0,0,blur,Another line to be removed",
     ],
     expected: &[
+        "[Events]",
+        "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text",
         "Dialogue: 0,0:00:01.00,0:00:03.00,Default,,0,0,0,,Normal line 1",
         "Dialogue: 0,0:00:03.00,0:00:05.00,Default,,0,0,0,,Normal line 2",
         "Dialogue: 0,0:00:05.00,0:00:07.00,Default,,0,0,0,fade,This line should be removed",
@@ -55,6 +59,11 @@ static REMOVE_OP_STYLE_CASE: TestCase = TestCase {
         "Dialogue: 0,0:00:05.00,0:00:07.00,OP,,0000,0000,0000,,This should be removed",
     ],
     expected: &[
+        "[Script Info]",
+        "Title: Test 2",
+        "",
+        "[Events]",
+        "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text",
         "Dialogue: 0,0:00:01.00,0:00:03.00,Default,,0000,0000,0000,,Hello world",
         "Dialogue: 0,0:00:03.00,0:00:05.00,Default,,0000,0000,0000,,Another line",
     ],
@@ -68,7 +77,11 @@ static WITH_FORMAT_CASE: TestCase = TestCase {
         "Dialogue: 0,0:00:02.00,0:00:05.00,Default,,0000,0000,0000,,{\\i1}Formatted text",
         "Dialogue: 0,0:00:05.00,0:00:08.00,Default,,0000,0000,0000,,{\\b1}{\\i1}",
     ],
-    expected: &["Dialogue: 0,0:00:02.00,0:00:05.00,Default,,0000,0000,0000,,{\\i1}Formatted text"],
+    expected: &[
+        "[Events]",
+        "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text",
+        "Dialogue: 0,0:00:02.00,0:00:05.00,Default,,0000,0000,0000,,{\\i1}Formatted text",
+    ],
 };
 
 static JUNK_IGNORED_CASE: TestCase = TestCase {
@@ -85,7 +98,18 @@ static JUNK_IGNORED_CASE: TestCase = TestCase {
         "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text",
         "Dialogue: 0,0:00:03.00,0:00:05.00,Default,,0000,0000,0000,,Hello",
     ],
-    expected: &["Dialogue: 0,0:00:03.00,0:00:05.00,Default,,0000,0000,0000,,Hello"],
+    expected: &[
+        "[Script Info]",
+        "; trash",
+        "; more trash",
+        "",
+        "[V4 Styles]",
+        "; ignore all",
+        "",
+        "[Events]",
+        "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text",
+        "Dialogue: 0,0:00:03.00,0:00:05.00,Default,,0000,0000,0000,,Hello",
+    ],
 };
 
 static LONG_TAGS_CASE: TestCase = TestCase {
@@ -108,12 +132,48 @@ static CORRUPT_LINE_CASE: TestCase = TestCase {
     expected: &[],
 };
 
+const COMMENT_EVENTS: &[&str] = &[
+    "[Events]",
+    "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text",
+    "Comment: 0,0:00:00.50,0:00:01.00,Default,,0,0,0,,TL note: keep the pun",
+    "Dialogue: 0,0:00:01.00,0:00:02.00,Default,,0,0,0,,Line A",
+    "Comment: 0,0:00:02.00,0:00:03.00,OP,,0,0,0,,",
+    "Picture: 0,0:00:03.00,0:00:04.00,Default,,0,0,0,,logo.png",
+];
+
+static KEEP_COMMENTS_CASE: TestCase = TestCase {
+    name: "keeps comments and other event kinds untouched",
+    input: COMMENT_EVENTS,
+    expected: COMMENT_EVENTS,
+};
+
+static DROP_COMMENTS_CASE: TestCase = TestCase {
+    name: "drops comments when asked to",
+    input: COMMENT_EVENTS,
+    expected: &[
+        "[Events]",
+        "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text",
+        "Dialogue: 0,0:00:01.00,0:00:02.00,Default,,0,0,0,,Line A",
+        "Picture: 0,0:00:03.00,0:00:04.00,Default,,0,0,0,,logo.png",
+    ],
+};
+
 fn run_test_case(test_case: &TestCase) -> ParseRes<()> {
-    let mut input: Vec<String> = test_case.input.iter().map(|s| s.to_string()).collect();
+    run_with_comments(test_case, true)
+}
+
+fn run_with_comments(test_case: &TestCase, keep_comments: bool) -> ParseRes<()> {
+    let input: Vec<String> = test_case.input.iter().map(|s| s.to_string()).collect();
     let expected: Vec<String> = test_case.expected.iter().map(|s| s.to_string()).collect();
-    let mut cleaner = Cleaner::new();
-    cleaner.run(&mut input)?;
-    assert_eq!(input, expected, "Failed at case: {}", test_case.name);
+    let mut doc = AssDocument::parse(&input)?;
+    let mut cleaner = Cleaner::new().with_comments(keep_comments);
+    cleaner.run(&mut doc);
+    assert_eq!(
+        doc.serialize(),
+        expected,
+        "Failed at case: {}",
+        test_case.name
+    );
     Ok(())
 }
 
@@ -157,7 +217,17 @@ fn test_fails_on_corrupt_data() {
 fn test_error_missing_fields() {
     let result = run_test_case(&MISSING_FIELDS_CASE);
     match result {
-        Err(ParserError::MissingFields { found }) => assert_eq!(found, 3),
+        Err(ParserError::MissingFields { found, .. }) => assert_eq!(found, 3),
         _ => panic!("Expected MissingFields(3), got {:?}", result),
     }
 }
+
+#[test]
+fn test_clean_keeps_comments() -> ParseRes<()> {
+    run_test_case(&KEEP_COMMENTS_CASE)
+}
+
+#[test]
+fn test_clean_drops_comments() -> ParseRes<()> {
+    run_with_comments(&DROP_COMMENTS_CASE, false)
+}
@@ -0,0 +1,254 @@
+use crate::model::parse_error::{ParseRes, ParserError};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AttachmentKind {
+    Font,
+    Graphic,
+}
+
+impl AttachmentKind {
+    const FONTS_HEADER: &str = "[Fonts]";
+    const GRAPHICS_HEADER: &str = "[Graphics]";
+    const FONT_KEY: &str = "fontname:";
+    const GRAPHIC_KEY: &str = "filename:";
+    const FONT_EXTENSIONS: [&str; 5] = ["ttf", "otf", "ttc", "fon", "pfb"];
+
+    pub fn from_header(header: &str) -> Option<Self> {
+        let header = header.trim();
+        match header {
+            _ if header.eq_ignore_ascii_case(Self::FONTS_HEADER) => Some(AttachmentKind::Font),
+            _ if header.eq_ignore_ascii_case(Self::GRAPHICS_HEADER) => {
+                Some(AttachmentKind::Graphic)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn from_file_name(name: &str) -> Self {
+        let extension = name.rsplit_once('.').map(|(_, ext)| ext.to_lowercase());
+        match extension {
+            Some(ext) if Self::FONT_EXTENSIONS.contains(&ext.as_str()) => AttachmentKind::Font,
+            _ => AttachmentKind::Graphic,
+        }
+    }
+
+    pub fn header(self) -> &'static str {
+        match self {
+            AttachmentKind::Font => Self::FONTS_HEADER,
+            AttachmentKind::Graphic => Self::GRAPHICS_HEADER,
+        }
+    }
+
+    fn key(self) -> &'static str {
+        match self {
+            AttachmentKind::Font => Self::FONT_KEY,
+            AttachmentKind::Graphic => Self::GRAPHIC_KEY,
+        }
+    }
+
+    fn strip_key(self, line: &str) -> Option<&str> {
+        let key = self.key();
+        line.get(..key.len())
+            .filter(|prefix| prefix.eq_ignore_ascii_case(key))
+            .map(|_| line[key.len()..].trim())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Attachment {
+    pub kind: AttachmentKind,
+    pub name: String,
+    raw_name: Option<String>,
+    data: Vec<String>,
+}
+
+impl Attachment {
+    const LINE_WIDTH: usize = 80;
+    const OFFSET: u8 = 33;
+    const MAX_CHAR: u8 = Self::OFFSET + 63;
+
+    pub fn from_bytes(kind: AttachmentKind, name: &str, bytes: &[u8]) -> Self {
+        Self {
+            kind,
+            name: name.to_string(),
+            raw_name: None,
+            data: Self::encode(bytes),
+        }
+    }
+
+    fn parse(kind: AttachmentKind, line: &str) -> Option<Self> {
+        Some(Self {
+            kind,
+            name: kind.strip_key(line)?.to_string(),
+            raw_name: Some(line.to_string()),
+            data: Vec::new(),
+        })
+    }
+
+    fn is_data_char(byte: u8) -> bool {
+        (Self::OFFSET..=Self::MAX_CHAR).contains(&byte)
+    }
+
+    fn is_data_line(line: &str) -> bool {
+        !line.is_empty() && line.bytes().all(Self::is_data_char)
+    }
+
+    fn encode(bytes: &[u8]) -> Vec<String> {
+        let mut encoded = String::new();
+        for chunk in bytes.chunks(3) {
+            let mut group = [0u8; 3];
+            group[..chunk.len()].copy_from_slice(chunk);
+            let sextets = [
+                group[0] >> 2,
+                ((group[0] & 0x03) << 4) | (group[1] >> 4),
+                ((group[1] & 0x0F) << 2) | (group[2] >> 6),
+                group[2] & 0x3F,
+            ];
+            encoded.extend(
+                sextets[..chunk.len() + 1]
+                    .iter()
+                    .map(|s| (s + Self::OFFSET) as char),
+            );
+        }
+        encoded
+            .as_bytes()
+            .chunks(Self::LINE_WIDTH)
+            .map(|line| String::from_utf8_lossy(line).into_owned())
+            .collect()
+    }
+
+    pub fn decode(&self) -> ParseRes<Vec<u8>> {
+        let encoded: Vec<u8> = self.data.iter().flat_map(|l| l.trim().bytes()).collect();
+        let invalid = || ParserError::InvalidAttachment {
+            name: self.name.clone(),
+        };
+        let mut bytes = Vec::with_capacity(encoded.len() / 4 * 3);
+        for chunk in encoded.chunks(4) {
+            if chunk.len() < 2 || !chunk.iter().copied().all(Self::is_data_char) {
+                return Err(invalid());
+            }
+            let mut sextets = [0u8; 4];
+            for (slot, b) in sextets.iter_mut().zip(chunk) {
+                *slot = b - Self::OFFSET;
+            }
+            let group = [
+                (sextets[0] << 2) | (sextets[1] >> 4),
+                (sextets[1] << 4) | (sextets[2] >> 2),
+                (sextets[2] << 6) | sextets[3],
+            ];
+            bytes.extend_from_slice(&group[..chunk.len() - 1]);
+        }
+        Ok(bytes)
+    }
+
+    pub fn size(&self) -> usize {
+        let chars: usize = self.data.iter().map(|l| l.trim().len()).sum();
+        chars / 4 * 3 + (chars % 4).saturating_sub(1)
+    }
+
+    fn name_line(&self) -> String {
+        match &self.raw_name {
+            Some(raw) if self.kind.strip_key(raw) == Some(self.name.as_str()) => raw.clone(),
+            _ => format!("{} {}", self.kind.key(), self.name),
+        }
+    }
+
+    fn to_lines(&self) -> Vec<String> {
+        std::iter::once(self.name_line())
+            .chain(self.data.iter().cloned())
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum AttachmentEntry {
+    File(Attachment),
+    Raw(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct AttachmentsSection {
+    pub header: String,
+    pub kind: AttachmentKind,
+    pub entries: Vec<AttachmentEntry>,
+}
+
+impl AttachmentsSection {
+    pub fn new(kind: AttachmentKind) -> Self {
+        Self {
+            header: kind.header().to_string(),
+            kind,
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn open(header: &str) -> Option<Self> {
+        let kind = AttachmentKind::from_header(header)?;
+        Some(Self {
+            header: header.to_string(),
+            kind,
+            entries: Vec::new(),
+        })
+    }
+
+    fn current_file(&mut self) -> Option<&mut Attachment> {
+        match self.entries.last_mut() {
+            Some(AttachmentEntry::File(file)) => Some(file),
+            _ => None,
+        }
+    }
+
+    pub fn is_data_line(&self, line: &str) -> bool {
+        matches!(self.entries.last(), Some(AttachmentEntry::File(_)))
+            && Attachment::is_data_line(line)
+    }
+
+    pub fn push_line(&mut self, line: &str) {
+        if let Some(file) = Attachment::parse(self.kind, line) {
+            self.entries.push(AttachmentEntry::File(file));
+            return;
+        }
+        match (Attachment::is_data_line(line), self.current_file()) {
+            (true, Some(file)) => file.data.push(line.to_string()),
+            _ => self.entries.push(AttachmentEntry::Raw(line.to_string())),
+        }
+    }
+
+    pub fn attachments(&self) -> impl Iterator<Item = &Attachment> {
+        self.entries.iter().filter_map(|entry| match entry {
+            AttachmentEntry::File(file) => Some(file),
+            AttachmentEntry::Raw(_) => None,
+        })
+    }
+
+    fn content_end(&self) -> usize {
+        self.entries
+            .iter()
+            .rposition(|entry| !matches!(entry, AttachmentEntry::Raw(l) if l.trim().is_empty()))
+            .map_or(0, |idx| idx + 1)
+    }
+
+    pub fn embed(&mut self, attachment: Attachment) {
+        let existing = self.entries.iter_mut().find_map(|entry| match entry {
+            AttachmentEntry::File(file) if file.name == attachment.name => Some(file),
+            _ => None,
+        });
+        match existing {
+            Some(file) => file.data = attachment.data,
+            None => {
+                let idx = self.content_end();
+                self.entries.insert(idx, AttachmentEntry::File(attachment));
+            }
+        }
+    }
+
+    pub fn to_lines(&self) -> Vec<String> {
+        let entries = self.entries.iter().flat_map(|entry| match entry {
+            AttachmentEntry::File(file) => file.to_lines(),
+            AttachmentEntry::Raw(line) => vec![line.clone()],
+        });
+        std::iter::once(self.header.clone())
+            .chain(entries)
+            .collect()
+    }
+}
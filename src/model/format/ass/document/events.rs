@@ -0,0 +1,150 @@
+use crate::model::{
+    format::ass::{
+        event_format::EventFormat,
+        line_ass::{AssLine, EventKind},
+        parser::parser::Parser,
+    },
+    parse_error::ParseRes,
+    timestamp::Timestamp,
+};
+
+#[derive(Debug, Clone)]
+pub enum EventEntry {
+    Format(String),
+    Event(Box<AssLine>),
+    Raw(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct EventsSection {
+    pub header: Option<String>,
+    pub format: EventFormat,
+    pub entries: Vec<EventEntry>,
+}
+
+impl EventsSection {
+    const MARKED_PREFIX: &str = "Marked=";
+
+    pub fn new(header: Option<&str>) -> Self {
+        Self {
+            header: header.map(String::from),
+            format: EventFormat::default(),
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn push_line(&mut self, line: &str, parser: &Parser) -> ParseRes<()> {
+        let entry = match line {
+            _ if parser.is_event(line) => {
+                EventEntry::Event(Box::new(parser.parse_ass_line(line, &self.format)?))
+            }
+            _ if parser.is_format_line(line) => {
+                self.format = EventFormat::parse(line)?;
+                EventEntry::Format(line.to_string())
+            }
+            _ => EventEntry::Raw(line.to_string()),
+        };
+        self.entries.push(entry);
+        Ok(())
+    }
+
+    pub fn lines(&self) -> impl Iterator<Item = &AssLine> {
+        self.entries.iter().filter_map(|entry| match entry {
+            EventEntry::Event(line) => Some(line.as_ref()),
+            _ => None,
+        })
+    }
+
+    pub fn lines_mut(&mut self) -> impl Iterator<Item = &mut AssLine> {
+        self.entries.iter_mut().filter_map(|entry| match entry {
+            EventEntry::Event(line) => Some(line.as_mut()),
+            _ => None,
+        })
+    }
+
+    pub fn dialogues(&self) -> impl Iterator<Item = &AssLine> {
+        self.lines().filter(|line| line.kind == EventKind::Dialogue)
+    }
+
+    pub fn dialogues_mut(&mut self) -> impl Iterator<Item = &mut AssLine> {
+        self.lines_mut()
+            .filter(|line| line.kind == EventKind::Dialogue)
+    }
+
+    pub fn retain_lines<F: FnMut(&AssLine) -> bool>(&mut self, mut keep: F) {
+        self.entries.retain(|entry| match entry {
+            EventEntry::Event(line) => keep(line),
+            _ => true,
+        });
+    }
+
+    fn push_group(&mut self, group: Vec<AssLine>, offset: &mut Timestamp, original: &AssLine) {
+        if let Some(first) = group.first() {
+            *offset = first.start - original.start;
+        }
+        self.entries.extend(
+            group
+                .into_iter()
+                .map(|line| EventEntry::Event(Box::new(line))),
+        );
+    }
+
+    fn push_shifted(&mut self, mut line: Box<AssLine>, offset: Timestamp) {
+        line.shift(offset);
+        self.entries.push(EventEntry::Event(line));
+    }
+
+    pub fn replace_dialogues<I>(&mut self, groups: &mut I, offset: &mut Timestamp)
+    where
+        I: Iterator<Item = Vec<AssLine>>,
+    {
+        let entries = std::mem::take(&mut self.entries);
+        for entry in entries {
+            match entry {
+                EventEntry::Event(line) if line.kind == EventKind::Dialogue => {
+                    let group = groups.next().unwrap_or_default();
+                    self.push_group(group, offset, &line);
+                }
+                EventEntry::Event(line) => self.push_shifted(line, *offset),
+                other => self.entries.push(other),
+            }
+        }
+    }
+
+    pub fn insert_before_dialogues(&mut self, lines: Vec<AssLine>) {
+        let position = self
+            .entries
+            .iter()
+            .position(|entry| {
+                matches!(entry, EventEntry::Event(line) if line.kind == EventKind::Dialogue)
+            })
+            .unwrap_or(self.entries.len());
+        let entries = lines
+            .into_iter()
+            .map(|line| EventEntry::Event(Box::new(line)));
+        self.entries.splice(position..position, entries);
+    }
+
+    pub fn convert_to_v4_plus(&mut self) {
+        let format_line = self.format.to_line();
+        for entry in &mut self.entries {
+            match entry {
+                EventEntry::Format(line) => *line = format_line.clone(),
+                EventEntry::Event(line) => {
+                    if let Some(layer) = line.layer.trim().strip_prefix(Self::MARKED_PREFIX) {
+                        line.layer = layer.to_string();
+                    }
+                }
+                EventEntry::Raw(_) => {}
+            }
+        }
+    }
+
+    pub fn to_lines(&self, parser: &Parser) -> Vec<String> {
+        let entries = self.entries.iter().map(|entry| match entry {
+            EventEntry::Format(line) | EventEntry::Raw(line) => line.clone(),
+            EventEntry::Event(line) => parser.ass_line_to_string(line, &self.format),
+        });
+        self.header.iter().cloned().chain(entries).collect()
+    }
+}
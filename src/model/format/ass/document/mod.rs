@@ -0,0 +1,247 @@
+use crate::model::{
+    format::ass::{
+        document::{
+            attachments::{Attachment, AttachmentsSection},
+            events::EventsSection,
+            script_info::ScriptInfo,
+            styles::StylesSection,
+        },
+        line_ass::{AssLine, EventKind},
+        parser::parser::Parser,
+    },
+    parse_error::{ParseRes, ParserError, SourceLocation},
+};
+
+pub mod attachments;
+pub mod events;
+pub mod script_info;
+pub mod styles;
+
+#[derive(Debug, Clone)]
+pub struct RawSection {
+    pub header: String,
+    pub lines: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub enum AssSection {
+    ScriptInfo(ScriptInfo),
+    Styles(StylesSection),
+    Events(EventsSection),
+    Attachments(AttachmentsSection),
+    Unknown(RawSection),
+}
+
+impl AssSection {
+    fn open(header: &str, parser: &Parser) -> Self {
+        match header {
+            _ if parser.is_script_info(header) => AssSection::ScriptInfo(ScriptInfo::new(header)),
+            _ if StylesSection::is_styles_header(header) => {
+                AssSection::Styles(StylesSection::new(header))
+            }
+            _ if parser.is_events_section(header) => {
+                AssSection::Events(EventsSection::new(Some(header)))
+            }
+            _ => match AttachmentsSection::open(header) {
+                Some(attachments) => AssSection::Attachments(attachments),
+                None => AssSection::Unknown(RawSection {
+                    header: header.to_string(),
+                    lines: Vec::new(),
+                }),
+            },
+        }
+    }
+
+    fn push_line(&mut self, line: &str, parser: &Parser) -> ParseRes<()> {
+        match self {
+            AssSection::ScriptInfo(info) => info.push_line(line),
+            AssSection::Styles(styles) => styles.push_line(line)?,
+            AssSection::Events(events) => events.push_line(line, parser)?,
+            AssSection::Attachments(attachments) => attachments.push_line(line),
+            AssSection::Unknown(raw) => raw.lines.push(line.to_string()),
+        }
+        Ok(())
+    }
+
+    fn is_data_line(&self, line: &str) -> bool {
+        matches!(self, AssSection::Attachments(attachments) if attachments.is_data_line(line))
+    }
+
+    fn to_lines(&self, parser: &Parser) -> Vec<String> {
+        match self {
+            AssSection::ScriptInfo(info) => info.to_lines(),
+            AssSection::Styles(styles) => styles.to_lines(),
+            AssSection::Events(events) => events.to_lines(parser),
+            AssSection::Attachments(attachments) => attachments.to_lines(),
+            AssSection::Unknown(raw) => std::iter::once(raw.header.clone())
+                .chain(raw.lines.iter().cloned())
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AssDocument {
+    pub sections: Vec<AssSection>,
+}
+
+impl AssDocument {
+    const SCRIPT_TYPE_KEY: &str = "ScriptType";
+    const V4_PLUS_TYPE: &str = "v4.00+";
+
+    pub fn parse(lines: &[String]) -> ParseRes<Self> {
+        let parser = Parser::new();
+        let mut sections = Vec::new();
+        let mut current: Option<AssSection> = None;
+        for (idx, line) in lines.iter().enumerate() {
+            let is_data = current.as_ref().is_some_and(|s| s.is_data_line(line));
+            if parser.is_section_start(line) && !is_data {
+                Self::check_header(line, idx + 1, &parser)?;
+                sections.extend(current.replace(AssSection::open(line, &parser)));
+                continue;
+            }
+            current
+                .get_or_insert_with(|| AssSection::Events(EventsSection::new(None)))
+                .push_line(line, &parser)
+                .map_err(|e| e.at_line(idx + 1, line))?;
+        }
+        sections.extend(current);
+        Ok(Self { sections })
+    }
+
+    fn check_header(line: &str, number: usize, parser: &Parser) -> ParseRes<()> {
+        match parser.is_section_header(line) {
+            true => Ok(()),
+            false => Err(ParserError::UnknownSection {
+                location: SourceLocation::new(number, line),
+            }),
+        }
+    }
+
+    pub fn serialize(&self) -> Vec<String> {
+        let parser = Parser::new();
+        self.sections
+            .iter()
+            .flat_map(|section| section.to_lines(&parser))
+            .collect()
+    }
+
+    pub fn script_info(&self) -> Option<&ScriptInfo> {
+        self.sections.iter().find_map(|section| match section {
+            AssSection::ScriptInfo(info) => Some(info),
+            _ => None,
+        })
+    }
+
+    pub fn script_info_mut(&mut self) -> Option<&mut ScriptInfo> {
+        self.sections.iter_mut().find_map(|section| match section {
+            AssSection::ScriptInfo(info) => Some(info),
+            _ => None,
+        })
+    }
+
+    pub fn styles_mut(&mut self) -> Option<&mut StylesSection> {
+        self.sections.iter_mut().find_map(|section| match section {
+            AssSection::Styles(styles) => Some(styles),
+            _ => None,
+        })
+    }
+
+    pub fn styles(&self) -> impl Iterator<Item = &StylesSection> {
+        self.sections.iter().filter_map(|section| match section {
+            AssSection::Styles(styles) => Some(styles),
+            _ => None,
+        })
+    }
+
+    pub fn is_legacy(&self) -> bool {
+        self.styles().any(|styles| styles.is_legacy())
+    }
+
+    pub fn convert_to_v4_plus(&mut self) {
+        if !self.is_legacy() {
+            return;
+        }
+        for section in &mut self.sections {
+            match section {
+                AssSection::ScriptInfo(info) => info.set(Self::SCRIPT_TYPE_KEY, Self::V4_PLUS_TYPE),
+                AssSection::Styles(styles) => styles.convert_to_v4_plus(),
+                AssSection::Events(events) => events.convert_to_v4_plus(),
+                AssSection::Attachments(_) | AssSection::Unknown(_) => {}
+            }
+        }
+    }
+
+    pub fn events(&self) -> impl Iterator<Item = &EventsSection> {
+        self.sections.iter().filter_map(|section| match section {
+            AssSection::Events(events) => Some(events),
+            _ => None,
+        })
+    }
+
+    pub fn events_mut(&mut self) -> impl Iterator<Item = &mut EventsSection> {
+        self.sections
+            .iter_mut()
+            .filter_map(|section| match section {
+                AssSection::Events(events) => Some(events),
+                _ => None,
+            })
+    }
+
+    pub fn lines_mut(&mut self) -> impl Iterator<Item = &mut AssLine> {
+        self.events_mut().flat_map(|events| events.lines_mut())
+    }
+
+    pub fn dialogues(&self) -> impl Iterator<Item = &AssLine> {
+        self.events().flat_map(|events| events.dialogues())
+    }
+
+    pub fn dialogues_mut(&mut self) -> impl Iterator<Item = &mut AssLine> {
+        self.events_mut().flat_map(|events| events.dialogues_mut())
+    }
+
+    pub fn remove_comments(&mut self) {
+        for events in self.events_mut() {
+            events.retain_lines(|line| line.kind != EventKind::Comment);
+        }
+    }
+
+    pub fn attachments(&self) -> impl Iterator<Item = &Attachment> {
+        self.sections
+            .iter()
+            .filter_map(|section| match section {
+                AssSection::Attachments(attachments) => Some(attachments),
+                _ => None,
+            })
+            .flat_map(|attachments| attachments.attachments())
+    }
+
+    pub fn embed(&mut self, attachment: Attachment) {
+        let existing = self.sections.iter_mut().find_map(|section| match section {
+            AssSection::Attachments(attachments) if attachments.kind == attachment.kind => {
+                Some(attachments)
+            }
+            _ => None,
+        });
+        match existing {
+            Some(attachments) => attachments.embed(attachment),
+            None => {
+                let mut attachments = AttachmentsSection::new(attachment.kind);
+                attachments.embed(attachment);
+                self.sections.push(AssSection::Attachments(attachments));
+            }
+        }
+    }
+
+    pub fn insert_after_script_info(&mut self, section: AssSection) {
+        let idx = self
+            .sections
+            .iter()
+            .position(|s| matches!(s, AssSection::ScriptInfo(_)))
+            .map_or(0, |idx| idx + 1);
+        self.sections.insert(idx, section);
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,90 @@
+#[derive(Debug, Clone)]
+pub enum InfoEntry {
+    Property { key: String, value: String },
+    Raw(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct ScriptInfo {
+    pub header: String,
+    pub entries: Vec<InfoEntry>,
+}
+
+impl ScriptInfo {
+    const COMMENT_PREFIX: char = ';';
+    const KEY_SEPARATOR: char = ':';
+
+    pub fn new(header: &str) -> Self {
+        Self {
+            header: header.to_string(),
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn push_line(&mut self, line: &str) {
+        let entry = match line.split_once(Self::KEY_SEPARATOR) {
+            Some((key, value))
+                if !line.starts_with(Self::COMMENT_PREFIX) && !key.trim().is_empty() =>
+            {
+                InfoEntry::Property {
+                    key: key.to_string(),
+                    value: value.to_string(),
+                }
+            }
+            _ => InfoEntry::Raw(line.to_string()),
+        };
+        self.entries.push(entry);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.entries.iter().find_map(|entry| match entry {
+            InfoEntry::Property { key, value } if key.trim() == name => Some(value.trim()),
+            _ => None,
+        })
+    }
+
+    fn find_mut(&mut self, name: &str) -> Option<&mut String> {
+        self.entries.iter_mut().find_map(|entry| match entry {
+            InfoEntry::Property { key, value } if key.trim() == name => Some(value),
+            _ => None,
+        })
+    }
+
+    fn is_blank(entry: &InfoEntry) -> bool {
+        matches!(entry, InfoEntry::Raw(line) if line.trim().is_empty())
+    }
+
+    fn content_end(&self) -> usize {
+        self.entries
+            .iter()
+            .rposition(|entry| !Self::is_blank(entry))
+            .map_or(0, |idx| idx + 1)
+    }
+
+    pub fn set(&mut self, name: &str, new_value: &str) {
+        let formatted = format!(" {}", new_value);
+        match self.find_mut(name) {
+            Some(value) => *value = formatted,
+            None => {
+                let idx = self.content_end();
+                let entry = InfoEntry::Property {
+                    key: name.to_string(),
+                    value: formatted,
+                };
+                self.entries.insert(idx, entry);
+            }
+        }
+    }
+
+    pub fn to_lines(&self) -> Vec<String> {
+        let entries = self.entries.iter().map(|entry| match entry {
+            InfoEntry::Property { key, value } => {
+                format!("{}{}{}", key, Self::KEY_SEPARATOR, value)
+            }
+            InfoEntry::Raw(line) => line.clone(),
+        });
+        std::iter::once(self.header.clone())
+            .chain(entries)
+            .collect()
+    }
+}
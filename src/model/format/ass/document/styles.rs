@@ -0,0 +1,142 @@
+use crate::model::{
+    format::ass::{style_ass::AssStyle, style_format::StyleFormat},
+    parse_error::ParseRes,
+};
+
+#[derive(Debug, Clone)]
+pub enum StyleEntry {
+    Format(String),
+    Style(AssStyle),
+    Raw(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct StylesSection {
+    pub header: String,
+    pub format: StyleFormat,
+    pub entries: Vec<StyleEntry>,
+}
+
+impl StylesSection {
+    const V4_PLUS_HEADER: &str = "[V4+ Styles]";
+    const LEGACY_HEADER: &str = "[V4 Styles]";
+    const FORMAT_PREFIX: &str = "Format:";
+    const STYLE_PREFIX: &str = "Style:";
+
+    pub fn new(header: &str) -> Self {
+        let format = match header.trim().eq_ignore_ascii_case(Self::LEGACY_HEADER) {
+            true => StyleFormat::ssa(),
+            false => StyleFormat::default(),
+        };
+        Self {
+            header: header.to_string(),
+            format,
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn v4_plus() -> Self {
+        let mut section = Self::new(Self::V4_PLUS_HEADER);
+        section
+            .entries
+            .push(StyleEntry::Format(section.format.to_line()));
+        section
+    }
+
+    pub fn is_styles_header(header: &str) -> bool {
+        let header = header.trim();
+        header.eq_ignore_ascii_case(Self::V4_PLUS_HEADER)
+            || header.eq_ignore_ascii_case(Self::LEGACY_HEADER)
+    }
+
+    pub fn push_line(&mut self, line: &str) -> ParseRes<()> {
+        let trimmed = line.trim_start();
+        let entry = match trimmed {
+            _ if trimmed.starts_with(Self::FORMAT_PREFIX) => {
+                self.format = StyleFormat::parse(trimmed, self.format.is_legacy())?;
+                StyleEntry::Format(line.to_string())
+            }
+            _ if trimmed.starts_with(Self::STYLE_PREFIX) => {
+                StyleEntry::Style(AssStyle::parse(trimmed, &self.format)?)
+            }
+            _ => StyleEntry::Raw(line.to_string()),
+        };
+        self.entries.push(entry);
+        Ok(())
+    }
+
+    pub fn is_legacy(&self) -> bool {
+        self.format.is_legacy()
+    }
+
+    pub fn styles(&self) -> impl Iterator<Item = &AssStyle> {
+        self.entries.iter().filter_map(|entry| match entry {
+            StyleEntry::Style(style) => Some(style),
+            _ => None,
+        })
+    }
+
+    pub fn styles_mut(&mut self) -> impl Iterator<Item = &mut AssStyle> {
+        self.entries.iter_mut().filter_map(|entry| match entry {
+            StyleEntry::Style(style) => Some(style),
+            _ => None,
+        })
+    }
+
+    pub fn style_mut(&mut self, name: &str) -> Option<&mut AssStyle> {
+        self.styles_mut().find(|style| style.name == name)
+    }
+
+    fn definitions_end(&self) -> usize {
+        self.entries
+            .iter()
+            .rposition(|entry| !matches!(entry, StyleEntry::Raw(_)))
+            .map_or(0, |idx| idx + 1)
+    }
+
+    pub fn add_styles(&mut self, styles: Vec<AssStyle>) {
+        for style in styles {
+            match self.style_mut(&style.name) {
+                Some(existing) => *existing = style,
+                None => {
+                    let idx = self.definitions_end();
+                    self.entries.insert(idx, StyleEntry::Style(style));
+                }
+            }
+        }
+    }
+
+    fn replace_format_line(&mut self) {
+        let line = self.format.to_line();
+        match self
+            .entries
+            .iter_mut()
+            .find(|entry| matches!(entry, StyleEntry::Format(_)))
+        {
+            Some(entry) => *entry = StyleEntry::Format(line),
+            None => self.entries.insert(0, StyleEntry::Format(line)),
+        }
+    }
+
+    pub fn convert_to_v4_plus(&mut self) {
+        if !self.is_legacy() {
+            return;
+        }
+        self.header = Self::V4_PLUS_HEADER.to_string();
+        self.format = StyleFormat::default();
+        self.replace_format_line();
+        for style in self.styles_mut() {
+            style.convert_to_v4_plus();
+        }
+    }
+
+    pub fn to_lines(&self) -> Vec<String> {
+        let entries = self.entries.iter().map(|entry| match entry {
+            StyleEntry::Format(line) | StyleEntry::Raw(line) => line.clone(),
+            StyleEntry::Style(style) => style.to_line(&self.format),
+        });
+        std::iter::once(self.header.clone())
+            .chain(entries)
+            .collect()
+    }
+}
@@ -0,0 +1,392 @@
+use crate::model::{parse_error::ParserError, timestamp::Timestamp};
+
+use super::attachments::AttachmentKind;
+use super::*;
+
+struct TestCase {
+    name: &'static str,
+    input: &'static [&'static str],
+}
+
+static FULL_SCRIPT_CASE: TestCase = TestCase {
+    name: "round trips a full script",
+    input: &[
+        "[Script Info]",
+        "; Script generated by Aegisub",
+        "Title: Round Trip",
+        "ScriptType: v4.00+",
+        "",
+        "[V4+ Styles]",
+        "Format: Name, Fontname, Fontsize, PrimaryColour",
+        "Style: Default,Arial,20,&H00FFFFFF",
+        "",
+        "[Events]",
+        "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text",
+        "Dialogue: 0,0:00:01.00,0:00:02.00,Default,,0000,0000,0000,,Line 1",
+        "Dialogue: 0,0:00:03.00,0:00:04.00,Default,ADDITIONAL SCENE,0010,0020,0030,,Line 2",
+        "",
+        "[Aegisub Project Garbage]",
+        "Video File: video.mkv",
+    ],
+};
+
+static HEADERLESS_CASE: TestCase = TestCase {
+    name: "round trips dialogue lines without section headers",
+    input: &[
+        "Dialogue: 0,0:00:01.00,0:00:02.00,Default,,0,0,0,,Line 1",
+        "Dialogue: 0,0:00:03.00,0:00:04.00,Default,,0,0,0,,Line 2",
+    ],
+};
+
+static NON_CANONICAL_CASE: TestCase = TestCase {
+    name: "keeps unedited lines byte-identical",
+    input: &[
+        "[Events]",
+        "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text",
+        "Dialogue: 1,00:00:01.00,00:00:02.00, Sign , Narrator ,15,0020,120,Banner;30, padded text ",
+        "Dialogue: 0,0:00:03.00,0:00:04.00,Default,,0000,0000,0000,,Text, with, commas",
+    ],
+};
+
+static SSA_FORMAT_CASE: TestCase = TestCase {
+    name: "reads SSA columns and an omitted Effect column",
+    input: &[
+        "[Events]",
+        "Format: Marked, Start, End, Style, Name, MarginL, MarginR, MarginV, Text",
+        "Dialogue: Marked=0,0:00:01.00,0:00:02.00,Default,ADDITIONAL SCENE,0,0,0,Hello, world",
+    ],
+};
+
+static REORDERED_FORMAT_CASE: TestCase = TestCase {
+    name: "reads reordered columns",
+    input: &[
+        "[Events]",
+        "Format: Start, End, Style, Layer, Text",
+        "Dialogue: 0:00:01.00,0:00:02.00,Sign,3,Reordered",
+    ],
+};
+
+static MISSING_COLUMN_CASE: TestCase = TestCase {
+    name: "fails when the format has no End column",
+    input: &[
+        "[Events]",
+        "Format: Layer, Start, Style, Text",
+        "Dialogue: 0,0:00:01.00,Default,Hello",
+    ],
+};
+
+static UNKNOWN_COLUMN_CASE: TestCase = TestCase {
+    name: "fails on an unknown column",
+    input: &["[Events]", "Format: Layer, Start, End, Colour, Text"],
+};
+
+static EVENT_KINDS_CASE: TestCase = TestCase {
+    name: "round trips every event kind",
+    input: &[
+        "[Events]",
+        "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text",
+        "Comment: 0,0:00:00.00,0:00:01.00,Default,,0,0,0,,Translator note",
+        "Dialogue: 0,0:00:01.00,0:00:02.00,Default,,0,0,0,,Line",
+        "Picture: 0,0:00:02.00,0:00:03.00,Default,,0,0,0,,image.png",
+        "Sound: 0,0:00:03.00,0:00:04.00,Default,,0,0,0,,sound.wav",
+        "Movie: 0,0:00:04.00,0:00:05.00,Default,,0,0,0,,movie.avi",
+        "Command: 0,0:00:05.00,0:00:06.00,Default,,0,0,0,,command",
+    ],
+};
+
+static MISSING_FIELDS_CASE: TestCase = TestCase {
+    name: "fails when a dialogue has missing fields",
+    input: &["[Events]", "Dialogue: 0,0:00:01.00,NotEnoughFields"],
+};
+
+static INVALID_TIMESTAMP_CASE: TestCase = TestCase {
+    name: "fails on a comma separated timestamp",
+    input: &[
+        "[Events]",
+        "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text",
+        "Dialogue: 0,0:00:01.00,0:00:02.00,Default,,0,0,0,,Line 1",
+        "Dialogue: 0,0:00:1,00,0:00:04.00,Default,,0,0,0,,Line 2",
+    ],
+};
+
+static MISSING_CENTIS_CASE: TestCase = TestCase {
+    name: "fails on a timestamp without centiseconds",
+    input: &["Dialogue: 0,0:00:01,0:00:02.00,Default,,0,0,0,,Line 1"],
+};
+
+static INVALID_NUMBER_CASE: TestCase = TestCase {
+    name: "fails on a non numeric margin",
+    input: &[
+        "[Events]",
+        "Dialogue: 0,0:00:01.00,0:00:02.00,Default,,0,1O,0,,Line 1",
+    ],
+};
+
+static UNKNOWN_SECTION_CASE: TestCase = TestCase {
+    name: "fails on an unterminated section header",
+    input: &["[Script Info]", "Title: Broken", "", "[Events"],
+};
+
+static ATTACHMENTS_CASE: TestCase = TestCase {
+    name: "keeps fonts and graphics placed after the events",
+    input: &[
+        "[Events]",
+        "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text",
+        "Dialogue: 0,0:00:01.00,0:00:02.00,Default,,0,0,0,,Line 1",
+        "",
+        "[Fonts]",
+        "fontname: Sign_0.ttf",
+        "3'EB",
+        "[!!!!!]",
+        "",
+        "[Graphics]",
+        "filename: logo.png",
+        "3'EB",
+    ],
+};
+
+static BROKEN_ATTACHMENT_CASE: TestCase = TestCase {
+    name: "fails to decode truncated uuencoded data",
+    input: &["[Fonts]", "fontname: broken.ttf", "3'EB3"],
+};
+
+fn to_lines(test_case: &TestCase) -> Vec<String> {
+    test_case.input.iter().map(|s| s.to_string()).collect()
+}
+
+fn run_round_trip(test_case: &TestCase) -> ParseRes<()> {
+    let input = to_lines(test_case);
+    let doc = AssDocument::parse(&input)?;
+    assert_eq!(doc.serialize(), input, "Failed at case: {}", test_case.name);
+    Ok(())
+}
+
+#[test]
+fn test_round_trip_full_script() -> ParseRes<()> {
+    run_round_trip(&FULL_SCRIPT_CASE)
+}
+
+#[test]
+fn test_round_trip_headerless() -> ParseRes<()> {
+    run_round_trip(&HEADERLESS_CASE)
+}
+
+#[test]
+fn test_round_trip_non_canonical() -> ParseRes<()> {
+    run_round_trip(&NON_CANONICAL_CASE)
+}
+
+#[test]
+fn test_edit_changes_only_edited_field() -> ParseRes<()> {
+    let mut doc = AssDocument::parse(&to_lines(&NON_CANONICAL_CASE))?;
+    if let Some(line) = doc.dialogues_mut().next() {
+        line.start += Timestamp::from_millis(1_000);
+    }
+    let lines = doc.serialize();
+    assert_eq!(
+        lines[2],
+        "Dialogue: 1,0:00:02.00,00:00:02.00, Sign , Narrator ,15,0020,120,Banner;30, padded text "
+    );
+    assert_eq!(lines[3], NON_CANONICAL_CASE.input[3]);
+    Ok(())
+}
+
+#[test]
+fn test_round_trip_ssa_format() -> ParseRes<()> {
+    run_round_trip(&SSA_FORMAT_CASE)
+}
+
+#[test]
+fn test_column_map_ssa_format() -> ParseRes<()> {
+    let doc = AssDocument::parse(&to_lines(&SSA_FORMAT_CASE))?;
+    let line = doc.dialogues().next().expect("missing dialogue");
+    assert_eq!(line.layer, "Marked=0");
+    assert_eq!(line.name, "ADDITIONAL SCENE");
+    assert_eq!(line.effect, "");
+    assert_eq!(line.text, "Hello, world");
+    Ok(())
+}
+
+#[test]
+fn test_column_map_reordered() -> ParseRes<()> {
+    run_round_trip(&REORDERED_FORMAT_CASE)?;
+    let doc = AssDocument::parse(&to_lines(&REORDERED_FORMAT_CASE))?;
+    let line = doc.dialogues().next().expect("missing dialogue");
+    assert_eq!(line.start, Timestamp::from_millis(1_000));
+    assert_eq!(line.style, "Sign");
+    assert_eq!(line.layer, "3");
+    assert_eq!(line.text, "Reordered");
+    Ok(())
+}
+
+#[test]
+fn test_error_missing_column() {
+    let result = AssDocument::parse(&to_lines(&MISSING_COLUMN_CASE));
+    match result {
+        Err(ParserError::MissingColumn { column }) => assert_eq!(column, "End"),
+        _ => panic!("Expected MissingColumn(End), got {:?}", result),
+    }
+}
+
+#[test]
+fn test_error_unknown_column() {
+    let result = AssDocument::parse(&to_lines(&UNKNOWN_COLUMN_CASE));
+    match result {
+        Err(ParserError::UnknownColumn { column }) => assert_eq!(column, "Colour"),
+        _ => panic!("Expected UnknownColumn(Colour), got {:?}", result),
+    }
+}
+
+#[test]
+fn test_round_trip_event_kinds() -> ParseRes<()> {
+    run_round_trip(&EVENT_KINDS_CASE)?;
+    let doc = AssDocument::parse(&to_lines(&EVENT_KINDS_CASE))?;
+    assert_eq!(doc.dialogues().count(), 1);
+    Ok(())
+}
+
+#[test]
+fn test_typed_access() -> ParseRes<()> {
+    let doc = AssDocument::parse(&to_lines(&FULL_SCRIPT_CASE))?;
+    let info = doc.script_info().expect("missing script info");
+    assert_eq!(info.get("Title"), Some("Round Trip"));
+    let margins: Vec<&str> = doc.dialogues().map(|l| l.margin_v.as_str()).collect();
+    assert_eq!(margins, vec!["0000", "0030"]);
+    assert_eq!(doc.events().count(), 1);
+    Ok(())
+}
+
+#[test]
+fn test_script_info_set() -> ParseRes<()> {
+    let mut doc = AssDocument::parse(&to_lines(&FULL_SCRIPT_CASE))?;
+    let info = doc.script_info_mut().expect("missing script info");
+    info.set("Title", "Edited");
+    info.set("PlayResX", "640");
+    let lines = doc.serialize();
+    assert_eq!(lines[2], "Title: Edited");
+    assert_eq!(lines[4], "PlayResX: 640");
+    assert_eq!(lines[5], "");
+    Ok(())
+}
+
+#[test]
+fn test_error_missing_fields() {
+    let result = AssDocument::parse(&to_lines(&MISSING_FIELDS_CASE));
+    match result {
+        Err(ParserError::MissingFields { found, .. }) => assert_eq!(found, 3),
+        _ => panic!("Expected MissingFields(3), got {:?}", result),
+    }
+}
+
+#[test]
+fn test_error_invalid_timestamp() {
+    let result = AssDocument::parse(&to_lines(&INVALID_TIMESTAMP_CASE));
+    match result {
+        Err(ParserError::InvalidTimestamp { location, found }) => {
+            assert_eq!(found, "0:00:1");
+            assert_eq!(location.line, 4);
+            assert_eq!(location.column, 12);
+            assert_eq!(location.source, INVALID_TIMESTAMP_CASE.input[3]);
+        }
+        _ => panic!("Expected InvalidTimestamp, got {:?}", result),
+    }
+}
+
+#[test]
+fn test_error_missing_centiseconds() {
+    let result = AssDocument::parse(&to_lines(&MISSING_CENTIS_CASE));
+    match result {
+        Err(ParserError::InvalidTimestamp { location, found }) => {
+            assert_eq!(found, "0:00:01");
+            assert_eq!(location.line, 1);
+        }
+        _ => panic!("Expected InvalidTimestamp, got {:?}", result),
+    }
+}
+
+#[test]
+fn test_error_invalid_number() {
+    let result = AssDocument::parse(&to_lines(&INVALID_NUMBER_CASE));
+    match result {
+        Err(ParserError::InvalidNumber { location, found }) => {
+            assert_eq!(found, "1O");
+            assert_eq!(location.line, 2);
+            assert_eq!(location.width, 2);
+        }
+        _ => panic!("Expected InvalidNumber, got {:?}", result),
+    }
+}
+
+#[test]
+fn test_error_unknown_section() {
+    let result = AssDocument::parse(&to_lines(&UNKNOWN_SECTION_CASE));
+    match result {
+        Err(ParserError::UnknownSection { location }) => {
+            assert_eq!(location.line, 4);
+            assert_eq!(location.source, "[Events");
+        }
+        _ => panic!("Expected UnknownSection, got {:?}", result),
+    }
+}
+
+#[test]
+fn test_error_path_is_kept_once_set() {
+    let error = AssDocument::parse(&to_lines(&INVALID_TIMESTAMP_CASE))
+        .unwrap_err()
+        .with_path("b.ass")
+        .with_path("a.ass");
+    assert_eq!(error.location().map(|l| l.path.as_str()), Some("b.ass"));
+}
+
+#[test]
+fn test_round_trip_attachments() -> ParseRes<()> {
+    run_round_trip(&ATTACHMENTS_CASE)?;
+    let doc = AssDocument::parse(&to_lines(&ATTACHMENTS_CASE))?;
+    assert_eq!(doc.dialogues().count(), 1);
+    let names: Vec<&str> = doc.attachments().map(|a| a.name.as_str()).collect();
+    assert_eq!(names, vec!["Sign_0.ttf", "logo.png"]);
+    Ok(())
+}
+
+#[test]
+fn test_decode_attachment() -> ParseRes<()> {
+    let doc = AssDocument::parse(&to_lines(&ATTACHMENTS_CASE))?;
+    let logo = doc.attachments().nth(1).expect("missing graphic");
+    assert_eq!(logo.kind, AttachmentKind::Graphic);
+    assert_eq!(logo.decode()?, b"Hi!");
+    assert_eq!(logo.size(), 3);
+    Ok(())
+}
+
+#[test]
+fn test_embed_attachment() -> ParseRes<()> {
+    let mut doc = AssDocument::parse(&to_lines(&FULL_SCRIPT_CASE))?;
+    let bytes: Vec<u8> = (0..=255).collect();
+    doc.embed(Attachment::from_bytes(
+        AttachmentKind::Font,
+        "Font.ttf",
+        &bytes,
+    ));
+    let lines = doc.serialize();
+    let start = FULL_SCRIPT_CASE.input.len();
+    assert_eq!(lines[start], "[Fonts]");
+    assert_eq!(lines[start + 1], "fontname: Font.ttf");
+    assert!(lines[start + 2..].iter().all(|line| line.len() <= 80));
+
+    let reparsed = AssDocument::parse(&lines)?;
+    let font = reparsed.attachments().next().expect("missing font");
+    assert_eq!(font.decode()?, bytes);
+    assert_eq!(font.size(), bytes.len());
+    Ok(())
+}
+
+#[test]
+fn test_error_invalid_attachment() -> ParseRes<()> {
+    let doc = AssDocument::parse(&to_lines(&BROKEN_ATTACHMENT_CASE))?;
+    let result = doc.attachments().next().expect("missing font").decode();
+    match result {
+        Err(ParserError::InvalidAttachment { name }) => assert_eq!(name, "broken.ttf"),
+        _ => panic!("Expected InvalidAttachment, got {:?}", result),
+    }
+    Ok(())
+}
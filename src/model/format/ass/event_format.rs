@@ -0,0 +1,83 @@
+use crate::model::{
+    format::ass::line_ass::AssField,
+    parse_error::{ParseRes, ParserError},
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventFormat {
+    columns: Vec<AssField>,
+}
+
+impl Default for EventFormat {
+    fn default() -> Self {
+        Self {
+            columns: AssField::ALL.to_vec(),
+        }
+    }
+}
+
+impl EventFormat {
+    const FORMAT_PREFIX: &str = "Format:";
+    const REQUIRED_COLUMNS: [AssField; 3] = [AssField::Start, AssField::End, AssField::Text];
+
+    fn parse_column(name: &str) -> ParseRes<AssField> {
+        AssField::from_name(name).ok_or_else(|| ParserError::UnknownColumn {
+            column: name.trim().to_string(),
+        })
+    }
+
+    fn check_required(&self) -> ParseRes<()> {
+        match Self::REQUIRED_COLUMNS
+            .iter()
+            .find(|field| !self.columns.contains(field))
+        {
+            Some(field) => Err(ParserError::MissingColumn {
+                column: field.name().to_string(),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    fn check_text_last(&self) -> ParseRes<()> {
+        match self.columns.last() == Some(&AssField::Text) {
+            true => Ok(()),
+            false => Err(ParserError::MisplacedText),
+        }
+    }
+
+    pub fn parse(line: &str) -> ParseRes<Self> {
+        let body = line
+            .trim()
+            .strip_prefix(Self::FORMAT_PREFIX)
+            .unwrap_or(line);
+        let columns = body
+            .split(',')
+            .map(Self::parse_column)
+            .collect::<ParseRes<Vec<_>>>()?;
+        let format = Self { columns };
+        format.check_required()?;
+        format.check_text_last()?;
+        Ok(format)
+    }
+
+    pub fn to_line(&self) -> String {
+        let names: Vec<&str> = self.columns.iter().map(|column| column.name()).collect();
+        format!("{} {}", Self::FORMAT_PREFIX, names.join(", "))
+    }
+
+    pub fn len(&self) -> usize {
+        self.columns.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.columns.is_empty()
+    }
+
+    pub fn columns(&self) -> &[AssField] {
+        &self.columns
+    }
+
+    pub fn position(&self, field: AssField) -> Option<usize> {
+        self.columns.iter().position(|column| *column == field)
+    }
+}
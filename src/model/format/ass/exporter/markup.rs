@@ -0,0 +1,245 @@
+use std::collections::HashMap;
+
+use crate::model::format::ass::{
+    exporter::ExportTarget,
+    style_ass::{AssColor, AssStyle},
+    text::{
+        OverrideItem, TextSegment,
+        tag::{Tag, TagKind},
+    },
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Markup {
+    Italic,
+    Bold,
+    Underline,
+    Colour(AssColor),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct MarkupState {
+    italic: bool,
+    bold: bool,
+    underline: bool,
+    colour: Option<AssColor>,
+}
+
+impl MarkupState {
+    fn from_style(style: &AssStyle) -> Self {
+        Self {
+            italic: style.italic,
+            bold: style.bold,
+            underline: style.underline,
+            colour: None,
+        }
+    }
+
+    fn wanted(&self) -> Vec<Markup> {
+        let toggles = [
+            (self.italic, Markup::Italic),
+            (self.bold, Markup::Bold),
+            (self.underline, Markup::Underline),
+        ];
+        toggles
+            .into_iter()
+            .filter_map(|(on, markup)| on.then_some(markup))
+            .chain(self.colour.map(Markup::Colour))
+            .collect()
+    }
+}
+
+pub struct MarkupWriter<'a> {
+    target: ExportTarget,
+    styles: &'a HashMap<String, AssStyle>,
+    style: AssStyle,
+    state: MarkupState,
+    open: Vec<Markup>,
+    colours: Vec<AssColor>,
+    pending: String,
+    out: String,
+}
+
+impl<'a> MarkupWriter<'a> {
+    const LINE_BREAK: &'static str = "\n";
+    const SPACE: &'static str = " ";
+    const COLOUR_CLASS_PREFIX: &'static str = "c";
+
+    pub fn new(target: ExportTarget, styles: &'a HashMap<String, AssStyle>, name: &str) -> Self {
+        let style = styles.get(name).cloned().unwrap_or_default();
+        Self {
+            target,
+            styles,
+            state: MarkupState::from_style(&style),
+            style,
+            open: Vec::new(),
+            colours: Vec::new(),
+            pending: String::new(),
+            out: String::new(),
+        }
+    }
+
+    pub fn colour_class(colour: AssColor) -> String {
+        format!(
+            "{}{:02x}{:02x}{:02x}",
+            Self::COLOUR_CLASS_PREFIX,
+            colour.r,
+            colour.g,
+            colour.b
+        )
+    }
+
+    pub fn css_colour(colour: AssColor) -> String {
+        format!("#{:02x}{:02x}{:02x}", colour.r, colour.g, colour.b)
+    }
+
+    fn open_tag(&self, markup: Markup) -> String {
+        match (markup, self.target) {
+            (_, ExportTarget::SubViewer) => String::new(),
+            (Markup::Italic, ExportTarget::MicroDvd(_)) => "{y:i}".to_string(),
+            (Markup::Bold, ExportTarget::MicroDvd(_)) => "{y:b}".to_string(),
+            (Markup::Underline, ExportTarget::MicroDvd(_)) => "{y:u}".to_string(),
+            (Markup::Colour(colour), ExportTarget::MicroDvd(_)) => {
+                format!("{{c:${:02X}{:02X}{:02X}}}", colour.b, colour.g, colour.r)
+            }
+            (Markup::Italic, ExportTarget::Ttml(..)) => {
+                "<span tts:fontStyle=\"italic\">".to_string()
+            }
+            (Markup::Bold, ExportTarget::Ttml(..)) => "<span tts:fontWeight=\"bold\">".to_string(),
+            (Markup::Underline, ExportTarget::Ttml(..)) => {
+                "<span tts:textDecoration=\"underline\">".to_string()
+            }
+            (Markup::Colour(colour), ExportTarget::Ttml(..)) => {
+                format!("<span tts:color=\"{}\">", Self::css_colour(colour))
+            }
+            (Markup::Italic, _) => "<i>".to_string(),
+            (Markup::Bold, _) => "<b>".to_string(),
+            (Markup::Underline, _) => "<u>".to_string(),
+            (Markup::Colour(colour), ExportTarget::Srt) => {
+                format!("<font color=\"{}\">", Self::css_colour(colour))
+            }
+            (Markup::Colour(colour), _) => {
+                format!("<c.{}>", Self::colour_class(colour))
+            }
+        }
+    }
+
+    fn close_tag(&self, markup: Markup) -> &'static str {
+        match (markup, self.target) {
+            (_, ExportTarget::MicroDvd(_) | ExportTarget::SubViewer) => "",
+            (_, ExportTarget::Ttml(..)) => "</span>",
+            (Markup::Italic, _) => "</i>",
+            (Markup::Bold, _) => "</b>",
+            (Markup::Underline, _) => "</u>",
+            (Markup::Colour(_), ExportTarget::Srt) => "</font>",
+            (Markup::Colour(_), _) => "</c>",
+        }
+    }
+
+    fn close_from(&mut self, idx: usize) {
+        while self.open.len() > idx {
+            let markup = self.open.pop().unwrap();
+            self.out.push_str(self.close_tag(markup));
+        }
+    }
+
+    fn sync_markup(&mut self) {
+        let wanted = self.state.wanted();
+        let kept = self
+            .open
+            .iter()
+            .take_while(|markup| wanted.contains(markup))
+            .count();
+        self.close_from(kept);
+        self.out.push_str(&std::mem::take(&mut self.pending));
+        for markup in wanted {
+            if let Markup::Colour(colour) = markup
+                && !self.colours.contains(&colour)
+            {
+                self.colours.push(colour);
+            }
+            if !self.open.contains(&markup) {
+                self.out.push_str(&self.open_tag(markup));
+                self.open.push(markup);
+            }
+        }
+    }
+
+    fn escape(&self, text: &str) -> String {
+        match self.target {
+            ExportTarget::Vtt | ExportTarget::Ttml(..) => text
+                .replace('&', "&amp;")
+                .replace('<', "&lt;")
+                .replace('>', "&gt;"),
+            _ => text.to_string(),
+        }
+    }
+
+    fn push_text(&mut self, text: &str) {
+        let escaped = self.escape(text);
+        if text.trim().is_empty() {
+            self.pending.push_str(&escaped);
+            return;
+        }
+        self.sync_markup();
+        self.out.push_str(&escaped);
+    }
+
+    fn colour_override(&self, colour: Option<AssColor>) -> Option<AssColor> {
+        colour.filter(|c| {
+            let primary = self.style.primary_colour;
+            (c.r, c.g, c.b) != (primary.r, primary.g, primary.b)
+        })
+    }
+
+    fn apply_tag(&mut self, tag: &Tag) {
+        match &tag.kind {
+            TagKind::Italic(on) => self.state.italic = *on,
+            TagKind::Bold(weight) => self.state.bold = *weight != 0 && *weight != 400,
+            TagKind::Underline(on) => self.state.underline = *on,
+            TagKind::Colour { index: 1, colour } => {
+                self.state.colour = self.colour_override(Some(*colour))
+            }
+            TagKind::Other { name, args } if args.is_empty() && (name == "c" || name == "1c") => {
+                self.state.colour = None
+            }
+            TagKind::Reset(name) => {
+                let style = name.as_deref().and_then(|n| self.styles.get(n.trim()));
+                self.state = MarkupState::from_style(style.unwrap_or(&self.style));
+            }
+            _ => {}
+        }
+    }
+
+    pub fn push_segment(&mut self, segment: &TextSegment) {
+        match segment {
+            TextSegment::Override(items) => {
+                for item in items {
+                    if let OverrideItem::Tag(tag) = item {
+                        self.apply_tag(tag);
+                    }
+                }
+            }
+            TextSegment::Text(text) => self.push_text(text),
+            TextSegment::HardBreak => {
+                self.close_from(0);
+                self.pending.clear();
+                self.out.push_str(Self::LINE_BREAK);
+            }
+            TextSegment::SoftBreak | TextSegment::HardSpace => self.push_text(Self::SPACE),
+            TextSegment::Drawing(_) => {}
+        }
+    }
+
+    pub fn finish(mut self) -> (String, Vec<AssColor>) {
+        self.close_from(0);
+        let text = self
+            .out
+            .split(Self::LINE_BREAK)
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<&str>>()
+            .join(Self::LINE_BREAK);
+        (text, self.colours)
+    }
+}
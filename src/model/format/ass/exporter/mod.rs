@@ -0,0 +1,461 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::model::{
+    format::{
+        ass::{
+            document::AssDocument,
+            exporter::markup::MarkupWriter,
+            line_ass::AssLine,
+            style_ass::{AssColor, AssStyle},
+            text::{AssText, TextSegment, tag::TagKind},
+        },
+        microdvd::{
+            document::MicroDvdDocument, line_microdvd::MicroDvdLine, parser::MicroDvdParser,
+        },
+        srt::{line_srt::SrtLine, parser::SrtParser},
+        subviewer::{
+            document::SubViewerDocument, line_subviewer::SubViewerLine, parser::SubViewerParser,
+        },
+        ttml::{
+            document::{TtmlDocument, TtmlElement, TtmlParagraph, TtmlProfile, TtmlTiming},
+            parser::TtmlParser,
+        },
+        vtt::{
+            document::{VttBlock, VttDocument},
+            line_vtt::VttLine,
+            parser::VttParser,
+        },
+    },
+    timestamp::Timestamp,
+};
+
+pub mod markup;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportTarget {
+    Srt,
+    Vtt,
+    MicroDvd(f64),
+    SubViewer,
+    Ttml(TtmlProfile, TtmlTiming),
+}
+
+#[derive(Debug, Clone)]
+struct ExportCue {
+    start: Timestamp,
+    end: Timestamp,
+    alignment: u8,
+    style: String,
+    margins: [i32; 3],
+    text: String,
+    colours: Vec<AssColor>,
+}
+
+pub struct Exporter {
+    target: ExportTarget,
+    styles: Vec<String>,
+}
+
+impl Exporter {
+    const DEFAULT_ALIGNMENT: u8 = 2;
+    const LINE_BREAK: &str = "\n";
+    const VTT_SIGNATURE: &str = "WEBVTT";
+    const VTT_STYLE_KEYWORD: &str = "STYLE";
+    const DEFAULT_PLAY_RES: (f64, f64) = (384.0, 288.0);
+    const TTML_CELL_ROWS: f64 = 15.0;
+
+    pub fn new(target: ExportTarget) -> Self {
+        Self {
+            target,
+            styles: Vec::new(),
+        }
+    }
+
+    pub fn with_styles(mut self, styles: Vec<String>) -> Self {
+        self.styles = styles;
+        self
+    }
+
+    fn includes_style(&self, style: &str) -> bool {
+        self.styles.is_empty()
+            || self
+                .styles
+                .iter()
+                .any(|s| s.trim().eq_ignore_ascii_case(style.trim()))
+    }
+
+    fn is_sign(text: &AssText) -> bool {
+        let positioned = text.tags().any(|tag| {
+            matches!(
+                tag.kind,
+                TagKind::Pos { .. }
+                    | TagKind::Move { .. }
+                    | TagKind::Org { .. }
+                    | TagKind::Clip { .. }
+            )
+        });
+        let drawing = text
+            .segments
+            .iter()
+            .any(|segment| matches!(segment, TextSegment::Drawing(_)));
+        positioned || drawing
+    }
+
+    fn alignment(text: &AssText, style: Option<&AssStyle>) -> u8 {
+        text.tags()
+            .find_map(|tag| match tag.kind {
+                TagKind::Alignment(alignment) => Some(alignment),
+                _ => None,
+            })
+            .or(style.map(|s| s.alignment))
+            .unwrap_or(Self::DEFAULT_ALIGNMENT)
+    }
+
+    fn to_cue(&self, line: &AssLine, styles: &HashMap<String, AssStyle>) -> Option<ExportCue> {
+        if line.end <= line.start || !self.includes_style(&line.style) {
+            return None;
+        }
+        let text = AssText::parse(&line.text);
+        if Self::is_sign(&text) {
+            return None;
+        }
+        let mut writer = MarkupWriter::new(self.target, styles, line.style.trim());
+        for segment in &text.segments {
+            writer.push_segment(segment);
+        }
+        let (markup, colours) = writer.finish();
+        if markup.is_empty() {
+            return None;
+        }
+        Some(ExportCue {
+            start: line.start,
+            end: line.end,
+            alignment: Self::alignment(&text, styles.get(line.style.trim())),
+            style: line.style.trim().to_string(),
+            margins: [&line.margin_l, &line.margin_r, &line.margin_v]
+                .map(|margin| margin.trim().parse().unwrap_or(0)),
+            text: markup,
+            colours,
+        })
+    }
+
+    fn flatten_group(cues: &[ExportCue]) -> Vec<ExportCue> {
+        let mut bounds: Vec<Timestamp> = cues.iter().flat_map(|c| [c.start, c.end]).collect();
+        bounds.sort();
+        bounds.dedup();
+        let mut flat: Vec<ExportCue> = Vec::new();
+        for window in bounds.windows(2) {
+            let (from, to) = (window[0], window[1]);
+            let active: Vec<&str> = cues
+                .iter()
+                .filter(|c| c.start <= from && c.end >= to)
+                .map(|c| c.text.as_str())
+                .collect();
+            if active.is_empty() {
+                continue;
+            }
+            let text = active.join(Self::LINE_BREAK);
+            match flat.last_mut() {
+                Some(last) if last.end == from && last.text == text => last.end = to,
+                _ => flat.push(ExportCue {
+                    start: from,
+                    end: to,
+                    alignment: cues[0].alignment,
+                    style: cues[0].style.clone(),
+                    margins: cues[0].margins,
+                    text,
+                    colours: Vec::new(),
+                }),
+            }
+        }
+        flat
+    }
+
+    fn flatten_overlaps(cues: Vec<ExportCue>) -> Vec<ExportCue> {
+        let mut groups: BTreeMap<u8, Vec<ExportCue>> = BTreeMap::new();
+        for cue in cues {
+            groups.entry(cue.alignment).or_default().push(cue);
+        }
+        let mut flat: Vec<ExportCue> = groups
+            .values()
+            .flat_map(|group| Self::flatten_group(group))
+            .collect();
+        flat.sort_by_key(|cue| cue.start);
+        flat
+    }
+
+    fn srt_text(cue: &ExportCue) -> String {
+        match cue.alignment {
+            Self::DEFAULT_ALIGNMENT => cue.text.clone(),
+            alignment => format!("{{\\an{}}}{}", alignment, cue.text),
+        }
+    }
+
+    fn to_srt(&self, cues: Vec<ExportCue>) -> Vec<String> {
+        let lines: Vec<SrtLine> = Self::flatten_overlaps(cues)
+            .iter()
+            .map(|cue| SrtLine {
+                start: cue.start,
+                end: cue.end,
+                name: String::new(),
+                text: Self::srt_text(cue),
+            })
+            .collect();
+        SrtParser::new().to_lines(&lines)
+    }
+
+    fn flatten_unaligned(cues: Vec<ExportCue>) -> Vec<ExportCue> {
+        let unaligned = cues
+            .into_iter()
+            .map(|cue| ExportCue {
+                alignment: Self::DEFAULT_ALIGNMENT,
+                ..cue
+            })
+            .collect();
+        Self::flatten_overlaps(unaligned)
+    }
+
+    fn to_microdvd(&self, fps: f64, cues: Vec<ExportCue>) -> Vec<String> {
+        let cues = Self::flatten_unaligned(cues)
+            .into_iter()
+            .map(|cue| MicroDvdLine {
+                start_frame: cue.start.to_frame(fps),
+                end_frame: cue.end.to_frame(fps),
+                text: cue.text,
+            })
+            .collect();
+        let doc = MicroDvdDocument {
+            frame_rate: Some(fps),
+            cues,
+        };
+        MicroDvdParser::new().to_lines(&doc)
+    }
+
+    fn to_subviewer(&self, cues: Vec<ExportCue>) -> Vec<String> {
+        let cues = Self::flatten_unaligned(cues)
+            .into_iter()
+            .map(|cue| SubViewerLine {
+                start: cue.start,
+                end: cue.end,
+                text: cue.text,
+            })
+            .collect();
+        let doc = SubViewerDocument {
+            header: Vec::new(),
+            cues,
+        };
+        SubViewerParser::new().to_lines(&doc)
+    }
+
+    fn vtt_settings(alignment: u8) -> String {
+        let row = (alignment.clamp(1, 9) - 1) / 3;
+        let column = (alignment.clamp(1, 9) - 1) % 3;
+        let line = match row {
+            2 => Some("line:0"),
+            1 => Some("line:50%"),
+            _ => None,
+        };
+        let position = match column {
+            0 => Some("position:10% align:start"),
+            2 => Some("position:90% align:end"),
+            _ => None,
+        };
+        let settings: Vec<&str> = line.into_iter().chain(position).collect();
+        settings.join(" ")
+    }
+
+    fn vtt_style(cues: &[ExportCue]) -> Option<VttBlock> {
+        let mut colours: Vec<AssColor> = Vec::new();
+        for colour in cues.iter().flat_map(|cue| &cue.colours) {
+            if !colours.contains(colour) {
+                colours.push(*colour);
+            }
+        }
+        if colours.is_empty() {
+            return None;
+        }
+        let rules = colours.into_iter().map(|colour| {
+            format!(
+                "::cue(.{}) {{ color: {}; }}",
+                MarkupWriter::colour_class(colour),
+                MarkupWriter::css_colour(colour)
+            )
+        });
+        Some(VttBlock::Style(
+            std::iter::once(Self::VTT_STYLE_KEYWORD.to_string())
+                .chain(rules)
+                .collect(),
+        ))
+    }
+
+    fn to_vtt(&self, mut cues: Vec<ExportCue>) -> Vec<String> {
+        cues.sort_by_key(|cue| cue.start);
+        let style = Self::vtt_style(&cues);
+        let blocks = style
+            .into_iter()
+            .chain(cues.into_iter().map(|cue| {
+                VttBlock::Cue(VttLine {
+                    identifier: None,
+                    start: cue.start,
+                    end: cue.end,
+                    settings: Self::vtt_settings(cue.alignment),
+                    voice_classes: String::new(),
+                    voice: String::new(),
+                    text: cue.text,
+                })
+            }))
+            .collect();
+        let doc = VttDocument {
+            header: vec![Self::VTT_SIGNATURE.to_string()],
+            blocks,
+        };
+        VttParser::new().to_lines(&doc)
+    }
+
+    fn percent(value: f64) -> String {
+        format!("{}%", (value * 100.0).round() / 100.0)
+    }
+
+    fn ttml_style(name: &str, style: Option<&AssStyle>, play_res_y: f64) -> TtmlElement {
+        let id = name
+            .chars()
+            .map(|c| match c.is_ascii_alphanumeric() {
+                true => c,
+                false => '_',
+            })
+            .collect::<String>();
+        let mut attributes = Vec::new();
+        if let Some(style) = style {
+            let cells = style.fontsize / play_res_y * Self::TTML_CELL_ROWS;
+            attributes.push(("fontFamily".to_string(), style.fontname.clone()));
+            attributes.push((
+                "fontSize".to_string(),
+                format!("{}c", (cells * 100.0).round() / 100.0),
+            ));
+            attributes.push((
+                "color".to_string(),
+                MarkupWriter::css_colour(style.primary_colour),
+            ));
+            let flags = [
+                (style.italic, "fontStyle", "italic"),
+                (style.bold, "fontWeight", "bold"),
+                (style.underline, "textDecoration", "underline"),
+            ];
+            for (_, key, value) in flags.into_iter().filter(|(on, _, _)| *on) {
+                attributes.push((key.to_string(), value.to_string()));
+            }
+        }
+        TtmlElement {
+            id: format!("s_{}", id),
+            attributes,
+        }
+    }
+
+    fn ttml_region(cue: &ExportCue, style: Option<&AssStyle>, play_res: (f64, f64)) -> TtmlElement {
+        let defaults = style.map_or([0; 3], |s| [s.margin_l, s.margin_r, s.margin_v]);
+        let [left, right, vertical] = [0, 1, 2].map(|idx| match cue.margins[idx] {
+            0 => defaults[idx],
+            margin => margin,
+        });
+        let (width, height) = play_res;
+        let row = (cue.alignment.clamp(1, 9) - 1) / 3;
+        let column = (cue.alignment.clamp(1, 9) - 1) % 3;
+        let display_align = match row {
+            2 => "before",
+            1 => "center",
+            _ => "after",
+        };
+        let text_align = match column {
+            0 => "left",
+            2 => "right",
+            _ => "center",
+        };
+        let origin = format!(
+            "{} {}",
+            Self::percent(left as f64 / width * 100.0),
+            Self::percent(vertical as f64 / height * 100.0)
+        );
+        let extent = format!(
+            "{} {}",
+            Self::percent(((width - (left + right) as f64) / width * 100.0).max(0.0)),
+            Self::percent(((height - (2 * vertical) as f64) / height * 100.0).max(0.0))
+        );
+        TtmlElement {
+            id: format!("r{}_{}_{}_{}", cue.alignment, left, right, vertical),
+            attributes: vec![
+                ("origin".to_string(), origin),
+                ("extent".to_string(), extent),
+                ("displayAlign".to_string(), display_align.to_string()),
+                ("textAlign".to_string(), text_align.to_string()),
+            ],
+        }
+    }
+
+    fn to_ttml(
+        &self,
+        profile: TtmlProfile,
+        timing: TtmlTiming,
+        mut cues: Vec<ExportCue>,
+        styles: &HashMap<String, AssStyle>,
+        play_res: (f64, f64),
+    ) -> Vec<String> {
+        cues.sort_by_key(|cue| cue.start);
+        let mut doc = TtmlDocument::new(profile, timing);
+        for cue in cues {
+            let style = styles.get(&cue.style);
+            let ttml_style = Self::ttml_style(&cue.style, style, play_res.1);
+            let region = Self::ttml_region(&cue, style, play_res);
+            let paragraph = TtmlParagraph {
+                begin: cue.start,
+                end: cue.end,
+                region: Some(region.id.clone()),
+                style: Some(ttml_style.id.clone()),
+                text: cue.text,
+            };
+            if doc.style(&ttml_style.id).is_none() {
+                doc.styles.push(ttml_style);
+            }
+            if doc.region(&region.id).is_none() {
+                doc.regions.push(region);
+            }
+            doc.paragraphs.push(paragraph);
+        }
+        TtmlParser::new().to_lines(&doc)
+    }
+
+    fn play_res(doc: &AssDocument) -> (f64, f64) {
+        let info = doc.script_info();
+        let value = |key: &str| {
+            info.and_then(|i| i.get(key))
+                .and_then(|v| v.trim().parse().ok())
+        };
+        (
+            value("PlayResX").unwrap_or(Self::DEFAULT_PLAY_RES.0),
+            value("PlayResY").unwrap_or(Self::DEFAULT_PLAY_RES.1),
+        )
+    }
+
+    pub fn run(&self, doc: &AssDocument) -> Vec<String> {
+        let styles: HashMap<String, AssStyle> = doc
+            .styles()
+            .flat_map(|section| section.styles())
+            .map(|style| (style.name.trim().to_string(), style.clone()))
+            .collect();
+        let cues: Vec<ExportCue> = doc
+            .dialogues()
+            .filter_map(|line| self.to_cue(line, &styles))
+            .collect();
+        match self.target {
+            ExportTarget::Srt => self.to_srt(cues),
+            ExportTarget::Vtt => self.to_vtt(cues),
+            ExportTarget::MicroDvd(fps) => self.to_microdvd(fps, cues),
+            ExportTarget::SubViewer => self.to_subviewer(cues),
+            ExportTarget::Ttml(profile, timing) => {
+                self.to_ttml(profile, timing, cues, &styles, Self::play_res(doc))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,254 @@
+use crate::model::parse_error::ParseRes;
+
+use crate::model::format::ttml::document::{TtmlProfile, TtmlTiming};
+
+use super::*;
+
+struct TestCase {
+    name: &'static str,
+    target: ExportTarget,
+    styles: &'static [&'static str],
+    input: &'static [&'static str],
+    expected: &'static [&'static str],
+}
+
+const HEADER: [&str; 6] = [
+    "[V4+ Styles]",
+    "Format: Name, Fontname, Fontsize, PrimaryColour, Italic, Alignment",
+    "Style: Default,Arial,20,&H00FFFFFF,0,2",
+    "Style: Thoughts,Arial,20,&H00FFFFFF,-1,2",
+    "Style: Top,Arial,20,&H00FFFFFF,0,8",
+    "[Events]",
+];
+
+static TAGS_CASE: TestCase = TestCase {
+    name: "maps override tags to SRT markup",
+    target: ExportTarget::Srt,
+    styles: &[],
+    input: &[
+        "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text",
+        "Dialogue: 0,0:00:01.00,0:00:02.00,Default,,0,0,0,,{\\i1}Hello{\\i0} {\\b1}there{\\b0}\\Nsecond {\\c&H0000FF&}red{\\c} line",
+        "Dialogue: 0,0:00:03.00,0:00:04.00,Thoughts,,0,0,0,,Inner {\\u1}voice",
+    ],
+    expected: &[
+        "1",
+        "00:00:01,000 --> 00:00:02,000",
+        "<i>Hello</i> <b>there</b>",
+        "second <font color=\"#ff0000\">red</font> line",
+        "",
+        "2",
+        "00:00:03,000 --> 00:00:04,000",
+        "<i>Inner <u>voice</u></i>",
+    ],
+};
+
+static SIGNS_CASE: TestCase = TestCase {
+    name: "drops signs, drawings and excluded styles",
+    target: ExportTarget::Srt,
+    styles: &["Default", "Top"],
+    input: &[
+        "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text",
+        "Dialogue: 0,0:00:01.00,0:00:02.00,Default,,0,0,0,,{\\pos(320,50)}STORE",
+        "Dialogue: 0,0:00:01.00,0:00:02.00,Default,,0,0,0,,{\\p1}m 0 0 l 10 0 10 10{\\p0}",
+        "Dialogue: 0,0:00:01.00,0:00:02.00,Thoughts,,0,0,0,,Excluded",
+        "Dialogue: 0,0:00:01.00,0:00:02.00,Top,,0,0,0,,Kept",
+    ],
+    expected: &["1", "00:00:01,000 --> 00:00:02,000", "{\\an8}Kept"],
+};
+
+static OVERLAP_CASE: TestCase = TestCase {
+    name: "splits overlapping events into stacked SRT cues",
+    target: ExportTarget::Srt,
+    styles: &[],
+    input: &[
+        "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text",
+        "Dialogue: 0,0:00:01.00,0:00:04.00,Default,,0,0,0,,First",
+        "Dialogue: 0,0:00:02.00,0:00:03.00,Default,,0,0,0,,Second",
+    ],
+    expected: &[
+        "1",
+        "00:00:01,000 --> 00:00:02,000",
+        "First",
+        "",
+        "2",
+        "00:00:02,000 --> 00:00:03,000",
+        "First",
+        "Second",
+        "",
+        "3",
+        "00:00:03,000 --> 00:00:04,000",
+        "First",
+    ],
+};
+
+static VTT_CASE: TestCase = TestCase {
+    name: "maps tags to VTT spans and alignment to cue settings",
+    target: ExportTarget::Vtt,
+    styles: &[],
+    input: &[
+        "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text",
+        "Dialogue: 0,0:00:01.00,0:00:02.00,Default,,0,0,0,,{\\an7\\1c&H00FF00&}Green & <b>",
+        "Dialogue: 0,0:00:01.50,0:00:02.50,Top,,0,0,0,,{\\i1}Top",
+    ],
+    expected: &[
+        "WEBVTT",
+        "",
+        "STYLE",
+        "::cue(.c00ff00) { color: #00ff00; }",
+        "",
+        "00:00:01.000 --> 00:00:02.000 line:0 position:10% align:start",
+        "<c.c00ff00>Green &amp; &lt;b&gt;</c>",
+        "",
+        "00:00:01.500 --> 00:00:02.500 line:0",
+        "<i>Top</i>",
+    ],
+};
+
+static MICRODVD_CASE: TestCase = TestCase {
+    name: "maps tags to MicroDVD control codes and times to frames",
+    target: ExportTarget::MicroDvd(25.0),
+    styles: &[],
+    input: &[
+        "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text",
+        "Dialogue: 0,0:00:01.00,0:00:02.00,Default,,0,0,0,,{\\i1}Hello\\N{\\c&H0000FF&}red",
+        "Dialogue: 0,0:00:03.00,0:00:04.00,Top,,0,0,0,,Top line",
+    ],
+    expected: &[
+        "{1}{1}25",
+        "{25}{50}{y:i}Hello|{y:i}{c:$0000FF}red",
+        "{75}{100}Top line",
+    ],
+};
+
+static SUBVIEWER_CASE: TestCase = TestCase {
+    name: "writes plain SubViewer cues with line separators",
+    target: ExportTarget::SubViewer,
+    styles: &[],
+    input: &[
+        "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text",
+        "Dialogue: 0,0:00:01.00,0:00:02.50,Default,,0,0,0,,{\\b1}Bold{\\b0}\\Nsecond",
+    ],
+    expected: &[
+        "[INFORMATION]",
+        "[TITLE]",
+        "[AUTHOR]",
+        "[SOURCE]",
+        "[PRG]",
+        "[FILEPATH]",
+        "[DELAY]0",
+        "[CD TRACK]0",
+        "[COMMENT]",
+        "[END INFORMATION]",
+        "[SUBTITLE]",
+        "[COLF]&HFFFFFF,[STYLE]no,[SIZE]18,[FONT]Arial",
+        "",
+        "00:00:01.00,00:00:02.50",
+        "Bold[br]second",
+    ],
+};
+
+static TTML_CASE: TestCase = TestCase {
+    name: "writes TTML regions, styles and spans",
+    target: ExportTarget::Ttml(TtmlProfile::Dfxp, TtmlTiming::Clock),
+    styles: &[],
+    input: &[
+        "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text",
+        "Dialogue: 0,0:00:01.00,0:00:02.50,Default,,0,0,0,,{\\i1}Hello{\\i0} & <b>\\Nsecond {\\c&H0000FF&}red",
+        "Dialogue: 0,0:00:03.00,0:00:04.00,Top,,0,0,36,,Top line",
+    ],
+    expected: &[
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>",
+        "<tt xmlns=\"http://www.w3.org/ns/ttml\" xmlns:tts=\"http://www.w3.org/ns/ttml#styling\" xmlns:ttp=\"http://www.w3.org/ns/ttml#parameter\" xml:lang=\"\" ttp:timeBase=\"media\" ttp:cellResolution=\"32 15\">",
+        "  <head>",
+        "    <styling>",
+        "      <style xml:id=\"s_Default\" tts:fontFamily=\"Arial\" tts:fontSize=\"1.04c\" tts:color=\"#ffffff\"/>",
+        "      <style xml:id=\"s_Top\" tts:fontFamily=\"Arial\" tts:fontSize=\"1.04c\" tts:color=\"#ffffff\"/>",
+        "    </styling>",
+        "    <layout>",
+        "      <region xml:id=\"r2_10_10_10\" tts:origin=\"2.6% 3.47%\" tts:extent=\"94.79% 93.06%\" tts:displayAlign=\"after\" tts:textAlign=\"center\"/>",
+        "      <region xml:id=\"r8_10_10_36\" tts:origin=\"2.6% 12.5%\" tts:extent=\"94.79% 75%\" tts:displayAlign=\"before\" tts:textAlign=\"center\"/>",
+        "    </layout>",
+        "  </head>",
+        "  <body>",
+        "    <div>",
+        "      <p xml:id=\"sub1\" begin=\"00:00:01.000\" end=\"00:00:02.500\" region=\"r2_10_10_10\" style=\"s_Default\"><span tts:fontStyle=\"italic\">Hello</span> &amp; &lt;b&gt;<br/>second <span tts:color=\"#ff0000\">red</span></p>",
+        "      <p xml:id=\"sub2\" begin=\"00:00:03.000\" end=\"00:00:04.000\" region=\"r8_10_10_36\" style=\"s_Top\">Top line</p>",
+        "    </div>",
+        "  </body>",
+        "</tt>",
+    ],
+};
+
+fn run_test_case(test_case: &TestCase) -> ParseRes<()> {
+    let input: Vec<String> = HEADER
+        .iter()
+        .chain(test_case.input)
+        .map(|s| s.to_string())
+        .collect();
+    let doc = AssDocument::parse(&input)?;
+    let styles = test_case.styles.iter().map(|s| s.to_string()).collect();
+    let result = Exporter::new(test_case.target)
+        .with_styles(styles)
+        .run(&doc);
+    assert_eq!(
+        result, test_case.expected,
+        "Failed at case: {}",
+        test_case.name
+    );
+    Ok(())
+}
+
+#[test]
+fn test_tags_to_srt_markup() -> ParseRes<()> {
+    run_test_case(&TAGS_CASE)
+}
+
+#[test]
+fn test_signs_and_styles_are_filtered() -> ParseRes<()> {
+    run_test_case(&SIGNS_CASE)
+}
+
+#[test]
+fn test_overlapping_events() -> ParseRes<()> {
+    run_test_case(&OVERLAP_CASE)
+}
+
+#[test]
+fn test_vtt_export() -> ParseRes<()> {
+    run_test_case(&VTT_CASE)
+}
+
+#[test]
+fn test_microdvd_export() -> ParseRes<()> {
+    run_test_case(&MICRODVD_CASE)
+}
+
+#[test]
+fn test_subviewer_export() -> ParseRes<()> {
+    run_test_case(&SUBVIEWER_CASE)
+}
+
+#[test]
+fn test_ttml_export() -> ParseRes<()> {
+    run_test_case(&TTML_CASE)
+}
+
+#[test]
+fn test_ttml_frame_timing() -> ParseRes<()> {
+    let input: Vec<String> = HEADER
+        .iter()
+        .chain(&TTML_CASE.input[..2])
+        .map(|s| s.to_string())
+        .collect();
+    let doc = AssDocument::parse(&input)?;
+    let timing = TtmlTiming::Frames(Timestamp::FILM_FRAME_RATE);
+    let result = Exporter::new(ExportTarget::Ttml(TtmlProfile::EbuTt, timing)).run(&doc);
+    assert!(result[1].contains("xmlns:ebuttm=\"urn:ebu:tt:metadata\""));
+    assert!(result[1].contains("ttp:frameRate=\"24\" ttp:frameRateMultiplier=\"1000 1001\""));
+    assert!(
+        result
+            .iter()
+            .any(|l| l.contains("begin=\"00:00:01:00\" end=\"00:00:02:12\""))
+    );
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use crate::model::format::ass::parser::{parser::Parser, parser_error::ParseRes};
+use crate::model::format::ass::{document::AssDocument, parser::parser::Parser};
 
 pub struct SceneExtractor {
     parser: Parser,
@@ -11,25 +11,17 @@ impl SceneExtractor {
         }
     }
 
-    fn process_line(&mut self, line: &String, output: &mut Vec<String>) -> ParseRes<()> {
-        match self.parser.is_scene_line(line)? {
-            true => self.extract_and_push(line, output),
-            false => Ok(()),
-        }
-    }
-
-    fn extract_and_push(&mut self, line: &String, output: &mut Vec<String>) -> ParseRes<()> {
-        let text = self.parser.get_text(line)?;
-        output.push(text.clone());
-        Ok(())
+    pub fn run(&mut self, doc: &AssDocument) -> Vec<String> {
+        doc.dialogues()
+            .filter(|line| self.parser.is_scene_line(line))
+            .map(|line| line.text.trim().to_string())
+            .collect()
     }
+}
 
-    pub fn run(&mut self, lines: &[String]) -> ParseRes<Vec<String>> {
-        let mut output = Vec::new();
-        for line in lines {
-            self.process_line(line, &mut output)?;
-        }
-        Ok(output)
+impl Default for SceneExtractor {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -1,4 +1,4 @@
-use crate::model::format::ass::parser::parser_error::ParserError;
+use crate::model::parse_error::{ParseRes, ParserError};
 
 use super::*;
 
@@ -70,11 +70,21 @@ static MISSING_FIELDS_CASE: TestCase = TestCase {
     expected: &[],
 };
 
+static COMMENTS_CASE: TestCase = TestCase {
+    name: "ignores comments in additional scenes",
+    input: &[
+        "Comment: 0,0:00:01.00,0:00:02.00,Default,ADDITIONAL SCENE,0,0,0,,Disabled line",
+        "Dialogue: 0,0:00:02.50,0:00:03.50,Default,ADDITIONAL SCENE,0,0,0,,Extra 1",
+    ],
+    expected: &["Extra 1"],
+};
+
 fn run_test_case(test_case: &TestCase) -> ParseRes<()> {
     let input: Vec<String> = test_case.input.iter().map(|s| s.to_string()).collect();
     let expected: Vec<String> = test_case.expected.iter().map(|s| s.to_string()).collect();
+    let doc = AssDocument::parse(&input)?;
     let mut extractor = SceneExtractor::new();
-    let result = extractor.run(&input)?;
+    let result = extractor.run(&doc);
     assert_eq!(result, expected, "Failed at case: {}", test_case.name);
     Ok(())
 }
@@ -104,7 +114,12 @@ fn test_fails_on_corrupt_data() {
 fn test_error_missing_fields() {
     let result = run_test_case(&MISSING_FIELDS_CASE);
     match result {
-        Err(ParserError::MissingFields { found }) => assert_eq!(found, 3),
+        Err(ParserError::MissingFields { found, .. }) => assert_eq!(found, 3),
         _ => panic!("Expected MissingFields(3), got {:?}", result),
     }
 }
+
+#[test]
+fn test_extract_skips_comments() -> ParseRes<()> {
+    run_test_case(&COMMENTS_CASE)
+}
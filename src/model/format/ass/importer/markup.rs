@@ -0,0 +1,271 @@
+use crate::model::format::ass::style_ass::AssColor;
+
+pub struct MarkupReader {
+    out: String,
+    spans: Vec<String>,
+}
+
+impl MarkupReader {
+    const LINE_BREAK: char = '\n';
+    const HARD_BREAK: &str = "\\N";
+    const ITALIC_LINE_PREFIX: char = '/';
+    const SPAN_TAG: &str = "span";
+    const NAMED_COLOURS: [(&str, &str); 8] = [
+        ("white", "#ffffff"),
+        ("black", "#000000"),
+        ("red", "#ff0000"),
+        ("lime", "#00ff00"),
+        ("blue", "#0000ff"),
+        ("yellow", "#ffff00"),
+        ("cyan", "#00ffff"),
+        ("magenta", "#ff00ff"),
+    ];
+    const ENTITIES: [(&str, &str); 6] = [
+        ("&lt;", "<"),
+        ("&gt;", ">"),
+        ("&nbsp;", "\\h"),
+        ("&quot;", "\""),
+        ("&apos;", "'"),
+        ("&amp;", "&"),
+    ];
+
+    pub fn new() -> Self {
+        Self {
+            out: String::new(),
+            spans: Vec::new(),
+        }
+    }
+
+    pub fn with_style(mut self, attributes: &[(String, String)]) -> Self {
+        let items: String = Self::style_items(attributes)
+            .into_iter()
+            .map(|(item, _)| item)
+            .collect();
+        if !items.is_empty() {
+            self.push_override(items);
+        }
+        self
+    }
+
+    fn html_colour(value: &str) -> Option<AssColor> {
+        let value = value.trim().trim_matches(['"', '\'']);
+        let value = Self::NAMED_COLOURS
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(value))
+            .map_or(value, |(_, hex)| hex);
+        let hex = value.strip_prefix('#')?;
+        let hex = match hex.len() {
+            8 => hex.get(..6).unwrap_or(hex),
+            _ => hex,
+        };
+        let rgb = u32::from_str_radix(hex, 16)
+            .ok()
+            .filter(|_| hex.len() == 6)?;
+        let [b, g, r, _] = rgb.to_le_bytes();
+        Some(AssColor::new(r, g, b, 0))
+    }
+
+    fn font_override(attributes: &str) -> Option<String> {
+        let (_, value) = attributes.split_once("color=")?;
+        let value = value.split_whitespace().next().unwrap_or_default();
+        Self::html_colour(value).map(|colour| format!("\\c{}", colour.to_bgr()))
+    }
+
+    fn tag_override(tag: &str) -> Option<String> {
+        let (closing, body) = match tag.strip_prefix('/') {
+            Some(body) => (true, body),
+            None => (false, tag),
+        };
+        let (name, attributes) = body.split_once(' ').unwrap_or((body, ""));
+        let flag = match closing {
+            true => 0,
+            false => 1,
+        };
+        match name.to_lowercase().as_str() {
+            "i" | "b" | "u" | "s" => Some(format!("\\{}{}", name.to_lowercase(), flag)),
+            "font" if closing => Some("\\c".to_string()),
+            "font" => Self::font_override(attributes),
+            _ => None,
+        }
+    }
+
+    fn style_items(attributes: &[(String, String)]) -> Vec<(String, String)> {
+        let pair = |on: &str, off: &str| Some((on.to_string(), off.to_string()));
+        attributes
+            .iter()
+            .filter_map(|(key, value)| match (key.as_str(), value.trim()) {
+                ("fontStyle", "italic" | "oblique") => pair("\\i1", "\\i0"),
+                ("fontWeight", "bold") => pair("\\b1", "\\b0"),
+                ("textDecoration", decoration) if decoration.contains("underline") => {
+                    pair("\\u1", "\\u0")
+                }
+                ("textDecoration", decoration) if decoration.contains("lineThrough") => {
+                    pair("\\s1", "\\s0")
+                }
+                ("color", colour) => Self::html_colour(colour)
+                    .map(|c| (format!("\\c{}", c.to_bgr()), "\\c".to_string())),
+                ("fontFamily", family) if !family.is_empty() => {
+                    Some((format!("\\fn{}", family), "\\fn".to_string()))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn span_attributes(attributes: &str) -> Vec<(String, String)> {
+        attributes
+            .split('"')
+            .collect::<Vec<&str>>()
+            .chunks(2)
+            .filter_map(|pair| {
+                let key = pair[0].trim().strip_suffix('=')?;
+                let key = key.rsplit(':').next().unwrap_or(key);
+                Some((key.to_string(), pair.get(1)?.to_string()))
+            })
+            .collect()
+    }
+
+    fn push_span(&mut self, tag: &str) {
+        match tag.strip_prefix('/') {
+            Some(_) => {
+                if let Some(reset) = self.spans.pop()
+                    && !reset.is_empty()
+                {
+                    self.push_override(reset);
+                }
+            }
+            None => {
+                let attributes = &tag[Self::SPAN_TAG.len()..];
+                let (items, resets): (Vec<String>, Vec<String>) =
+                    Self::style_items(&Self::span_attributes(attributes))
+                        .into_iter()
+                        .unzip();
+                if !items.is_empty() {
+                    self.push_override(items.concat());
+                }
+                self.spans.push(resets.into_iter().rev().collect());
+            }
+        }
+    }
+
+    fn push_tag(&mut self, tag: &str) {
+        let name = tag
+            .trim_start_matches('/')
+            .split(' ')
+            .next()
+            .unwrap_or_default();
+        if name.eq_ignore_ascii_case(Self::SPAN_TAG) {
+            return self.push_span(tag);
+        }
+        if let Some(item) = Self::tag_override(tag) {
+            self.push_override(item);
+        }
+    }
+
+    fn code_items(code: &str) -> Option<(bool, Vec<(String, String)>)> {
+        let (key, value) = code.split_once(':')?;
+        let global = key.chars().all(|c| c.is_ascii_uppercase());
+        let value = value.trim();
+        let items = match key.to_ascii_lowercase().as_str() {
+            "y" => value
+                .split(',')
+                .map(|flag| flag.trim().to_ascii_lowercase())
+                .filter(|flag| matches!(flag.as_str(), "i" | "b" | "u" | "s"))
+                .map(|flag| (format!("\\{}1", flag), format!("\\{}0", flag)))
+                .collect(),
+            "c" => {
+                let hex = value.strip_prefix('$')?;
+                u32::from_str_radix(hex, 16)
+                    .ok()
+                    .filter(|_| hex.len() == 6)?;
+                let colour = format!("\\c&H{}&", hex.to_ascii_uppercase());
+                vec![(colour, "\\c".to_string())]
+            }
+            "f" => vec![(format!("\\fn{}", value), "\\fn".to_string())],
+            "s" => {
+                let size: u32 = value.parse().ok()?;
+                vec![(format!("\\fs{}", size), "\\fs".to_string())]
+            }
+            _ => return None,
+        };
+        Some((global, items))
+    }
+
+    fn push_override(&mut self, item: String) {
+        match self.out.ends_with('}') && self.out.contains('{') {
+            true => {
+                self.out.pop();
+                self.out.push_str(&item);
+                self.out.push('}');
+            }
+            false => self.out.push_str(&format!("{{{}}}", item)),
+        }
+    }
+
+    fn push_text(&mut self, text: &str) {
+        let mut decoded = text.replace(Self::LINE_BREAK, Self::HARD_BREAK);
+        for (entity, value) in Self::ENTITIES {
+            decoded = decoded.replace(entity, value);
+        }
+        self.out.push_str(&decoded);
+    }
+
+    fn read_code_line(&mut self, line: &str, resets: &mut Vec<String>) {
+        let mut rest = line;
+        if let Some(stripped) = rest.strip_prefix(Self::ITALIC_LINE_PREFIX) {
+            self.push_override("\\i1".to_string());
+            resets.push("\\i0".to_string());
+            rest = stripped;
+        }
+        while let Some(open) = rest.find('{') {
+            let Some(close) = rest[open..].find('}').map(|idx| open + idx) else {
+                break;
+            };
+            self.out.push_str(&rest[..open]);
+            if let Some((global, items)) = Self::code_items(&rest[open + 1..close]) {
+                for (item, reset) in items {
+                    self.push_override(item);
+                    if !global {
+                        resets.push(reset);
+                    }
+                }
+            }
+            rest = &rest[close + 1..];
+        }
+        self.out.push_str(rest);
+    }
+
+    pub fn read_codes(mut self, text: &str) -> String {
+        let mut resets: Vec<String> = Vec::new();
+        for (idx, line) in text.split(Self::LINE_BREAK).enumerate() {
+            if idx > 0 {
+                if !resets.is_empty() {
+                    self.push_override(std::mem::take(&mut resets).concat());
+                }
+                self.out.push_str(Self::HARD_BREAK);
+            }
+            self.read_code_line(line, &mut resets);
+        }
+        self.out
+    }
+
+    pub fn read(mut self, markup: &str) -> String {
+        let mut rest = markup;
+        while let Some(open) = rest.find('<') {
+            let Some(close) = rest[open..].find('>').map(|idx| open + idx) else {
+                break;
+            };
+            self.push_text(&rest[..open]);
+            self.push_tag(rest[open + 1..close].trim());
+            rest = &rest[close + 1..];
+        }
+        self.push_text(rest);
+        self.out
+    }
+}
+
+impl Default for MarkupReader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,300 @@
+use crate::model::{
+    format::{
+        ass::{
+            document::{
+                AssDocument, AssSection,
+                events::{EventEntry, EventsSection},
+                script_info::ScriptInfo,
+            },
+            event_format::EventFormat,
+            importer::markup::MarkupReader,
+            line_ass::{AssLine, EventKind},
+            stylist::{Stylist, style_type::StyleType},
+        },
+        microdvd::parser::MicroDvdParser,
+        srt::parser::SrtParser,
+        subviewer::parser::SubViewerParser,
+        ttml::{
+            document::{TtmlDocument, TtmlParagraph},
+            parser::TtmlParser,
+        },
+        vtt::{line_vtt::VttLine, parser::VttParser},
+    },
+    parse_error::ParseRes,
+    timestamp::Timestamp,
+};
+
+pub mod markup;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImportSource {
+    Srt,
+    Vtt,
+    MicroDvd(f64),
+    SubViewer,
+    Ttml,
+}
+
+impl ImportSource {
+    const MICRODVD_PREFIX: char = '{';
+    const BOM: char = '\u{feff}';
+
+    pub fn sniff_sub(lines: &[String], fps: f64) -> Self {
+        let first = lines
+            .iter()
+            .map(|line| line.trim_start_matches(Self::BOM).trim())
+            .find(|line| !line.is_empty());
+        match first.is_some_and(|line| line.starts_with(Self::MICRODVD_PREFIX)) {
+            true => ImportSource::MicroDvd(fps),
+            false => ImportSource::SubViewer,
+        }
+    }
+}
+
+struct ImportCue {
+    start: Timestamp,
+    end: Timestamp,
+    name: String,
+    alignment: Option<u8>,
+    text: String,
+}
+
+pub struct Importer {
+    source: ImportSource,
+    style_type: StyleType,
+}
+
+impl Importer {
+    const SCRIPT_INFO_HEADER: &str = "[Script Info]";
+    const EVENTS_HEADER: &str = "[Events]";
+    const DEFAULT_STYLE: &str = "Default";
+    const DEFAULT_LAYER: &str = "0";
+    const DEFAULT_MARGIN: &str = "0";
+    const DEFAULT_ALIGNMENT: u8 = 2;
+    const SCRIPT_INFO: [(&str, &str); 2] = [("ScriptType", "v4.00+"), ("WrapStyle", "0")];
+
+    pub fn new(source: ImportSource) -> Self {
+        Self {
+            source,
+            style_type: StyleType::Main,
+        }
+    }
+
+    pub fn with_style(mut self, style_type: StyleType) -> Self {
+        self.style_type = style_type;
+        self
+    }
+
+    fn srt_cues(lines: &[String]) -> ParseRes<Vec<ImportCue>> {
+        let cues = SrtParser::new().parse_lines(lines)?;
+        Ok(cues
+            .into_iter()
+            .map(|cue| ImportCue {
+                start: cue.start,
+                end: cue.end,
+                name: cue.name,
+                alignment: None,
+                text: MarkupReader::new().read(&cue.text),
+            })
+            .collect())
+    }
+
+    fn microdvd_cues(lines: &[String], fps: f64) -> ParseRes<Vec<ImportCue>> {
+        let doc = MicroDvdParser::new().parse_lines(lines)?;
+        let fps = doc.frame_rate.unwrap_or(fps);
+        Ok(doc
+            .cues
+            .into_iter()
+            .map(|cue| ImportCue {
+                start: Timestamp::from_frame(cue.start_frame, fps),
+                end: Timestamp::from_frame(cue.end_frame, fps),
+                name: String::new(),
+                alignment: None,
+                text: MarkupReader::new().read_codes(&cue.text),
+            })
+            .collect())
+    }
+
+    fn subviewer_cues(lines: &[String]) -> ParseRes<Vec<ImportCue>> {
+        let doc = SubViewerParser::new().parse_lines(lines)?;
+        Ok(doc
+            .cues
+            .into_iter()
+            .map(|cue| ImportCue {
+                start: cue.start,
+                end: cue.end,
+                name: String::new(),
+                alignment: None,
+                text: MarkupReader::new().read(&cue.text),
+            })
+            .collect())
+    }
+
+    fn vtt_row(line: &str) -> u8 {
+        let value = line.split(',').next().unwrap_or_default();
+        match value.strip_suffix('%') {
+            Some(percent) => match percent.parse::<f64>().unwrap_or(100.0) {
+                p if p < 33.0 => 2,
+                p if p < 66.0 => 1,
+                _ => 0,
+            },
+            None => match value.parse::<i32>() {
+                Ok(number) if number >= 0 => 2,
+                _ => 0,
+            },
+        }
+    }
+
+    fn vtt_alignment(cue: &VttLine) -> Option<u8> {
+        let setting = |key: &str| {
+            cue.settings
+                .split_whitespace()
+                .find_map(|s| s.strip_prefix(key))
+        };
+        let row = setting("line:").map_or(0, Self::vtt_row);
+        let column = match setting("align:") {
+            Some("start" | "left") => 0,
+            Some("end" | "right") => 2,
+            _ => 1,
+        };
+        Some(row * 3 + column + 1).filter(|alignment| *alignment != Self::DEFAULT_ALIGNMENT)
+    }
+
+    fn vtt_cues(lines: &[String]) -> ParseRes<Vec<ImportCue>> {
+        let doc = VttParser::new().parse(lines)?;
+        Ok(doc
+            .cues()
+            .map(|cue| ImportCue {
+                start: cue.start,
+                end: cue.end,
+                name: cue.voice.clone(),
+                alignment: Self::vtt_alignment(cue),
+                text: MarkupReader::new().read(&cue.text),
+            })
+            .collect())
+    }
+
+    fn percentages(value: Option<&str>) -> Option<(f64, f64)> {
+        let values: Vec<f64> = value?
+            .split_whitespace()
+            .map(|v| v.strip_suffix('%')?.parse().ok())
+            .collect::<Option<Vec<f64>>>()?;
+        match values.as_slice() {
+            [x, y] => Some((*x, *y)),
+            _ => None,
+        }
+    }
+
+    fn ttml_alignment(doc: &TtmlDocument, paragraph: &TtmlParagraph) -> Option<u8> {
+        let region = paragraph.region.as_deref().and_then(|id| doc.region(id))?;
+        let style = paragraph.style.as_deref().and_then(|id| doc.style(id));
+        let (_, top) = Self::percentages(region.get("origin")).unwrap_or((0.0, 0.0));
+        let (_, height) = Self::percentages(region.get("extent")).unwrap_or((100.0, 100.0));
+        let anchor = match region.get("displayAlign") {
+            Some("after") => top + height,
+            Some("center") => top + height / 2.0,
+            _ => top,
+        };
+        let row = match anchor {
+            a if a < 33.0 => 2,
+            a if a < 66.0 => 1,
+            _ => 0,
+        };
+        let text_align = style
+            .and_then(|s| s.get("textAlign"))
+            .or(region.get("textAlign"));
+        let column = match text_align {
+            Some("left" | "start") => 0,
+            Some("right" | "end") => 2,
+            _ => 1,
+        };
+        Some(row * 3 + column + 1).filter(|alignment| *alignment != Self::DEFAULT_ALIGNMENT)
+    }
+
+    fn ttml_cues(lines: &[String]) -> ParseRes<Vec<ImportCue>> {
+        let doc = TtmlParser::new().parse_lines(lines)?;
+        Ok(doc
+            .paragraphs
+            .iter()
+            .map(|paragraph| {
+                let style = paragraph.style.as_deref().and_then(|id| doc.style(id));
+                let reader = match style {
+                    Some(style) => MarkupReader::new().with_style(&style.attributes),
+                    None => MarkupReader::new(),
+                };
+                ImportCue {
+                    start: paragraph.begin,
+                    end: paragraph.end,
+                    name: String::new(),
+                    alignment: Self::ttml_alignment(&doc, paragraph),
+                    text: reader.read(&paragraph.text),
+                }
+            })
+            .collect())
+    }
+
+    fn script_info() -> ScriptInfo {
+        let mut info = ScriptInfo::new(Self::SCRIPT_INFO_HEADER);
+        for (key, value) in Self::SCRIPT_INFO {
+            info.set(key, value);
+        }
+        info.push_line("");
+        info
+    }
+
+    fn to_line(&self, cue: ImportCue) -> AssLine {
+        let text = match (cue.alignment, cue.text.strip_prefix('{')) {
+            (Some(alignment), Some(rest)) => format!("{{\\an{}{}", alignment, rest),
+            (Some(alignment), None) => format!("{{\\an{}}}{}", alignment, cue.text),
+            (None, _) => cue.text,
+        };
+        AssLine {
+            kind: EventKind::Dialogue,
+            layer: Self::DEFAULT_LAYER.to_string(),
+            start: cue.start,
+            end: cue.end,
+            style: Self::DEFAULT_STYLE.to_string(),
+            name: cue.name,
+            margin_l: Self::DEFAULT_MARGIN.to_string(),
+            margin_r: Self::DEFAULT_MARGIN.to_string(),
+            margin_v: Self::DEFAULT_MARGIN.to_string(),
+            effect: String::new(),
+            text,
+            raw_start: None,
+            raw_end: None,
+        }
+    }
+
+    fn events(&self, cues: Vec<ImportCue>) -> EventsSection {
+        let mut events = EventsSection::new(Some(Self::EVENTS_HEADER));
+        events
+            .entries
+            .push(EventEntry::Format(EventFormat::default().to_line()));
+        events.entries.extend(
+            cues.into_iter()
+                .map(|cue| EventEntry::Event(Box::new(self.to_line(cue)))),
+        );
+        events
+    }
+
+    pub fn run(&self, lines: &[String]) -> ParseRes<AssDocument> {
+        let cues = match self.source {
+            ImportSource::Srt => Self::srt_cues(lines)?,
+            ImportSource::Vtt => Self::vtt_cues(lines)?,
+            ImportSource::MicroDvd(fps) => Self::microdvd_cues(lines, fps)?,
+            ImportSource::SubViewer => Self::subviewer_cues(lines)?,
+            ImportSource::Ttml => Self::ttml_cues(lines)?,
+        };
+        let mut doc = AssDocument {
+            sections: vec![
+                AssSection::ScriptInfo(Self::script_info()),
+                AssSection::Events(self.events(cues)),
+            ],
+        };
+        Stylist::new(&self.style_type).run(&mut doc);
+        Ok(doc)
+    }
+}
+
+#[cfg(test)]
+mod tests;
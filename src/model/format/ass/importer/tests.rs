@@ -0,0 +1,204 @@
+use super::*;
+
+struct TestCase {
+    name: &'static str,
+    source: ImportSource,
+    style_type: StyleType,
+    input: &'static [&'static str],
+    expected_styles: &'static [&'static str],
+    expected_dialogues: &'static [&'static str],
+}
+
+static SRT_CASE: TestCase = TestCase {
+    name: "imports SRT markup and labels into a Main skeleton",
+    source: ImportSource::Srt,
+    style_type: StyleType::Main,
+    input: &[
+        "1",
+        "00:00:01,000 --> 00:00:02,500",
+        "<i>Hi</i> &amp; <font color=\"#ff0000\">red</font>",
+        "second",
+        "",
+        "2",
+        "00:00:03,000 --> 00:00:04,000",
+        "[ADDITIONAL SCENE] <b><u>Loud</u></b>&nbsp;<span>plain</span>",
+    ],
+    expected_styles: &["Main"],
+    expected_dialogues: &[
+        "Dialogue: 0,0:00:01.00,0:00:02.50,Main,,0,0,0,,{\\i1}Hi{\\i0} & {\\c&H0000FF&}red{\\c}\\Nsecond",
+        "Dialogue: 0,0:00:03.00,0:00:04.00,Main,ADDITIONAL SCENE,0,0,0,,{\\b1\\u1}Loud{\\u0\\b0}\\hplain",
+    ],
+};
+
+static VTT_CASE: TestCase = TestCase {
+    name: "imports VTT voices and placement with the Second profile",
+    source: ImportSource::Vtt,
+    style_type: StyleType::Second,
+    input: &[
+        "WEBVTT",
+        "",
+        "00:00:01.000 --> 00:00:02.000 line:0 align:start",
+        "<v Narrator>Top left",
+        "",
+        "00:00:02.000 --> 00:00:03.000",
+        "Bottom &lt;3",
+    ],
+    expected_styles: &["Second"],
+    expected_dialogues: &[
+        "Dialogue: 0,0:00:01.00,0:00:02.00,Second,Narrator,0,0,0,,{\\an7}Top left",
+        "Dialogue: 0,0:00:02.00,0:00:03.00,Second,,0,0,0,,Bottom <3",
+    ],
+};
+
+static MICRODVD_CASE: TestCase = TestCase {
+    name: "imports MicroDVD frames and control codes",
+    source: ImportSource::MicroDvd(25.0),
+    style_type: StyleType::Main,
+    input: &[
+        "{1}{1}23.976",
+        "{24}{48}{y:i}Local italics|Plain {c:$0000FF}red",
+        "{72}{}{Y:b}Bold everywhere|/still bold",
+        "{96}{120}{s:20}{f:Arial}Sized",
+    ],
+    expected_styles: &["Main"],
+    expected_dialogues: &[
+        "Dialogue: 0,0:00:01.00,0:00:02.00,Main,,0,0,0,,{\\i1}Local italics{\\i0}\\NPlain {\\c&H0000FF&}red",
+        "Dialogue: 0,0:00:03.00,0:00:04.00,Main,,0,0,0,,{\\b1}Bold everywhere\\N{\\i1}still bold",
+        "Dialogue: 0,0:00:04.00,0:00:05.01,Main,,0,0,0,,{\\fs20\\fnArial}Sized",
+    ],
+};
+
+static SUBVIEWER_CASE: TestCase = TestCase {
+    name: "imports SubViewer 2.0 cues after the information header",
+    source: ImportSource::SubViewer,
+    style_type: StyleType::Main,
+    input: &[
+        "[INFORMATION]",
+        "[TITLE]Episode 1",
+        "[END INFORMATION]",
+        "[SUBTITLE]",
+        "[COLF]&HFFFFFF,[STYLE]no,[SIZE]18,[FONT]Arial",
+        "00:00:01.00,00:00:02.50",
+        "First line[br]second line",
+        "",
+        "00:00:03.00,00:00:04.00",
+        "Another",
+    ],
+    expected_styles: &["Main"],
+    expected_dialogues: &[
+        "Dialogue: 0,0:00:01.00,0:00:02.50,Main,,0,0,0,,First line\\Nsecond line",
+        "Dialogue: 0,0:00:03.00,0:00:04.00,Main,,0,0,0,,Another",
+    ],
+};
+
+static TTML_CASE: TestCase = TestCase {
+    name: "imports basic TTML with styles, regions and spans",
+    source: ImportSource::Ttml,
+    style_type: StyleType::Main,
+    input: &[
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>",
+        "<tt xmlns=\"http://www.w3.org/ns/ttml\" xmlns:tts=\"http://www.w3.org/ns/ttml#styling\"",
+        "    xmlns:ttp=\"http://www.w3.org/ns/ttml#parameter\" ttp:frameRate=\"25\">",
+        "  <head>",
+        "    <styling>",
+        "      <style xml:id=\"italic\" tts:fontStyle=\"italic\"/>",
+        "      <style xml:id=\"yellow\" tts:color=\"yellow\"/>",
+        "    </styling>",
+        "    <layout>",
+        "      <region xml:id=\"top\" tts:origin=\"10% 5%\" tts:extent=\"80% 20%\" tts:displayAlign=\"before\"/>",
+        "      <region xml:id=\"bottom\">",
+        "        <style tts:origin=\"10% 70%\" tts:extent=\"80% 20%\" tts:displayAlign=\"after\"/>",
+        "      </region>",
+        "    </layout>",
+        "  </head>",
+        "  <body region=\"bottom\">",
+        "    <div>",
+        "      <!-- first cue -->",
+        "      <p begin=\"00:00:01.000\" end=\"00:00:02:12\">",
+        "        Hello <span tts:fontWeight=\"bold\">bold</span> &amp;",
+        "        <br/> <span style=\"yellow\">world</span>",
+        "      </p>",
+        "      <p begin=\"3s\" dur=\"25f\" region=\"top\" style=\"italic\">Top</p>",
+        "    </div>",
+        "  </body>",
+        "</tt>",
+    ],
+    expected_styles: &["Main"],
+    expected_dialogues: &[
+        "Dialogue: 0,0:00:01.00,0:00:02.48,Main,,0,0,0,,Hello {\\b1}bold{\\b0} &\\N{\\c&H00FFFF&}world{\\c}",
+        "Dialogue: 0,0:00:03.00,0:00:04.00,Main,,0,0,0,,{\\an8\\i1}Top",
+    ],
+};
+
+fn run_test_case(test_case: &TestCase) -> ParseRes<()> {
+    let input: Vec<String> = test_case.input.iter().map(|s| s.to_string()).collect();
+    let doc = Importer::new(test_case.source)
+        .with_style(test_case.style_type)
+        .run(&input)?;
+    let output = doc.serialize();
+    assert_eq!(
+        output.first().map(String::as_str),
+        Some("[Script Info]"),
+        "Failed at case: {}",
+        test_case.name
+    );
+    let styles: Vec<String> = doc
+        .styles()
+        .flat_map(|section| section.styles())
+        .map(|style| style.name.clone())
+        .collect();
+    assert_eq!(
+        styles, test_case.expected_styles,
+        "Failed at case: {}",
+        test_case.name
+    );
+    let dialogues: Vec<&String> = output
+        .iter()
+        .filter(|line| line.starts_with("Dialogue:"))
+        .collect();
+    assert_eq!(
+        dialogues, test_case.expected_dialogues,
+        "Failed at case: {}",
+        test_case.name
+    );
+    Ok(())
+}
+
+#[test]
+fn test_srt_import() -> ParseRes<()> {
+    run_test_case(&SRT_CASE)
+}
+
+#[test]
+fn test_vtt_import() -> ParseRes<()> {
+    run_test_case(&VTT_CASE)
+}
+
+#[test]
+fn test_microdvd_import() -> ParseRes<()> {
+    run_test_case(&MICRODVD_CASE)
+}
+
+#[test]
+fn test_subviewer_import() -> ParseRes<()> {
+    run_test_case(&SUBVIEWER_CASE)
+}
+
+#[test]
+fn test_ttml_import() -> ParseRes<()> {
+    run_test_case(&TTML_CASE)
+}
+
+#[test]
+fn test_sniff_sub_format() {
+    let microdvd = vec!["\u{feff}{1}{25}Hello".to_string()];
+    let subviewer = vec!["[INFORMATION]".to_string()];
+    assert_eq!(
+        ImportSource::sniff_sub(&microdvd, 25.0),
+        ImportSource::MicroDvd(25.0)
+    );
+    assert_eq!(
+        ImportSource::sniff_sub(&subviewer, 25.0),
+        ImportSource::SubViewer
+    );
+}
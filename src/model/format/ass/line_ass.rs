@@ -1,43 +1,139 @@
-use crate::model::line::Line;
+use crate::model::{line::Line, timestamp::Timestamp};
 
-#[repr(usize)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum AssField {
-    Layer = 0,
-    Start = 1,
-    End = 2,
-    Style = 3,
-    Name = 4,
-    Effect = 8,
-    Text = 9,
+    Layer,
+    Start,
+    End,
+    Style,
+    Name,
+    MarginL,
+    MarginR,
+    MarginV,
+    Effect,
+    Text,
 }
 
-pub const ASS_FIELDS_COUNT: usize = 10;
-
 impl AssField {
-    pub fn index(self) -> usize {
-        self as usize
+    pub const ALL: [AssField; 10] = [
+        AssField::Layer,
+        AssField::Start,
+        AssField::End,
+        AssField::Style,
+        AssField::Name,
+        AssField::MarginL,
+        AssField::MarginR,
+        AssField::MarginV,
+        AssField::Effect,
+        AssField::Text,
+    ];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "layer" | "marked" => Some(AssField::Layer),
+            "start" => Some(AssField::Start),
+            "end" => Some(AssField::End),
+            "style" => Some(AssField::Style),
+            "name" | "actor" => Some(AssField::Name),
+            "marginl" => Some(AssField::MarginL),
+            "marginr" => Some(AssField::MarginR),
+            "marginv" => Some(AssField::MarginV),
+            "effect" => Some(AssField::Effect),
+            "text" => Some(AssField::Text),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            AssField::Layer => "Layer",
+            AssField::Start => "Start",
+            AssField::End => "End",
+            AssField::Style => "Style",
+            AssField::Name => "Name",
+            AssField::MarginL => "MarginL",
+            AssField::MarginR => "MarginR",
+            AssField::MarginV => "MarginV",
+            AssField::Effect => "Effect",
+            AssField::Text => "Text",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EventKind {
+    Dialogue,
+    Comment,
+    Picture,
+    Sound,
+    Movie,
+    Command,
+}
+
+impl EventKind {
+    const ALL: [EventKind; 6] = [
+        EventKind::Dialogue,
+        EventKind::Comment,
+        EventKind::Picture,
+        EventKind::Sound,
+        EventKind::Movie,
+        EventKind::Command,
+    ];
+
+    pub fn prefix(self) -> &'static str {
+        match self {
+            EventKind::Dialogue => "Dialogue: ",
+            EventKind::Comment => "Comment: ",
+            EventKind::Picture => "Picture: ",
+            EventKind::Sound => "Sound: ",
+            EventKind::Movie => "Movie: ",
+            EventKind::Command => "Command: ",
+        }
+    }
+
+    pub fn from_line(line: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|kind| line.starts_with(kind.prefix()))
     }
 }
 
 #[derive(Debug, Clone)]
 pub struct AssLine {
+    pub kind: EventKind,
     pub layer: String,
-    pub start: f64,
-    pub end: f64,
+    pub start: Timestamp,
+    pub end: Timestamp,
     pub style: String,
     pub name: String,
+    pub margin_l: String,
+    pub margin_r: String,
+    pub margin_v: String,
     pub effect: String,
     pub text: String,
+    pub raw_start: Option<String>,
+    pub raw_end: Option<String>,
+}
+
+impl AssLine {
+    pub fn shift(&mut self, offset: Timestamp) {
+        self.start += offset;
+        self.end += offset;
+    }
 }
 
 impl Line for AssLine {
-    fn start(&self) -> f64 {
+    fn start(&self) -> Timestamp {
         self.start
     }
-    fn end(&self) -> f64 {
+    fn end(&self) -> Timestamp {
         self.end
     }
     fn text(&self) -> &str {
         &self.text
     }
+    fn set_times(&mut self, start: Timestamp, end: Timestamp) {
+        self.start = start;
+        self.end = end;
+    }
 }
@@ -1,12 +1,17 @@
 pub mod applier;
 pub mod ass_error;
 pub mod ass_processor;
-pub mod ass_segmenter;
 pub mod cleaner;
+pub mod document;
+pub mod event_format;
+pub mod exporter;
 pub mod extractor;
+pub mod importer;
 pub mod line_ass;
-pub mod line_collector;
 pub mod parser;
 pub mod sorter;
+pub mod style_ass;
+pub mod style_format;
 pub mod stylist;
 pub mod synchronizer;
+pub mod text;
@@ -1,2 +1,2 @@
+#[allow(clippy::module_inception)]
 pub mod parser;
-pub mod parser_error;
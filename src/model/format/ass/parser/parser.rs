@@ -1,34 +1,33 @@
 use regex::Regex;
 use std::result::Result;
 
-use crate::model::format::ass::{
-    line_ass::{ASS_FIELDS_COUNT, AssField, AssLine},
-    parser::parser_error::ParserError,
+use crate::model::{
+    format::ass::{
+        event_format::EventFormat,
+        line_ass::{AssField, AssLine, EventKind},
+        text::AssText,
+    },
+    parse_error::ParserError,
+    synchronizer::SceneMarker,
+    timestamp::Timestamp,
 };
 
 pub struct Parser {
     style_re: Regex,
-    tags_re: Regex,
     additional_scene_re: Regex,
 }
 
 impl Parser {
     const SCRIPT_INFO_PREFIX: &str = "[Script Info]";
-    const STYLES_PREFIX: &str = "[V4+ Styles]";
     const STYLE_PREFIX: &str = "Style:";
-    const PLAYRESX_PREFIX: &str = "PlayResX:";
-    const PLAYRESY_PREFIX: &str = "PlayResY:";
-    const SCALED_PREFIX: &str = "ScaledBorderAndShadow:";
-    const DIALOGUE_PREFIX: &str = "Dialogue: ";
     const EVENTS_PREFIX: &str = "[Events]";
     const FORMAT_PREFIX: &str = "Format:";
+    const MARKED_PREFIX: &str = "Marked=";
 
     const SPECIAL_STYLES_RE: &str = r"(?i)Opening|Ending|OP|ED";
-    const TAGS_EXPRESSION_RE: &str = r"\{.*?\}";
+    const SCENE_NAME: &str = "ADDITIONAL SCENE";
     const ADDITIONAL_SCENE_RE: &str = r"(?i)(additional scene|extra scene|bonus scene|special)";
 
-    const STYLES_FORMAT: &str = "Format: Name, Fontname, Fontsize, PrimaryColour, SecondaryColour, OutlineColour, BackColour, Bold, Italic, Underline, StrikeOut, ScaleX, ScaleY, Spacing, Angle, BorderStyle, Outline, Shadow, Alignment, MarginL, MarginR, MarginV, Encoding";
-
     const FX_KEYWORD: &str = "fx";
     const TEMPLATE_KEYWORD: &str = "template";
     const CODE_KEYWORD: &str = "code";
@@ -37,163 +36,137 @@ impl Parser {
     pub fn new() -> Self {
         Self {
             style_re: Regex::new(Self::SPECIAL_STYLES_RE).unwrap(),
-            tags_re: Regex::new(Self::TAGS_EXPRESSION_RE).unwrap(),
             additional_scene_re: Regex::new(Self::ADDITIONAL_SCENE_RE).unwrap(),
         }
     }
 
-    fn strip_dialogue(&self, line: &str) -> Result<String, ParserError> {
-        match line.strip_prefix(Self::DIALOGUE_PREFIX) {
-            Some(stripped) => Ok(stripped.to_string()),
+    fn strip_event(&self, line: &str) -> Result<(EventKind, String), ParserError> {
+        match EventKind::from_line(line) {
+            Some(kind) => Ok((kind, line[kind.prefix().len()..].to_string())),
             None => Err(ParserError::DialoguePrefix),
         }
     }
 
-    fn split_line(&self, line: &str) -> Result<Vec<String>, ParserError> {
+    fn split_line(&self, line: &str, format: &EventFormat) -> Result<Vec<String>, ParserError> {
         let parts: Vec<String> = line
-            .splitn(ASS_FIELDS_COUNT, ',')
+            .splitn(format.len(), ',')
             .map(|s| s.to_string())
             .collect();
 
-        match parts.len() >= ASS_FIELDS_COUNT {
+        match parts.len() >= format.len() {
             true => Ok(parts),
-            false => Err(ParserError::MissingFields { found: parts.len() }),
+            false => Err(ParserError::MissingFields {
+                expected: format.len(),
+                found: parts.len(),
+            }),
         }
     }
 
-    fn build_line_key(&self, start: &str, end: &str, style: &str) -> String {
-        format!("{}|{}|{}", start, end, style)
+    fn field_to_string(&self, l: &AssLine, field: AssField) -> String {
+        match field {
+            AssField::Layer => l.layer.clone(),
+            AssField::Start => self.format_time(l.start, l.raw_start.as_deref()),
+            AssField::End => self.format_time(l.end, l.raw_end.as_deref()),
+            AssField::Style => l.style.clone(),
+            AssField::Name => l.name.clone(),
+            AssField::MarginL => l.margin_l.clone(),
+            AssField::MarginR => l.margin_r.clone(),
+            AssField::MarginV => l.margin_v.clone(),
+            AssField::Effect => l.effect.clone(),
+            AssField::Text => l.text.clone(),
+        }
     }
 
-    fn is_additional_scene(&self, line: &str) -> Result<bool, ParserError> {
-        Ok(self
-            .additional_scene_re
-            .is_match(&self.parse_ass_line(line)?.name))
+    pub fn ass_line_to_string(&self, l: &AssLine, format: &EventFormat) -> String {
+        let fields: Vec<String> = format
+            .columns()
+            .iter()
+            .map(|field| self.field_to_string(l, *field))
+            .collect();
+        format!("{}{}", l.kind.prefix(), fields.join(","))
     }
 
-    fn ass_line_to_string(&self, l: &AssLine) -> String {
-        format!(
-            "Dialogue: {},{},{},{},{},0,0,0,{},{}",
-            l.layer,
-            self.seconds_to_hms(l.start),
-            self.seconds_to_hms(l.end),
-            l.style,
-            l.name,
-            l.effect,
-            l.text
-        )
+    fn parse_time(&self, t: &str) -> Result<Timestamp, ParserError> {
+        Timestamp::parse_ass(t).ok_or_else(|| ParserError::invalid_timestamp(t))
     }
 
-    fn parse_time(&self, t: &str) -> f64 {
-        let parts: Vec<&str> = t.split(':').collect();
-        let h: f64 = parts[0].parse().unwrap();
-        let m: f64 = parts[1].parse().unwrap();
-
-        let (ss, cs) = {
-            let s = parts[2];
-            let s_parts: Vec<&str> = s.split('.').collect();
-            (
-                s_parts[0].parse::<f64>().unwrap(),
-                s_parts[1].parse::<f64>().unwrap(),
-            )
-        };
-
-        h * 3600.0 + m * 60.0 + ss + cs / 100.0
+    fn check_number(&self, value: String) -> Result<String, ParserError> {
+        let trimmed = value.trim();
+        let digits = trimmed.strip_prefix(Self::MARKED_PREFIX).unwrap_or(trimmed);
+        match digits.is_empty() || digits.parse::<i32>().is_ok() {
+            true => Ok(value),
+            false => Err(ParserError::invalid_number(trimmed)),
+        }
     }
 
-    fn seconds_to_hms(&self, seg: f64) -> String {
-        let h = (seg / 3600.0).floor() as i32;
-        let m = ((seg % 3600.0) / 60.0).floor() as i32;
-        let s = (seg % 60.0).floor() as i32;
-        let cs = ((seg - seg.floor()) * 100.0).round() as i32;
-        format!("{h}:{m:02}:{s:02}.{cs:02}")
+    fn format_time(&self, time: Timestamp, raw: Option<&str>) -> String {
+        match raw {
+            Some(raw) if Timestamp::parse_ass(raw) == Some(time) => raw.to_string(),
+            _ => time.to_ass(),
+        }
     }
 
-    pub fn parse_ass_line(&self, line: &str) -> Result<AssLine, ParserError> {
-        let stripped = self.strip_dialogue(line)?;
-        let parts = self.split_line(&stripped)?;
+    pub fn parse_ass_line(&self, line: &str, format: &EventFormat) -> Result<AssLine, ParserError> {
+        let (kind, stripped) = self.strip_event(line)?;
+        let parts = self.split_line(&stripped, format)?;
+        let field = |f: AssField| {
+            format
+                .position(f)
+                .map_or(String::new(), |i| parts[i].clone())
+        };
         Ok(AssLine {
-            layer: parts[AssField::Layer.index()].trim().to_string(),
-            start: self.parse_time(&parts[AssField::Start.index()]),
-            end: self.parse_time(&parts[AssField::End.index()]),
-            style: parts[AssField::Style.index()].trim().to_string(),
-            name: parts[AssField::Name.index()].trim().to_string(),
-            effect: parts[AssField::Effect.index()].trim().to_string(),
-            text: parts[AssField::Text.index()].trim().to_string(),
+            kind,
+            layer: self.check_number(field(AssField::Layer))?,
+            start: self.parse_time(&field(AssField::Start))?,
+            end: self.parse_time(&field(AssField::End))?,
+            style: field(AssField::Style),
+            name: field(AssField::Name),
+            margin_l: self.check_number(field(AssField::MarginL))?,
+            margin_r: self.check_number(field(AssField::MarginR))?,
+            margin_v: self.check_number(field(AssField::MarginV))?,
+            effect: field(AssField::Effect),
+            text: field(AssField::Text),
+            raw_start: Some(field(AssField::Start)),
+            raw_end: Some(field(AssField::End)),
         })
     }
 
-    pub fn get_styles_tag(&self) -> String {
-        Self::STYLES_PREFIX.to_string()
-    }
-
-    pub fn get_styles_format(&self) -> String {
-        Self::STYLES_FORMAT.to_string()
-    }
-
-    pub fn get_new_offset(
-        &self,
-        next_line: &str,
-        next_block_line: &str,
-    ) -> Result<f64, ParserError> {
-        let next_ass = self.parse_ass_line(next_line)?;
-        let next_block_ass = self.parse_ass_line(next_block_line)?;
-        Ok(next_block_ass.start - next_ass.start)
-    }
-
-    pub fn get_style(&self, line: &str) -> Result<String, ParserError> {
-        Ok(self.parse_ass_line(line)?.style)
-    }
-
-    pub fn get_text(&self, line: &str) -> Result<String, ParserError> {
-        Ok(self.parse_ass_line(line)?.text)
-    }
-
-    pub fn get_start_time(&self, line: &str) -> Result<f64, ParserError> {
-        Ok(self.parse_ass_line(line)?.start)
-    }
-
-    pub fn get_end_time(&self, line: &str) -> Result<f64, ParserError> {
-        Ok(self.parse_ass_line(line)?.end)
-    }
-
-    pub fn get_line_key(&self, line: &str) -> Result<String, ParserError> {
-        let ass_line = self.parse_ass_line(line)?;
-        Ok(self.build_line_key(
-            &ass_line.start.to_string(),
-            &ass_line.end.to_string(),
-            &ass_line.style,
-        ))
+    pub fn get_new_offset(&self, next_line: &AssLine, next_block_line: &AssLine) -> Timestamp {
+        next_block_line.start - next_line.start
     }
 
-    pub fn set_start_time(&self, line: &str, new_start: f64) -> Result<String, ParserError> {
-        let mut ass_line = self.parse_ass_line(line)?;
-        ass_line.start = new_start;
-        Ok(self.ass_line_to_string(&ass_line))
+    pub fn get_line_key(&self, line: &AssLine) -> String {
+        format!(
+            "{}|{}|{}",
+            line.start.millis(),
+            line.end.millis(),
+            line.style
+        )
     }
 
-    pub fn set_end_time(&self, line: &str, new_end: f64) -> Result<String, ParserError> {
-        let mut ass_line = self.parse_ass_line(line)?;
-        ass_line.end = new_end;
-        Ok(self.ass_line_to_string(&ass_line))
+    pub fn is_scene_line(&self, line: &AssLine) -> bool {
+        self.additional_scene_re.is_match(&line.name)
     }
 
-    pub fn is_scene_line(&self, line: &str) -> Result<bool, ParserError> {
-        Ok(self.is_dialogue(line) && self.is_additional_scene(line)?)
+    pub fn mark_scene(&self, line: &mut AssLine) {
+        if !self.is_scene_line(line) {
+            line.name = Self::SCENE_NAME.to_string();
+        }
     }
 
-    pub fn is_special_style(&self, line: &str) -> Result<bool, ParserError> {
-        Ok(self.style_re.is_match(&self.parse_ass_line(line)?.style))
+    pub fn is_special_style(&self, line: &AssLine) -> bool {
+        self.style_re.is_match(&line.style)
     }
 
-    pub fn is_text_empty(&self, line: &str) -> Result<bool, ParserError> {
-        let text = self.parse_ass_line(line)?.text;
-        Ok(self.tags_re.replace_all(&text, "").trim().is_empty())
+    pub fn is_text_empty(&self, line: &AssLine) -> bool {
+        AssText::parse(&line.text)
+            .strip_overrides()
+            .trim()
+            .is_empty()
     }
 
     pub fn has_excessive_tags(&self, text: &str) -> bool {
-        let total_tags_len: usize = self.tags_re.find_iter(text).map(|m| m.as_str().len()).sum();
-        total_tags_len > Self::EXCESSIVE_TAGS_THRESHOLD
+        AssText::parse(text).override_len() > Self::EXCESSIVE_TAGS_THRESHOLD
     }
 
     fn is_technical_effect(&self, effect: &str) -> bool {
@@ -205,21 +178,14 @@ impl Parser {
             || trimmed.starts_with(Self::CODE_KEYWORD)
     }
 
-    pub fn is_technical_garbage(&self, line: &str) -> Result<bool, ParserError> {
-        let ass_line = self.parse_ass_line(line)?;
-        if self.is_technical_effect(&ass_line.effect) {
-            return Ok(true);
+    pub fn is_technical_garbage(&self, line: &AssLine) -> bool {
+        if self.is_technical_effect(&line.effect) {
+            return true;
         }
-        if self.has_excessive_tags(&ass_line.text) {
-            return Ok(true);
+        if self.has_excessive_tags(&line.text) {
+            return true;
         }
-        let clean_text = self
-            .tags_re
-            .replace_all(&ass_line.text, "")
-            .trim()
-            .to_string();
-
-        Ok(clean_text.is_empty())
+        self.is_text_empty(line)
     }
 
     pub fn is_events_section(&self, line: &str) -> bool {
@@ -230,65 +196,40 @@ impl Parser {
         line.starts_with(Self::FORMAT_PREFIX)
     }
 
-    pub fn is_dialogue(&self, line: &str) -> bool {
-        line.starts_with(Self::DIALOGUE_PREFIX)
+    pub fn is_event(&self, line: &str) -> bool {
+        EventKind::from_line(line).is_some()
     }
 
     pub fn is_script_info(&self, line: &str) -> bool {
         line.contains(Self::SCRIPT_INFO_PREFIX)
     }
 
-    pub fn is_v4_styles(&self, line: &str) -> bool {
-        line.trim() == Self::STYLES_PREFIX
-    }
-
     pub fn is_section_start(&self, line: &str) -> bool {
         line.trim().starts_with('[')
     }
 
-    pub fn is_playresx(&self, line: &str) -> bool {
-        line.trim().starts_with(Self::PLAYRESX_PREFIX)
-    }
-
-    pub fn is_playresy(&self, line: &str) -> bool {
-        line.trim().starts_with(Self::PLAYRESY_PREFIX)
-    }
-
-    pub fn is_scaled_border(&self, line: &str) -> bool {
-        line.trim().starts_with(Self::SCALED_PREFIX)
-    }
-
-    pub fn format_playresx(&self, value: u32) -> String {
-        format!("{} {}", Self::PLAYRESX_PREFIX, value)
-    }
-
-    pub fn format_playresy(&self, value: u32) -> String {
-        format!("{} {}", Self::PLAYRESY_PREFIX, value)
-    }
-
-    fn bool_to_str(active: bool) -> &'static str {
-        match active {
-            true => "yes",
-            false => "no",
-        }
-    }
-    pub fn format_scaled_border(&self, active: bool) -> String {
-        format!("{} {}", Self::SCALED_PREFIX, Self::bool_to_str(active))
+    pub fn is_section_header(&self, line: &str) -> bool {
+        let trimmed = line.trim();
+        trimmed.len() > 2 && trimmed.ends_with(']') && !trimmed[1..].contains('[')
     }
 
     pub fn is_style_definition(&self, line: &str) -> bool {
         line.trim().starts_with(Self::STYLE_PREFIX)
     }
+}
+
+impl Default for Parser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
-    pub fn replace_text(&self, line: &str, translation: &str) -> Result<String, ParserError> {
-        let mut ass_line = self.parse_ass_line(line)?;
-        ass_line.text = translation.to_string();
-        Ok(self.ass_line_to_string(&ass_line))
+impl SceneMarker<AssLine> for Parser {
+    fn is_scene_line(&self, line: &AssLine) -> bool {
+        Parser::is_scene_line(self, line)
     }
 
-    pub fn replace_style(&self, line: &str, style: &str) -> Result<String, ParserError> {
-        let mut ass_line = self.parse_ass_line(line)?;
-        ass_line.style = style.to_string();
-        Ok(self.ass_line_to_string(&ass_line))
+    fn mark_scene(&self, line: &mut AssLine) {
+        Parser::mark_scene(self, line)
     }
 }
@@ -1,59 +1,49 @@
-use crate::model::format::ass::{
-    ass_segmenter::AssSegmenter,
-    line_collector::LineCollector,
-    parser::{parser::Parser, parser_error::ParseRes},
+use crate::model::{
+    format::ass::{document::AssDocument, line_ass::AssLine},
+    timestamp::Timestamp,
 };
 
-type LinesVec<'a> = Vec<&'a String>;
-type TimedDialogues<'a> = Vec<(f64, &'a String)>;
-
 pub struct Sorter {
-    parser: Parser,
-    segmenter: AssSegmenter,
+    keep_comments: bool,
 }
 
 impl Sorter {
-    const MILLISECONDS_PER_SECOND: f64 = 1000.0;
-
     pub fn new() -> Self {
         Self {
-            parser: Parser::new(),
-            segmenter: AssSegmenter::new(),
+            keep_comments: true,
         }
     }
 
-    fn push_timed<'a>(&self, line: &'a String, to: &mut TimedDialogues<'a>) -> ParseRes<()> {
-        let start_time = self.parser.get_start_time(line)?;
-        to.push((start_time, line));
-        Ok(())
+    pub fn with_comments(mut self, keep_comments: bool) -> Self {
+        self.keep_comments = keep_comments;
+        self
     }
 
-    fn dialogue_sort_key(event: &(f64, &String)) -> u64 {
-        (event.0 * Self::MILLISECONDS_PER_SECOND) as u64
+    fn event_sort_key(line: &AssLine) -> Timestamp {
+        line.start
     }
 
-    fn sort_dialogues(&self, timed_dialogues: &mut TimedDialogues) {
-        timed_dialogues.sort_by_key(Self::dialogue_sort_key);
+    fn sort_events(&self, lines: &mut [AssLine]) {
+        lines.sort_by_key(Self::event_sort_key);
     }
 
-    fn process<'a>(&self, lines: &LinesVec<'a>, timed: &mut TimedDialogues<'a>) -> ParseRes<()> {
-        for line in lines {
-            self.push_timed(line, timed)?;
+    pub fn run(&self, doc: &mut AssDocument) {
+        if !self.keep_comments {
+            doc.remove_comments();
+        }
+        for events in doc.events_mut() {
+            let mut sorted: Vec<AssLine> = events.lines().cloned().collect();
+            self.sort_events(&mut sorted);
+            for (slot, line) in events.lines_mut().zip(sorted) {
+                *slot = line;
+            }
         }
-        Ok(())
     }
+}
 
-    pub fn run(&self, lines: &[String]) -> ParseRes<Vec<String>> {
-        let mut output_lines = Vec::new();
-        let lines_ref: Vec<&String> = lines.iter().collect();
-        let mut timed_dialogues: TimedDialogues = Vec::new();
-        let header_lines = self.segmenter.extract_header(&lines_ref, &self.parser);
-        let mut dialogue_lines = self.segmenter.extract_dialogues(&lines_ref, &self.parser);
-        self.process(&mut dialogue_lines, &mut timed_dialogues)?;
-        self.sort_dialogues(&mut timed_dialogues);
-        let mut collector = LineCollector::new(&mut output_lines);
-        collector.collect_all(&header_lines, &timed_dialogues);
-        Ok(output_lines)
+impl Default for Sorter {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -1,4 +1,4 @@
-use crate::model::format::ass::parser::parser_error::ParserError;
+use crate::model::parse_error::{ParseRes, ParserError};
 
 use super::*;
 
@@ -100,12 +100,36 @@ static MISSING_FIELDS_CASE: TestCase = TestCase {
     expected: &[],
 };
 
+static COMMENTS_CASE: TestCase = TestCase {
+    name: "sorts comments along with dialogues",
+    input: &[
+        "[Events]",
+        "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text",
+        "Dialogue: 0,0:00:03.00,0:00:04.00,Default,,0,0,0,,Line 2",
+        "Comment: 0,0:00:02.00,0:00:03.00,Default,,0,0,0,,Note for line 2",
+        "Dialogue: 0,0:00:01.00,0:00:02.00,Default,,0,0,0,,Line 1",
+    ],
+    expected: &[
+        "[Events]",
+        "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text",
+        "Dialogue: 0,0:00:01.00,0:00:02.00,Default,,0,0,0,,Line 1",
+        "Comment: 0,0:00:02.00,0:00:03.00,Default,,0,0,0,,Note for line 2",
+        "Dialogue: 0,0:00:03.00,0:00:04.00,Default,,0,0,0,,Line 2",
+    ],
+};
+
 fn run_test_case(test_case: &TestCase) -> ParseRes<()> {
     let input: Vec<String> = test_case.input.iter().map(|s| s.to_string()).collect();
     let expected: Vec<String> = test_case.expected.iter().map(|s| s.to_string()).collect();
+    let mut doc = AssDocument::parse(&input)?;
     let sorter = Sorter::new();
-    let result = sorter.run(&input)?;
-    assert_eq!(result, expected, "Failed at case: {}", test_case.name);
+    sorter.run(&mut doc);
+    assert_eq!(
+        doc.serialize(),
+        expected,
+        "Failed at case: {}",
+        test_case.name
+    );
     Ok(())
 }
 
@@ -134,7 +158,23 @@ fn test_fails_on_corrupt_time() {
 fn test_error_on_corrupt_dialogue() {
     let result = run_test_case(&MISSING_FIELDS_CASE);
     match result {
-        Err(ParserError::MissingFields { found }) => assert_eq!(found, 3),
+        Err(ParserError::MissingFields { found, .. }) => assert_eq!(found, 3),
         _ => panic!("Expected MissingFields(3), got {:?}", result),
     }
 }
+
+#[test]
+fn test_ordering_with_comments() -> ParseRes<()> {
+    run_test_case(&COMMENTS_CASE)
+}
+
+#[test]
+fn test_ordering_drops_comments() -> ParseRes<()> {
+    let input: Vec<String> = COMMENTS_CASE.input.iter().map(|s| s.to_string()).collect();
+    let mut doc = AssDocument::parse(&input)?;
+    Sorter::new().with_comments(false).run(&mut doc);
+    let result = doc.serialize();
+    assert_eq!(result.len(), 4);
+    assert!(result.iter().all(|l| !l.starts_with("Comment:")));
+    Ok(())
+}
@@ -0,0 +1,495 @@
+use std::str::FromStr;
+
+use crate::model::{
+    format::ass::style_format::StyleFormat,
+    parse_error::{ParseRes, ParserError},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StyleField {
+    Name,
+    Fontname,
+    Fontsize,
+    PrimaryColour,
+    SecondaryColour,
+    OutlineColour,
+    BackColour,
+    Bold,
+    Italic,
+    Underline,
+    StrikeOut,
+    ScaleX,
+    ScaleY,
+    Spacing,
+    Angle,
+    BorderStyle,
+    Outline,
+    Shadow,
+    Alignment,
+    MarginL,
+    MarginR,
+    MarginV,
+    AlphaLevel,
+    Encoding,
+}
+
+impl StyleField {
+    pub const V4_PLUS: [StyleField; 23] = [
+        StyleField::Name,
+        StyleField::Fontname,
+        StyleField::Fontsize,
+        StyleField::PrimaryColour,
+        StyleField::SecondaryColour,
+        StyleField::OutlineColour,
+        StyleField::BackColour,
+        StyleField::Bold,
+        StyleField::Italic,
+        StyleField::Underline,
+        StyleField::StrikeOut,
+        StyleField::ScaleX,
+        StyleField::ScaleY,
+        StyleField::Spacing,
+        StyleField::Angle,
+        StyleField::BorderStyle,
+        StyleField::Outline,
+        StyleField::Shadow,
+        StyleField::Alignment,
+        StyleField::MarginL,
+        StyleField::MarginR,
+        StyleField::MarginV,
+        StyleField::Encoding,
+    ];
+
+    pub const SSA: [StyleField; 18] = [
+        StyleField::Name,
+        StyleField::Fontname,
+        StyleField::Fontsize,
+        StyleField::PrimaryColour,
+        StyleField::SecondaryColour,
+        StyleField::OutlineColour,
+        StyleField::BackColour,
+        StyleField::Bold,
+        StyleField::Italic,
+        StyleField::BorderStyle,
+        StyleField::Outline,
+        StyleField::Shadow,
+        StyleField::Alignment,
+        StyleField::MarginL,
+        StyleField::MarginR,
+        StyleField::MarginV,
+        StyleField::AlphaLevel,
+        StyleField::Encoding,
+    ];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "name" => Some(StyleField::Name),
+            "fontname" => Some(StyleField::Fontname),
+            "fontsize" => Some(StyleField::Fontsize),
+            "primarycolour" => Some(StyleField::PrimaryColour),
+            "secondarycolour" => Some(StyleField::SecondaryColour),
+            "outlinecolour" | "tertiarycolour" => Some(StyleField::OutlineColour),
+            "backcolour" => Some(StyleField::BackColour),
+            "bold" => Some(StyleField::Bold),
+            "italic" => Some(StyleField::Italic),
+            "underline" => Some(StyleField::Underline),
+            "strikeout" => Some(StyleField::StrikeOut),
+            "scalex" => Some(StyleField::ScaleX),
+            "scaley" => Some(StyleField::ScaleY),
+            "spacing" => Some(StyleField::Spacing),
+            "angle" => Some(StyleField::Angle),
+            "borderstyle" => Some(StyleField::BorderStyle),
+            "outline" => Some(StyleField::Outline),
+            "shadow" => Some(StyleField::Shadow),
+            "alignment" => Some(StyleField::Alignment),
+            "marginl" => Some(StyleField::MarginL),
+            "marginr" => Some(StyleField::MarginR),
+            "marginv" => Some(StyleField::MarginV),
+            "alphalevel" => Some(StyleField::AlphaLevel),
+            "encoding" => Some(StyleField::Encoding),
+            _ => None,
+        }
+    }
+
+    pub fn name(self, legacy: bool) -> &'static str {
+        match self {
+            StyleField::Name => "Name",
+            StyleField::Fontname => "Fontname",
+            StyleField::Fontsize => "Fontsize",
+            StyleField::PrimaryColour => "PrimaryColour",
+            StyleField::SecondaryColour => "SecondaryColour",
+            StyleField::OutlineColour if legacy => "TertiaryColour",
+            StyleField::OutlineColour => "OutlineColour",
+            StyleField::BackColour => "BackColour",
+            StyleField::Bold => "Bold",
+            StyleField::Italic => "Italic",
+            StyleField::Underline => "Underline",
+            StyleField::StrikeOut => "StrikeOut",
+            StyleField::ScaleX => "ScaleX",
+            StyleField::ScaleY => "ScaleY",
+            StyleField::Spacing => "Spacing",
+            StyleField::Angle => "Angle",
+            StyleField::BorderStyle => "BorderStyle",
+            StyleField::Outline => "Outline",
+            StyleField::Shadow => "Shadow",
+            StyleField::Alignment => "Alignment",
+            StyleField::MarginL => "MarginL",
+            StyleField::MarginR => "MarginR",
+            StyleField::MarginV => "MarginV",
+            StyleField::AlphaLevel => "AlphaLevel",
+            StyleField::Encoding => "Encoding",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AssColor {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+impl AssColor {
+    const HEX_PREFIX: &str = "&H";
+    const HEX_SUFFIX: char = '&';
+
+    pub const fn new(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self { r, g, b, a }
+    }
+
+    fn from_abgr(value: u32) -> Self {
+        let [r, g, b, a] = value.to_le_bytes();
+        Self { r, g, b, a }
+    }
+
+    fn abgr(self) -> u32 {
+        u32::from_le_bytes([self.r, self.g, self.b, self.a])
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        let hex = value
+            .get(..Self::HEX_PREFIX.len())
+            .filter(|prefix| prefix.eq_ignore_ascii_case(Self::HEX_PREFIX))
+            .map(|_| value[Self::HEX_PREFIX.len()..].trim_end_matches(Self::HEX_SUFFIX));
+        match hex {
+            Some(digits) => u32::from_str_radix(digits, 16).ok().map(Self::from_abgr),
+            None => value.parse::<i64>().ok().map(|v| Self::from_abgr(v as u32)),
+        }
+    }
+
+    pub fn to_ass(self) -> String {
+        format!(
+            "{}{:02X}{:02X}{:02X}{:02X}",
+            Self::HEX_PREFIX,
+            self.a,
+            self.b,
+            self.g,
+            self.r
+        )
+    }
+
+    pub fn to_bgr(self) -> String {
+        format!(
+            "{}{:02X}{:02X}{:02X}{}",
+            Self::HEX_PREFIX,
+            self.b,
+            self.g,
+            self.r,
+            Self::HEX_SUFFIX
+        )
+    }
+
+    pub fn to_ssa(self) -> String {
+        (self.abgr() as i32).to_string()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AssStyle {
+    pub name: String,
+    pub fontname: String,
+    pub fontsize: f64,
+    pub primary_colour: AssColor,
+    pub secondary_colour: AssColor,
+    pub outline_colour: AssColor,
+    pub back_colour: AssColor,
+    pub bold: bool,
+    pub italic: bool,
+    pub underline: bool,
+    pub strike_out: bool,
+    pub scale_x: f64,
+    pub scale_y: f64,
+    pub spacing: f64,
+    pub angle: f64,
+    pub border_style: i32,
+    pub outline: f64,
+    pub shadow: f64,
+    pub alignment: u8,
+    pub margin_l: i32,
+    pub margin_r: i32,
+    pub margin_v: i32,
+    pub alpha_level: i32,
+    pub encoding: i32,
+    raw: Vec<(StyleField, String)>,
+}
+
+impl Default for AssStyle {
+    fn default() -> Self {
+        Self {
+            name: "Default".to_string(),
+            fontname: "Arial".to_string(),
+            fontsize: 20.0,
+            primary_colour: AssColor::new(0xFF, 0xFF, 0xFF, 0),
+            secondary_colour: AssColor::new(0xFF, 0, 0, 0),
+            outline_colour: AssColor::default(),
+            back_colour: AssColor::default(),
+            bold: false,
+            italic: false,
+            underline: false,
+            strike_out: false,
+            scale_x: 100.0,
+            scale_y: 100.0,
+            spacing: 0.0,
+            angle: 0.0,
+            border_style: 1,
+            outline: 2.0,
+            shadow: 2.0,
+            alignment: 2,
+            margin_l: 10,
+            margin_r: 10,
+            margin_v: 10,
+            alpha_level: 0,
+            encoding: 1,
+            raw: Vec::new(),
+        }
+    }
+}
+
+impl AssStyle {
+    const STYLE_PREFIX: &str = "Style:";
+    const TRUE_VALUE: &str = "-1";
+    const FALSE_VALUE: &str = "0";
+    const LEGACY_TOP_OFFSET: u8 = 4;
+    const LEGACY_MIDDLE_OFFSET: u8 = 8;
+
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            ..Self::default()
+        }
+    }
+
+    pub fn with_font(mut self, fontname: &str, fontsize: f64) -> Self {
+        self.fontname = fontname.to_string();
+        self.fontsize = fontsize;
+        self
+    }
+
+    pub fn with_border(mut self, outline: f64, shadow: f64) -> Self {
+        self.outline = outline;
+        self.shadow = shadow;
+        self
+    }
+
+    pub fn with_margins(mut self, margin_l: i32, margin_r: i32, margin_v: i32) -> Self {
+        self.margin_l = margin_l;
+        self.margin_r = margin_r;
+        self.margin_v = margin_v;
+        self
+    }
+
+    fn parse_number<T: FromStr>(value: &str) -> ParseRes<T> {
+        value
+            .trim()
+            .parse()
+            .map_err(|_| ParserError::invalid_number(value.trim()))
+    }
+
+    fn parse_bool(value: &str) -> ParseRes<bool> {
+        Ok(Self::parse_number::<i32>(value)? != 0)
+    }
+
+    fn parse_colour(value: &str) -> ParseRes<AssColor> {
+        AssColor::parse(value).ok_or_else(|| ParserError::invalid_number(value.trim()))
+    }
+
+    fn from_legacy_alignment(value: u8) -> Option<u8> {
+        match value {
+            1..=3 => Some(value),
+            5..=7 => Some(value + 2),
+            9..=11 => Some(value - 5),
+            _ => None,
+        }
+    }
+
+    fn to_legacy_alignment(value: u8) -> u8 {
+        match value {
+            7..=9 => value - 6 + Self::LEGACY_TOP_OFFSET,
+            4..=6 => value - 3 + Self::LEGACY_MIDDLE_OFFSET,
+            _ => value,
+        }
+    }
+
+    fn parse_alignment(value: &str, legacy: bool) -> ParseRes<u8> {
+        let number = Self::parse_number::<u8>(value)?;
+        let alignment = match legacy {
+            true => Self::from_legacy_alignment(number),
+            false => Some(number).filter(|n| (1..=9).contains(n)),
+        };
+        alignment.ok_or_else(|| ParserError::invalid_number(value.trim()))
+    }
+
+    fn set_field(&mut self, field: StyleField, value: &str, legacy: bool) -> ParseRes<()> {
+        match field {
+            StyleField::Name => self.name = value.trim().to_string(),
+            StyleField::Fontname => self.fontname = value.trim().to_string(),
+            StyleField::Fontsize => self.fontsize = Self::parse_number(value)?,
+            StyleField::PrimaryColour => self.primary_colour = Self::parse_colour(value)?,
+            StyleField::SecondaryColour => self.secondary_colour = Self::parse_colour(value)?,
+            StyleField::OutlineColour => self.outline_colour = Self::parse_colour(value)?,
+            StyleField::BackColour => self.back_colour = Self::parse_colour(value)?,
+            StyleField::Bold => self.bold = Self::parse_bool(value)?,
+            StyleField::Italic => self.italic = Self::parse_bool(value)?,
+            StyleField::Underline => self.underline = Self::parse_bool(value)?,
+            StyleField::StrikeOut => self.strike_out = Self::parse_bool(value)?,
+            StyleField::ScaleX => self.scale_x = Self::parse_number(value)?,
+            StyleField::ScaleY => self.scale_y = Self::parse_number(value)?,
+            StyleField::Spacing => self.spacing = Self::parse_number(value)?,
+            StyleField::Angle => self.angle = Self::parse_number(value)?,
+            StyleField::BorderStyle => self.border_style = Self::parse_number(value)?,
+            StyleField::Outline => self.outline = Self::parse_number(value)?,
+            StyleField::Shadow => self.shadow = Self::parse_number(value)?,
+            StyleField::Alignment => self.alignment = Self::parse_alignment(value, legacy)?,
+            StyleField::MarginL => self.margin_l = Self::parse_number(value)?,
+            StyleField::MarginR => self.margin_r = Self::parse_number(value)?,
+            StyleField::MarginV => self.margin_v = Self::parse_number(value)?,
+            StyleField::AlphaLevel => self.alpha_level = Self::parse_number(value)?,
+            StyleField::Encoding => self.encoding = Self::parse_number(value)?,
+        }
+        Ok(())
+    }
+
+    fn bool_to_str(value: bool) -> String {
+        match value {
+            true => Self::TRUE_VALUE.to_string(),
+            false => Self::FALSE_VALUE.to_string(),
+        }
+    }
+
+    fn colour_to_str(colour: AssColor, legacy: bool) -> String {
+        match legacy {
+            true => colour.to_ssa(),
+            false => colour.to_ass(),
+        }
+    }
+
+    fn alignment_to_str(alignment: u8, legacy: bool) -> String {
+        match legacy {
+            true => Self::to_legacy_alignment(alignment).to_string(),
+            false => alignment.to_string(),
+        }
+    }
+
+    fn field_to_string(&self, field: StyleField, legacy: bool) -> String {
+        match field {
+            StyleField::Name => self.name.clone(),
+            StyleField::Fontname => self.fontname.clone(),
+            StyleField::Fontsize => self.fontsize.to_string(),
+            StyleField::PrimaryColour => Self::colour_to_str(self.primary_colour, legacy),
+            StyleField::SecondaryColour => Self::colour_to_str(self.secondary_colour, legacy),
+            StyleField::OutlineColour => Self::colour_to_str(self.outline_colour, legacy),
+            StyleField::BackColour => Self::colour_to_str(self.back_colour, legacy),
+            StyleField::Bold => Self::bool_to_str(self.bold),
+            StyleField::Italic => Self::bool_to_str(self.italic),
+            StyleField::Underline => Self::bool_to_str(self.underline),
+            StyleField::StrikeOut => Self::bool_to_str(self.strike_out),
+            StyleField::ScaleX => self.scale_x.to_string(),
+            StyleField::ScaleY => self.scale_y.to_string(),
+            StyleField::Spacing => self.spacing.to_string(),
+            StyleField::Angle => self.angle.to_string(),
+            StyleField::BorderStyle => self.border_style.to_string(),
+            StyleField::Outline => self.outline.to_string(),
+            StyleField::Shadow => self.shadow.to_string(),
+            StyleField::Alignment => Self::alignment_to_str(self.alignment, legacy),
+            StyleField::MarginL => self.margin_l.to_string(),
+            StyleField::MarginR => self.margin_r.to_string(),
+            StyleField::MarginV => self.margin_v.to_string(),
+            StyleField::AlphaLevel => self.alpha_level.to_string(),
+            StyleField::Encoding => self.encoding.to_string(),
+        }
+    }
+
+    fn raw_value(&self, field: StyleField) -> Option<&str> {
+        self.raw
+            .iter()
+            .find(|(raw_field, _)| *raw_field == field)
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn canonical(field: StyleField, raw: &str, legacy: bool) -> Option<String> {
+        let mut style = Self::default();
+        style.set_field(field, raw, legacy).ok()?;
+        Some(style.field_to_string(field, legacy))
+    }
+
+    fn format_field(&self, field: StyleField, legacy: bool) -> String {
+        let value = self.field_to_string(field, legacy);
+        match self.raw_value(field) {
+            Some(raw) if Self::canonical(field, raw, legacy).as_ref() == Some(&value) => {
+                raw.to_string()
+            }
+            _ => value,
+        }
+    }
+
+    fn split_body<'a>(line: &'a str, format: &StyleFormat) -> ParseRes<Vec<&'a str>> {
+        let body = line.trim_start();
+        let body = body.strip_prefix(Self::STYLE_PREFIX).unwrap_or(body);
+        let body = body.strip_prefix(' ').unwrap_or(body);
+        let values: Vec<&str> = body.splitn(format.len(), ',').collect();
+        match values.len() == format.len() {
+            true => Ok(values),
+            false => Err(ParserError::MissingFields {
+                expected: format.len(),
+                found: values.len(),
+            }),
+        }
+    }
+
+    pub fn parse(line: &str, format: &StyleFormat) -> ParseRes<Self> {
+        let values = Self::split_body(line, format)?;
+        let mut style = Self::default();
+        for (field, value) in format.columns().iter().zip(values) {
+            style.set_field(*field, value, format.is_legacy())?;
+            style.raw.push((*field, value.to_string()));
+        }
+        Ok(style)
+    }
+
+    pub fn to_line(&self, format: &StyleFormat) -> String {
+        let values: Vec<String> = format
+            .columns()
+            .iter()
+            .map(|field| self.format_field(*field, format.is_legacy()))
+            .collect();
+        format!("{} {}", Self::STYLE_PREFIX, values.join(","))
+    }
+
+    pub fn convert_to_v4_plus(&mut self) {
+        let alpha = self.alpha_level.clamp(0, u8::MAX as i32) as u8;
+        for colour in [
+            &mut self.primary_colour,
+            &mut self.secondary_colour,
+            &mut self.outline_colour,
+        ] {
+            colour.a = alpha;
+        }
+        self.alpha_level = 0;
+        self.raw.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests;
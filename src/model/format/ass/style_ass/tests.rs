@@ -0,0 +1,142 @@
+use crate::model::parse_error::{ParseRes, ParserError};
+
+use super::*;
+
+struct TestCase {
+    name: &'static str,
+    format: &'static str,
+    legacy: bool,
+    input: &'static str,
+}
+
+static V4_PLUS_CASE: TestCase = TestCase {
+    name: "parses a full V4+ style",
+    format: "Format: Name, Fontname, Fontsize, PrimaryColour, SecondaryColour, OutlineColour, BackColour, Bold, Italic, Underline, StrikeOut, ScaleX, ScaleY, Spacing, Angle, BorderStyle, Outline, Shadow, Alignment, MarginL, MarginR, MarginV, Encoding",
+    legacy: false,
+    input: "Style: Sign,Tahoma,24.0,&H00FFFFFF,&H000000FF,&H80102030,&HA0000000,-1,0,0,1,100,95.5,0,0,3,2,1,8,0010,0020,0030,1",
+};
+
+static SSA_CASE: TestCase = TestCase {
+    name: "parses a legacy SSA style",
+    format: "Format: Name, Fontname, Fontsize, PrimaryColour, SecondaryColour, TertiaryColour, BackColour, Bold, Italic, BorderStyle, Outline, Shadow, Alignment, MarginL, MarginR, MarginV, AlphaLevel, Encoding",
+    legacy: true,
+    input: "Style: Default,Arial,20,65535,16777215,0,-2147483640,-1,0,1,2,2,6,30,30,10,128,0",
+};
+
+static REORDERED_CASE: TestCase = TestCase {
+    name: "maps values through a custom format",
+    format: "Format: Fontsize, Name, Alignment",
+    legacy: false,
+    input: "Style: 32,Custom,7",
+};
+
+fn parse(test_case: &TestCase) -> ParseRes<(AssStyle, StyleFormat)> {
+    let format = StyleFormat::parse(test_case.format, test_case.legacy)?;
+    Ok((AssStyle::parse(test_case.input, &format)?, format))
+}
+
+#[test]
+fn test_typed_v4_plus_fields() -> ParseRes<()> {
+    let (style, _) = parse(&V4_PLUS_CASE)?;
+    assert_eq!(style.name, "Sign", "Failed at case: {}", V4_PLUS_CASE.name);
+    assert_eq!(style.fontsize, 24.0);
+    assert_eq!(style.outline_colour, AssColor::new(0x30, 0x20, 0x10, 0x80));
+    assert_eq!(style.back_colour.a, 0xA0);
+    assert!(style.bold && style.strike_out && !style.italic);
+    assert_eq!(style.scale_y, 95.5);
+    assert_eq!(style.border_style, 3);
+    assert_eq!(style.alignment, 8);
+    assert_eq!(
+        (style.margin_l, style.margin_r, style.margin_v),
+        (10, 20, 30)
+    );
+    Ok(())
+}
+
+#[test]
+fn test_round_trip_is_verbatim() -> ParseRes<()> {
+    for case in [&V4_PLUS_CASE, &SSA_CASE, &REORDERED_CASE] {
+        let (style, format) = parse(case)?;
+        assert_eq!(
+            style.to_line(&format),
+            case.input,
+            "Failed at case: {}",
+            case.name
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn test_edit_changes_only_edited_field() -> ParseRes<()> {
+    let (mut style, format) = parse(&V4_PLUS_CASE)?;
+    style.italic = true;
+    style.primary_colour = AssColor::new(0xFF, 0, 0, 0);
+    assert_eq!(
+        style.to_line(&format),
+        "Style: Sign,Tahoma,24.0,&H000000FF,&H000000FF,&H80102030,&HA0000000,-1,-1,0,1,100,95.5,0,0,3,2,1,8,0010,0020,0030,1"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_legacy_fields() -> ParseRes<()> {
+    let (style, _) = parse(&SSA_CASE)?;
+    assert_eq!(style.alignment, 8, "Failed at case: {}", SSA_CASE.name);
+    assert_eq!(style.primary_colour, AssColor::new(0xFF, 0xFF, 0, 0));
+    assert_eq!(style.back_colour, AssColor::new(0x08, 0, 0, 0x80));
+    assert_eq!(style.alpha_level, 128);
+    Ok(())
+}
+
+#[test]
+fn test_legacy_alignment_mapping() {
+    for legacy in [1, 2, 3, 5, 6, 7, 9, 10, 11] {
+        let numpad = AssStyle::from_legacy_alignment(legacy).unwrap();
+        assert_eq!(AssStyle::to_legacy_alignment(numpad), legacy);
+    }
+    assert_eq!(AssStyle::from_legacy_alignment(9), Some(4));
+    assert_eq!(AssStyle::from_legacy_alignment(4), None);
+}
+
+#[test]
+fn test_convert_to_v4_plus() -> ParseRes<()> {
+    let (mut style, _) = parse(&SSA_CASE)?;
+    style.convert_to_v4_plus();
+    assert_eq!(
+        style.to_line(&StyleFormat::default()),
+        "Style: Default,Arial,20,&H8000FFFF,&H80FFFFFF,&H80000000,&H80000008,-1,0,0,0,100,100,0,0,1,2,2,8,30,30,10,0"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_new_style_uses_defaults() {
+    let style = AssStyle::new("Main").with_font("Trebuchet MS", 24.0);
+    assert_eq!(
+        style.to_line(&StyleFormat::default()),
+        "Style: Main,Trebuchet MS,24,&H00FFFFFF,&H000000FF,&H00000000,&H00000000,0,0,0,0,100,100,0,0,1,2,2,2,10,10,10,1"
+    );
+}
+
+#[test]
+fn test_error_invalid_number() {
+    let format = StyleFormat::default();
+    let result = AssStyle::parse(
+        "Style: Bad,Arial,big,&H00FFFFFF,&H000000FF,&H00000000,&H00000000,0,0,0,0,100,100,0,0,1,2,2,2,10,10,10,1",
+        &format,
+    );
+    match result {
+        Err(ParserError::InvalidNumber { found, .. }) => assert_eq!(found, "big"),
+        _ => panic!("Expected InvalidNumber, got {:?}", result),
+    }
+}
+
+#[test]
+fn test_error_missing_name_column() {
+    let result = StyleFormat::parse("Format: Fontname, Fontsize", false);
+    match result {
+        Err(ParserError::MissingColumn { column }) => assert_eq!(column, "Name"),
+        _ => panic!("Expected MissingColumn(Name), got {:?}", result),
+    }
+}
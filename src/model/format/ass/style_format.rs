@@ -0,0 +1,84 @@
+use crate::model::{
+    format::ass::style_ass::StyleField,
+    parse_error::{ParseRes, ParserError},
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StyleFormat {
+    columns: Vec<StyleField>,
+    legacy: bool,
+}
+
+impl Default for StyleFormat {
+    fn default() -> Self {
+        Self {
+            columns: StyleField::V4_PLUS.to_vec(),
+            legacy: false,
+        }
+    }
+}
+
+impl StyleFormat {
+    const FORMAT_PREFIX: &str = "Format:";
+
+    pub fn ssa() -> Self {
+        Self {
+            columns: StyleField::SSA.to_vec(),
+            legacy: true,
+        }
+    }
+
+    fn parse_column(name: &str) -> ParseRes<StyleField> {
+        StyleField::from_name(name).ok_or_else(|| ParserError::UnknownColumn {
+            column: name.trim().to_string(),
+        })
+    }
+
+    fn check_required(&self) -> ParseRes<()> {
+        match self.columns.contains(&StyleField::Name) {
+            true => Ok(()),
+            false => Err(ParserError::MissingColumn {
+                column: StyleField::Name.name(false).to_string(),
+            }),
+        }
+    }
+
+    pub fn parse(line: &str, legacy: bool) -> ParseRes<Self> {
+        let body = line
+            .trim()
+            .strip_prefix(Self::FORMAT_PREFIX)
+            .unwrap_or(line);
+        let columns = body
+            .split(',')
+            .map(Self::parse_column)
+            .collect::<ParseRes<Vec<_>>>()?;
+        let format = Self { columns, legacy };
+        format.check_required()?;
+        Ok(format)
+    }
+
+    pub fn to_line(&self) -> String {
+        let names: Vec<&str> = self
+            .columns
+            .iter()
+            .map(|column| column.name(self.legacy))
+            .collect();
+        format!("{} {}", Self::FORMAT_PREFIX, names.join(", "))
+    }
+
+    pub fn len(&self) -> usize {
+        self.columns.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.columns.is_empty()
+    }
+
+    pub fn is_legacy(&self) -> bool {
+        self.legacy
+    }
+
+    pub fn columns(&self) -> &[StyleField] {
+        &self.columns
+    }
+}
@@ -1,5 +1,6 @@
 use crate::model::format::ass::{
-    parser::{parser::Parser, parser_error::ParseRes},
+    document::AssDocument,
+    parser::parser::Parser,
     stylist::{
         script_preparer::ScriptPreparer, strategy_factory::StrategyFactory,
         style_editor::StyleEditor, style_strategy::StyleStrategy, style_type::StyleType,
@@ -16,6 +17,7 @@ pub mod style_type;
 pub struct Stylist {
     parser: Parser,
     strategy: Box<dyn StyleStrategy>,
+    keep_comments: bool,
 }
 
 impl Stylist {
@@ -23,16 +25,24 @@ impl Stylist {
         Self {
             parser: Parser::new(),
             strategy: StrategyFactory::create(style_type),
+            keep_comments: true,
         }
     }
 
-    pub fn run(&self, lines: &[String]) -> ParseRes<Vec<String>> {
-        let mut output_lines = Vec::new();
-        let mut script_preparer = ScriptPreparer::new(&self.parser);
-        script_preparer.analyze(lines, &mut output_lines)?;
+    pub fn with_comments(mut self, keep_comments: bool) -> Self {
+        self.keep_comments = keep_comments;
+        self
+    }
+
+    pub fn run(&self, doc: &mut AssDocument) {
+        doc.convert_to_v4_plus();
+        let mut script_preparer = ScriptPreparer::new();
+        script_preparer.analyze(doc);
         let mut style_editor = StyleEditor::new(&self.parser, &script_preparer, &*self.strategy);
-        style_editor.edit(&mut output_lines)?;
-        Ok(output_lines)
+        style_editor.edit(doc);
+        if !self.keep_comments {
+            doc.remove_comments();
+        }
     }
 }
 
@@ -1,29 +1,26 @@
 use crate::model::format::ass::{
-    parser::{parser::Parser, parser_error::ParseRes},
-    stylist::style_strategy::StyleStrategy,
+    line_ass::AssLine, style_ass::AssStyle, stylist::style_strategy::StyleStrategy,
 };
 
-pub struct MainStrategy {
-    parser: Parser,
-}
+pub struct MainStrategy;
 
 impl MainStrategy {
     pub fn new() -> Self {
-        Self {
-            parser: Parser::new(),
-        }
+        Self
     }
 }
 
 impl StyleStrategy for MainStrategy {
-    fn apply_style(&self, line: &str) -> ParseRes<String> {
-        let style = "Main".to_string();
-        Ok(self.parser.replace_style(line, &style)?)
+    fn apply_style(&self, line: &mut AssLine) {
+        line.style = "Main".to_string();
     }
 
-    fn styles(&self) -> Vec<String> {
+    fn styles(&self) -> Vec<AssStyle> {
         vec![
-            "Style: Main,Trebuchet MS,24,&H00FFFFFF,&H000000FF,&H00000000,&H00000000,0,0,0,0,100,100,0,0,1,2,1,2,0010,0010,0018,1".to_string(),
+            AssStyle::new("Main")
+                .with_font("Trebuchet MS", 24.0)
+                .with_border(2.0, 1.0)
+                .with_margins(10, 10, 18),
         ]
     }
 }
@@ -1,6 +1,6 @@
-use crate::model::format::ass::parser::parser_error::ParseRes;
+use crate::model::format::ass::{line_ass::AssLine, style_ass::AssStyle};
 
 pub trait StyleStrategy {
-    fn apply_style(&self, line: &str) -> ParseRes<String>;
-    fn styles(&self) -> Vec<String>;
+    fn apply_style(&self, line: &mut AssLine);
+    fn styles(&self) -> Vec<AssStyle>;
 }
@@ -1,4 +1,4 @@
-use crate::model::format::ass::parser::parser_error::ParserError;
+use crate::model::parse_error::{ParseRes, ParserError};
 
 use super::*;
 
@@ -28,7 +28,7 @@ static MAIN_CASE: TestCase = TestCase {
         "",
         "[V4+ Styles]",
         "Format: Name, Fontname, Fontsize, PrimaryColour, SecondaryColour, OutlineColour, BackColour, Bold, Italic, Underline, StrikeOut, ScaleX, ScaleY, Spacing, Angle, BorderStyle, Outline, Shadow, Alignment, MarginL, MarginR, MarginV, Encoding",
-        "Style: Main,Trebuchet MS,24,&H00FFFFFF,&H000000FF,&H00000000,&H00000000,0,0,0,0,100,100,0,0,1,2,1,2,0010,0010,0018,1",
+        "Style: Main,Trebuchet MS,24,&H00FFFFFF,&H000000FF,&H00000000,&H00000000,0,0,0,0,100,100,0,0,1,2,1,2,10,10,18,1",
         "",
         "[Events]",
         "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text",
@@ -91,7 +91,7 @@ static EXISTING_STYLE_CASE: TestCase = TestCase {
         "[V4+ Styles]",
         "Format: Name, Fontname, Fontsize, PrimaryColour, SecondaryColour, OutlineColour, BackColour, Bold, Italic, Underline, StrikeOut, ScaleX, ScaleY, Spacing, Angle, BorderStyle, Outline, Shadow, Alignment, MarginL, MarginR, MarginV, Encoding",
         "Style: Default,Tahoma,18,&H00FFFFFF,&H000000FF,&H00000000,&H00000000,0,0,0,0,100,100,0,0,1,2,1,2,0010,0010,0018,1",
-        "Style: Main,Trebuchet MS,24,&H00FFFFFF,&H000000FF,&H00000000,&H00000000,0,0,0,0,100,100,0,0,1,2,1,2,0010,0010,0018,1",
+        "Style: Main,Trebuchet MS,24,&H00FFFFFF,&H000000FF,&H00000000,&H00000000,0,0,0,0,100,100,0,0,1,2,1,2,10,10,18,1",
         "",
         "[Events]",
         "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text",
@@ -130,7 +130,7 @@ static MULTIPLE_STYLES_CASE: TestCase = TestCase {
         "Style: Default,Tahoma,18,&H00FFFFFF,&H000000FF,&H00000000,&H00000000,0,0,0,0,100,100,0,0,1,2,1,2,0010,0010,0018,1",
         "Style: Alternate,Arial,20,&H00FFFFFF,&H000000FF,&H00000000,&H00000000,0,0,0,0,100,100,0,0,1,2,1,2,0010,0010,0018,1",
         "Style: Signs,Comic Sans,22,&H00FFFFFF,&H000000FF,&H00000000,&H00000000,0,0,0,0,100,100,0,0,1,2,1,2,0010,0010,0018,1",
-        "Style: Main,Trebuchet MS,24,&H00FFFFFF,&H000000FF,&H00000000,&H00000000,0,0,0,0,100,100,0,0,1,2,1,2,0010,0010,0018,1",
+        "Style: Main,Trebuchet MS,24,&H00FFFFFF,&H000000FF,&H00000000,&H00000000,0,0,0,0,100,100,0,0,1,2,1,2,10,10,18,1",
         "",
         "[Events]",
         "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text",
@@ -167,7 +167,7 @@ static FULL_INFO_CASE: TestCase = TestCase {
         "[V4+ Styles]",
         "Format: Name, Fontname, Fontsize, PrimaryColour, SecondaryColour, OutlineColour, BackColour, Bold, Italic, Underline, StrikeOut, ScaleX, ScaleY, Spacing, Angle, BorderStyle, Outline, Shadow, Alignment, MarginL, MarginR, MarginV, Encoding",
         "Style: Default,Tahoma,18,&H00FFFFFF,&H000000FF,&H00000000,&H00000000,0,0,0,0,100,100,0,0,1,2,1,2,0010,0010,0018,1",
-        "Style: Main,Trebuchet MS,24,&H00FFFFFF,&H000000FF,&H00000000,&H00000000,0,0,0,0,100,100,0,0,1,2,1,2,0010,0010,0018,1",
+        "Style: Main,Trebuchet MS,24,&H00FFFFFF,&H000000FF,&H00000000,&H00000000,0,0,0,0,100,100,0,0,1,2,1,2,10,10,18,1",
         "",
         "[Events]",
         "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text",
@@ -175,6 +175,38 @@ static FULL_INFO_CASE: TestCase = TestCase {
     ],
 };
 
+static SSA_CASE: TestCase = TestCase {
+    name: "converts a legacy SSA script to V4+ before styling",
+    input: &[
+        "[Script Info]",
+        "ScriptType: v4.00",
+        "",
+        "[V4 Styles]",
+        "Format: Name, Fontname, Fontsize, PrimaryColour, SecondaryColour, TertiaryColour, BackColour, Bold, Italic, BorderStyle, Outline, Shadow, Alignment, MarginL, MarginR, MarginV, AlphaLevel, Encoding",
+        "Style: Default,Arial,20,16777215,65535,0,0,0,0,1,2,2,10,30,30,10,0,0",
+        "",
+        "[Events]",
+        "Format: Marked, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text",
+        "Dialogue: Marked=0,0:00:01.00,0:00:02.00,Default,,0000,0000,0000,,Line 1",
+    ],
+    expected: &[
+        "[Script Info]",
+        "ScriptType: v4.00+",
+        "PlayResX: 640",
+        "PlayResY: 360",
+        "ScaledBorderAndShadow: yes",
+        "",
+        "[V4+ Styles]",
+        "Format: Name, Fontname, Fontsize, PrimaryColour, SecondaryColour, OutlineColour, BackColour, Bold, Italic, Underline, StrikeOut, ScaleX, ScaleY, Spacing, Angle, BorderStyle, Outline, Shadow, Alignment, MarginL, MarginR, MarginV, Encoding",
+        "Style: Default,Arial,20,&H00FFFFFF,&H0000FFFF,&H00000000,&H00000000,0,0,0,0,100,100,0,0,1,2,2,5,30,30,10,0",
+        "Style: Main,Trebuchet MS,24,&H00FFFFFF,&H000000FF,&H00000000,&H00000000,0,0,0,0,100,100,0,0,1,2,1,2,10,10,18,1",
+        "",
+        "[Events]",
+        "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text",
+        "Dialogue: 0,0:00:01.00,0:00:02.00,Main,,0000,0000,0000,,Line 1",
+    ],
+};
+
 static CORRUPT_METADATA_CASE: TestCase = TestCase {
     name: "fails on corrupt script info",
     input: &["[Script Info]", "InvalidMetadataLine"],
@@ -187,12 +219,45 @@ static MISSING_FIELDS_CASE: TestCase = TestCase {
     expected: &[],
 };
 
+static COMMENTS_CASE: TestCase = TestCase {
+    name: "leaves comments unstyled",
+    input: &[
+        "[Script Info]",
+        "",
+        "[Events]",
+        "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text",
+        "Comment: 10,0:00:00.50,0:00:01.00,Default,ADDITIONAL SCENE,0,0,0,,Note",
+        "Dialogue: 10,0:00:01.00,0:00:02.00,Default,,0,0,0,,Line 1",
+    ],
+    expected: &[
+        "[Script Info]",
+        "PlayResX: 640",
+        "PlayResY: 360",
+        "ScaledBorderAndShadow: yes",
+        "",
+        "[V4+ Styles]",
+        "Format: Name, Fontname, Fontsize, PrimaryColour, SecondaryColour, OutlineColour, BackColour, Bold, Italic, Underline, StrikeOut, ScaleX, ScaleY, Spacing, Angle, BorderStyle, Outline, Shadow, Alignment, MarginL, MarginR, MarginV, Encoding",
+        "Style: Main,Trebuchet MS,24,&H00FFFFFF,&H000000FF,&H00000000,&H00000000,0,0,0,0,100,100,0,0,1,2,1,2,10,10,18,1",
+        "",
+        "[Events]",
+        "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text",
+        "Comment: 10,0:00:00.50,0:00:01.00,Default,ADDITIONAL SCENE,0,0,0,,Note",
+        "Dialogue: 10,0:00:01.00,0:00:02.00,Main,,0,0,0,,Line 1",
+    ],
+};
+
 fn run_test_case(test_case: &TestCase, style_type: &StyleType) -> ParseRes<()> {
     let input: Vec<String> = test_case.input.iter().map(|s| s.to_string()).collect();
     let expected: Vec<String> = test_case.expected.iter().map(|s| s.to_string()).collect();
+    let mut doc = AssDocument::parse(&input)?;
     let stylist = Stylist::new(style_type);
-    let result = stylist.run(&input)?;
-    assert_eq!(result, expected, "Failed at case: {}", test_case.name);
+    stylist.run(&mut doc);
+    assert_eq!(
+        doc.serialize(),
+        expected,
+        "Failed at case: {}",
+        test_case.name
+    );
     Ok(())
 }
 
@@ -221,6 +286,11 @@ fn test_full_info_case() -> ParseRes<()> {
     run_test_case(&FULL_INFO_CASE, &StyleType::Main)
 }
 
+#[test]
+fn test_converts_legacy_script() -> ParseRes<()> {
+    run_test_case(&SSA_CASE, &StyleType::Main)
+}
+
 #[test]
 #[should_panic]
 fn test_fails_on_corrupt_metadata() {
@@ -231,7 +301,12 @@ fn test_fails_on_corrupt_metadata() {
 fn test_error_missing_fields() {
     let result = run_test_case(&MISSING_FIELDS_CASE, &StyleType::Main);
     match result {
-        Err(ParserError::MissingFields { found }) => assert_eq!(found, 3),
+        Err(ParserError::MissingFields { found, .. }) => assert_eq!(found, 3),
         _ => panic!("Expected MissingFields(3), got {:?}", result),
     }
 }
+
+#[test]
+fn test_comments_unstyled() -> ParseRes<()> {
+    run_test_case(&COMMENTS_CASE, &StyleType::Main)
+}
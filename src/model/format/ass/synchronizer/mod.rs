@@ -1,63 +1,65 @@
-use crate::model::format::ass::{
-    ass_segmenter::AssSegmenter,
-    line_collector::LineCollector,
-    parser::{parser::Parser, parser_error::ParseRes},
-    synchronizer::line_processor::LineProcessor,
+use crate::model::{
+    format::ass::{document::AssDocument, line_ass::AssLine, parser::parser::Parser},
+    sync_options::SyncOptions,
+    sync_result::SyncResult,
+    synchronizer::{LineSynchronizer, SyncedLines},
+    timestamp::Timestamp,
 };
 
-mod block;
-mod block_state;
-mod blocks;
-mod line_processor;
-mod steps;
-
 pub struct Synchronizer {
-    segmenter: AssSegmenter,
     parser: Parser,
+    keep_comments: bool,
+    options: SyncOptions,
 }
 
 impl Synchronizer {
-    pub fn new() -> Self {
+    pub fn new(options: SyncOptions) -> Self {
         Self {
-            segmenter: AssSegmenter::new(),
             parser: Parser::new(),
+            keep_comments: true,
+            options,
         }
     }
 
-    fn process_dialogues(&self, lines_a: &[&String], lines_b: &[&String]) -> ParseRes<Vec<String>> {
-        let dialogues_a = self
-            .segmenter
-            .extract_dialogues(&lines_a.to_vec(), &self.parser);
-        let dialogues_b = self
-            .segmenter
-            .extract_dialogues(&lines_b.to_vec(), &self.parser);
-
-        let mut output_a_lines = Vec::new();
-        let mut processor = LineProcessor::new(&mut output_a_lines, &self.parser);
-
-        processor.run(&dialogues_a, &dialogues_b)?;
-        Ok(output_a_lines)
+    pub fn with_comments(mut self, keep_comments: bool) -> Self {
+        self.keep_comments = keep_comments;
+        self
     }
 
-    fn collect(&self, lines_a: &[&String], processed_a: &[String], output: &mut Vec<String>) {
-        let header_a = self
-            .segmenter
-            .extract_header(&lines_a.to_vec(), &self.parser);
-        let mut collector = LineCollector::new(output);
-
-        collector.collect_header_and_lines(&header_a, processed_a);
+    fn initial_offset(&self, doc_a: &AssDocument, processed_a: &[Vec<AssLine>]) -> Timestamp {
+        let first_a = doc_a.dialogues().next();
+        let first_out = processed_a.first().and_then(|group| group.first());
+        match (first_a, first_out) {
+            (Some(a), Some(out)) => out.start - a.start,
+            _ => Timestamp::ZERO,
+        }
     }
 
-    pub fn run(&mut self, source_a: &[String], source_b: &[String]) -> ParseRes<Vec<String>> {
-        let mut final_output = Vec::new();
-
-        let refs_a: Vec<&String> = source_a.iter().collect();
-        let refs_b: Vec<&String> = source_b.iter().collect();
-
-        let processed_a_lines = self.process_dialogues(&refs_a, &refs_b)?;
-        self.collect(&refs_a, &processed_a_lines, &mut final_output);
+    fn collect(&self, doc_a: &AssDocument, synced: SyncedLines<AssLine>) -> AssDocument {
+        let mut output = doc_a.clone();
+        let mut offset = self.initial_offset(doc_a, &synced.groups);
+        let mut groups = synced.groups.into_iter();
+        for events in output.events_mut() {
+            events.replace_dialogues(&mut groups, &mut offset);
+        }
+        if let Some(events) = output
+            .events_mut()
+            .find(|events| events.dialogues().next().is_some())
+        {
+            events.insert_before_dialogues(synced.leading);
+        }
+        if !self.keep_comments {
+            output.remove_comments();
+        }
+        output
+    }
 
-        Ok(final_output)
+    pub fn run(&mut self, doc_a: &AssDocument, doc_b: &AssDocument) -> SyncResult<AssDocument> {
+        let dialogues_a: Vec<&AssLine> = doc_a.dialogues().collect();
+        let dialogues_b: Vec<&AssLine> = doc_b.dialogues().collect();
+        LineSynchronizer::new(&self.parser, self.options)
+            .run(&dialogues_a, &dialogues_b)
+            .map(|synced| self.collect(doc_a, synced))
     }
 }
 
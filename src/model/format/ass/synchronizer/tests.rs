@@ -1,4 +1,7 @@
-use crate::model::format::ass::parser::parser_error::ParserError;
+use crate::model::{
+    parse_error::{ParseRes, ParserError},
+    sync_options::SyncMode,
+};
 
 use super::*;
 
@@ -110,6 +113,139 @@ static START_LATER_CASE: TestCase = TestCase {
     ],
 };
 
+static KEEP_END_CASE: TestCase = TestCase {
+    name: "shifts only start times when end shifting is disabled",
+    input_a: &[
+        "[Events]",
+        "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text",
+        "Dialogue: 0,0:00:01.00,0:00:03.50,Default,,0,0,0,,Línea 1",
+        "Dialogue: 0,0:00:03.00,0:00:05.50,Default,,0,0,0,,Línea 2",
+    ],
+    input_b: &[
+        "[Events]",
+        "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text",
+        "Dialogue: 0,0:00:02.00,0:00:03.00,Default,,0,0,0,,Line 1",
+        "Dialogue: 0,0:00:04.00,0:00:05.00,Default,,0,0,0,,Line 2",
+    ],
+    expected: &[
+        "[Events]",
+        "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text",
+        "Dialogue: 0,0:00:02.00,0:00:03.50,Default,,0,0,0,,Línea 1",
+        "Dialogue: 0,0:00:04.00,0:00:05.50,Default,,0,0,0,,Línea 2",
+    ],
+};
+
+static UNMATCHED_BLOCK_CASE: TestCase = TestCase {
+    name: "places a block whose anchor never matches by nearest timestamp",
+    input_a: &[
+        "[Events]",
+        "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text",
+        "Dialogue: 0,0:00:01.00,0:00:02.00,Default,,0,0,0,,Hola",
+        "Dialogue: 0,0:00:03.00,0:00:04.00,Default,,0,0,0,,¿Cómo estás?",
+        "Dialogue: 0,0:00:10.00,0:00:11.00,Default,,0,0,0,,Bien, gracias",
+    ],
+    input_b: &[
+        "[Events]",
+        "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text",
+        "Dialogue: 0,0:00:01.00,0:00:02.00,Default,,0,0,0,,Hello",
+        "Dialogue: 0,0:00:05.00,0:00:06.00,Default,,0,0,0,,How are you?",
+        "Dialogue: 0,0:00:06.00,0:00:07.00,Default,ADDITIONAL SCENE,0,0,0,,Escena extra",
+        "Dialogue: 0,0:00:12.00,0:00:13.00,Default,,0,0,0,,Fine, thanks",
+    ],
+    expected: &[
+        "[Events]",
+        "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text",
+        "Dialogue: 0,0:00:01.00,0:00:02.00,Default,,0,0,0,,Hola",
+        "Dialogue: 0,0:00:03.00,0:00:04.00,Default,,0,0,0,,¿Cómo estás?",
+        "Dialogue: 0,0:00:06.00,0:00:07.00,Default,ADDITIONAL SCENE,0,0,0,,Escena extra",
+        "Dialogue: 0,0:00:10.00,0:00:11.00,Default,,0,0,0,,Bien, gracias",
+    ],
+};
+
+static COLD_OPEN_CASE: TestCase = TestCase {
+    name: "inserts a cold open that only exists in B before the first line",
+    input_a: &[
+        "[Events]",
+        "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text",
+        "Dialogue: 0,0:00:01.00,0:00:02.00,Default,,0,0,0,,Hola",
+        "Dialogue: 0,0:00:03.00,0:00:04.00,Default,,0,0,0,,Adiós",
+    ],
+    input_b: &[
+        "[Events]",
+        "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text",
+        "Dialogue: 0,0:00:01.00,0:00:03.00,Default,ADDITIONAL SCENE,0,0,0,,Prólogo",
+        "Dialogue: 0,0:00:03.50,0:00:05.00,Default,ADDITIONAL SCENE,0,0,0,,Prólogo 2",
+        "Dialogue: 0,0:00:10.00,0:00:11.00,Default,,0,0,0,,Hello",
+        "Dialogue: 0,0:00:12.00,0:00:13.00,Default,,0,0,0,,Bye",
+    ],
+    expected: &[
+        "[Events]",
+        "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text",
+        "Dialogue: 0,0:00:01.00,0:00:03.00,Default,ADDITIONAL SCENE,0,0,0,,Prólogo",
+        "Dialogue: 0,0:00:03.50,0:00:05.00,Default,ADDITIONAL SCENE,0,0,0,,Prólogo 2",
+        "Dialogue: 0,0:00:10.00,0:00:11.00,Default,,0,0,0,,Hola",
+        "Dialogue: 0,0:00:12.00,0:00:13.00,Default,,0,0,0,,Adiós",
+    ],
+};
+
+static POST_CREDITS_CASE: TestCase = TestCase {
+    name: "inserts a post-credits scene and keeps later A lines after it",
+    input_a: &[
+        "[Events]",
+        "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text",
+        "Dialogue: 0,0:00:01.00,0:00:02.00,Default,,0,0,0,,Hola",
+        "Dialogue: 0,0:00:03.00,0:00:04.00,Default,,0,0,0,,Adiós",
+        "Dialogue: 0,0:00:05.00,0:00:06.00,Default,,0,0,0,,Avance",
+    ],
+    input_b: &[
+        "[Events]",
+        "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text",
+        "Dialogue: 0,0:00:02.00,0:00:03.00,Default,,0,0,0,,Hello",
+        "Dialogue: 0,0:00:04.00,0:00:05.00,Default,,0,0,0,,Bye",
+        "Dialogue: 0,0:00:06.00,0:00:08.00,Default,ADDITIONAL SCENE,0,0,0,,Escena post-créditos",
+    ],
+    expected: &[
+        "[Events]",
+        "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text",
+        "Dialogue: 0,0:00:02.00,0:00:03.00,Default,,0,0,0,,Hola",
+        "Dialogue: 0,0:00:04.00,0:00:05.00,Default,,0,0,0,,Adiós",
+        "Dialogue: 0,0:00:06.00,0:00:08.00,Default,ADDITIONAL SCENE,0,0,0,,Escena post-créditos",
+        "Dialogue: 0,0:00:08.00,0:00:09.00,Default,,0,0,0,,Avance",
+    ],
+};
+
+static ALIGNMENT_CASE: TestCase = TestCase {
+    name: "aligns by timing and tags untagged lines that only exist in B",
+    input_a: &[
+        "[Events]",
+        "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text",
+        "Dialogue: 0,0:00:01.00,0:00:02.00,Default,,0,0,0,,Hola",
+        "Dialogue: 0,0:00:03.00,0:00:05.00,Default,,0,0,0,,¿Qué tal?",
+        "Dialogue: 0,0:00:06.00,0:00:06.50,Default,,0,0,0,,Bien",
+        "Dialogue: 0,0:00:08.00,0:00:11.00,Default,,0,0,0,,Adiós",
+    ],
+    input_b: &[
+        "[Events]",
+        "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text",
+        "Dialogue: 0,0:00:01.50,0:00:02.50,Default,,0,0,0,,Hello",
+        "Dialogue: 0,0:00:03.50,0:00:05.50,Default,,0,0,0,,How are you?",
+        "Dialogue: 0,0:00:06.50,0:00:07.00,Default,,0,0,0,,Fine",
+        "Dialogue: 0,0:00:07.50,0:00:09.00,Default,,0,0,0,,Extra scene",
+        "Dialogue: 0,0:00:09.50,0:00:09.80,Default,,0,0,0,,Extra continuation",
+        "Dialogue: 0,0:00:11.00,0:00:14.00,Default,,0,0,0,,Bye",
+    ],
+    expected: &[
+        "[Events]",
+        "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text",
+        "Dialogue: 0,0:00:01.50,0:00:02.50,Default,,0,0,0,,Hola",
+        "Dialogue: 0,0:00:03.50,0:00:05.50,Default,,0,0,0,,¿Qué tal?",
+        "Dialogue: 0,0:00:06.50,0:00:07.00,Default,,0,0,0,,Bien",
+        "Dialogue: 0,0:00:07.50,0:00:09.00,Default,ADDITIONAL SCENE,0,0,0,,Extra scene",
+        "Dialogue: 0,0:00:09.50,0:00:09.80,Default,ADDITIONAL SCENE,0,0,0,,Extra continuation",
+        "Dialogue: 0,0:00:11.00,0:00:14.00,Default,,0,0,0,,Adiós",
+    ],
+};
+
 static MISSING_EVENTS_CASE: TestCase = TestCase {
     name: "fails when events section is missing",
     input_a: &["[Script Info]"],
@@ -117,13 +253,51 @@ static MISSING_EVENTS_CASE: TestCase = TestCase {
     expected: &[],
 };
 
+static COMMENTS_CASE: TestCase = TestCase {
+    name: "shifts comments with their neighbours and ignores comments in B",
+    input_a: &[
+        "[Events]",
+        "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text",
+        "Comment: 0,0:00:00.50,0:00:01.00,Default,,0,0,0,,Opening note",
+        "Dialogue: 0,0:00:01.00,0:00:02.00,Default,,0,0,0,,Línea 1",
+        "Comment: 0,0:00:02.00,0:00:03.00,Default,,0,0,0,,Note after line 1",
+        "Dialogue: 0,0:00:03.00,0:00:04.00,Default,,0,0,0,,Línea 2",
+    ],
+    input_b: &[
+        "[Events]",
+        "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text",
+        "Comment: 0,0:00:00.00,0:00:09.00,Default,,0,0,0,,B note",
+        "Dialogue: 0,0:00:02.00,0:00:03.00,Default,,0,0,0,,Line 1",
+        "Dialogue: 0,0:00:04.00,0:00:05.00,Default,,0,0,0,,Line 2",
+    ],
+    expected: &[
+        "[Events]",
+        "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text",
+        "Comment: 0,0:00:01.50,0:00:02.00,Default,,0,0,0,,Opening note",
+        "Dialogue: 0,0:00:02.00,0:00:03.00,Default,,0,0,0,,Línea 1",
+        "Comment: 0,0:00:03.00,0:00:04.00,Default,,0,0,0,,Note after line 1",
+        "Dialogue: 0,0:00:04.00,0:00:05.00,Default,,0,0,0,,Línea 2",
+    ],
+};
+
 fn run_test_case(test_case: &TestCase) -> ParseRes<()> {
+    run_test_case_with(test_case, SyncOptions::default())
+}
+
+fn run_test_case_with(test_case: &TestCase, options: SyncOptions) -> ParseRes<()> {
     let input_a: Vec<String> = test_case.input_a.iter().map(|s| s.to_string()).collect();
     let input_b: Vec<String> = test_case.input_b.iter().map(|s| s.to_string()).collect();
     let expected: Vec<String> = test_case.expected.iter().map(|s| s.to_string()).collect();
-    let mut synchronizer = Synchronizer::new();
-    let result = synchronizer.run(&input_a, &input_b)?;
-    assert_eq!(result, expected, "Failed at case: {}", test_case.name);
+    let doc_a = AssDocument::parse(&input_a)?;
+    let doc_b = AssDocument::parse(&input_b)?;
+    let mut synchronizer = Synchronizer::new(options);
+    let result = synchronizer.run(&doc_a, &doc_b);
+    assert_eq!(
+        result.output.serialize(),
+        expected,
+        "Failed at case: {}",
+        test_case.name
+    );
     Ok(())
 }
 
@@ -150,6 +324,48 @@ static CORRUPT_LONG_CASE: TestCase = TestCase {
     expected: &[],
 };
 
+static NO_DIALOGUE_A_CASE: TestCase = TestCase {
+    name: "file A without dialogue events",
+    input_a: &[
+        "[Events]",
+        "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text",
+        "Comment: 0,0:00:01.00,0:00:02.00,Default,,0,0,0,,Notes only",
+    ],
+    input_b: &[
+        "[Events]",
+        "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text",
+        "Dialogue: 0,0:00:02.00,0:00:03.00,Default,,0,0,0,,Hello",
+        "Dialogue: 0,0:00:04.00,0:00:05.00,Default,ADDITIONAL SCENE,0,0,0,,Extra",
+    ],
+    expected: &[
+        "[Events]",
+        "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text",
+        "Comment: 0,0:00:01.00,0:00:02.00,Default,,0,0,0,,Notes only",
+    ],
+};
+
+static ONLY_SCENES_B_CASE: TestCase = TestCase {
+    name: "file B with only extra scene lines",
+    input_a: &[
+        "[Events]",
+        "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text",
+        "Dialogue: 0,0:00:01.00,0:00:02.00,Default,,0,0,0,,Uno",
+        "Dialogue: 0,0:00:05.00,0:00:06.00,Default,,0,0,0,,Dos",
+    ],
+    input_b: &[
+        "[Events]",
+        "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text",
+        "Dialogue: 0,0:00:03.00,0:00:04.00,Default,ADDITIONAL SCENE,0,0,0,,Extra",
+    ],
+    expected: &[
+        "[Events]",
+        "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text",
+        "Dialogue: 0,0:00:01.00,0:00:02.00,Default,,0,0,0,,Uno",
+        "Dialogue: 0,0:00:03.00,0:00:04.00,Default,ADDITIONAL SCENE,0,0,0,,Extra",
+        "Dialogue: 0,0:00:05.00,0:00:06.00,Default,,0,0,0,,Dos",
+    ],
+};
+
 #[test]
 fn test_basic_synchronization() -> ParseRes<()> {
     run_test_case(&BASIC_SYNC_CASE)
@@ -170,7 +386,7 @@ fn test_fails_on_missing_events() {
 fn test_error_on_short_line() {
     let result = run_test_case(&CORRUPT_SHORT_CASE);
     match result {
-        Err(ParserError::MissingFields { found }) => assert_eq!(found, 4),
+        Err(ParserError::MissingFields { found, .. }) => assert_eq!(found, 4),
         _ => panic!("Expected MissingFields(4), got {:?}", result),
     }
 }
@@ -179,7 +395,50 @@ fn test_error_on_short_line() {
 fn test_error_on_almost_complete_line() {
     let result = run_test_case(&CORRUPT_LONG_CASE);
     match result {
-        Err(ParserError::MissingFields { found }) => assert_eq!(found, 9),
+        Err(ParserError::MissingFields { found, .. }) => assert_eq!(found, 9),
         _ => panic!("Expected MissingFields(9), got {:?}", result),
     }
 }
+
+#[test]
+fn test_sync_without_dialogue_in_a() -> ParseRes<()> {
+    run_test_case(&NO_DIALOGUE_A_CASE)
+}
+
+#[test]
+fn test_sync_with_only_scenes_in_b() -> ParseRes<()> {
+    run_test_case(&ONLY_SCENES_B_CASE)
+}
+
+#[test]
+fn test_sync_keeps_comments_in_place() -> ParseRes<()> {
+    run_test_case(&COMMENTS_CASE)
+}
+
+#[test]
+fn test_sync_keeps_end_times() -> ParseRes<()> {
+    run_test_case_with(&KEEP_END_CASE, SyncOptions::new().with_shift_end(false))
+}
+
+#[test]
+fn test_sync_places_unmatched_block() -> ParseRes<()> {
+    run_test_case(&UNMATCHED_BLOCK_CASE)
+}
+
+#[test]
+fn test_sync_inserts_cold_open() -> ParseRes<()> {
+    run_test_case(&COLD_OPEN_CASE)
+}
+
+#[test]
+fn test_sync_inserts_post_credits_scene() -> ParseRes<()> {
+    run_test_case(&POST_CREDITS_CASE)
+}
+
+#[test]
+fn test_alignment_mode_inserts_untagged_scene() -> ParseRes<()> {
+    run_test_case_with(
+        &ALIGNMENT_CASE,
+        SyncOptions::new().with_mode(SyncMode::Timing),
+    )
+}
@@ -0,0 +1,211 @@
+use crate::model::format::ass::text::tag::{Tag, TagKind, split_items};
+
+pub mod tag;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum OverrideItem {
+    Tag(Tag),
+    Comment(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TextSegment {
+    Override(Vec<OverrideItem>),
+    Text(String),
+    Drawing(String),
+    HardBreak,
+    SoftBreak,
+    HardSpace,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AssText {
+    pub segments: Vec<TextSegment>,
+}
+
+impl OverrideItem {
+    fn parse(item: &str) -> Self {
+        match item.starts_with('\\') {
+            true => OverrideItem::Tag(Tag::parse(item)),
+            false => OverrideItem::Comment(item.to_string()),
+        }
+    }
+
+    fn to_text(&self) -> String {
+        match self {
+            OverrideItem::Tag(tag) => tag.to_text(),
+            OverrideItem::Comment(text) => text.clone(),
+        }
+    }
+}
+
+impl TextSegment {
+    const HARD_BREAK: &str = "\\N";
+    const SOFT_BREAK: &str = "\\n";
+    const HARD_SPACE: &str = "\\h";
+
+    fn to_text(&self) -> String {
+        match self {
+            TextSegment::Override(items) => {
+                let body: String = items.iter().map(OverrideItem::to_text).collect();
+                format!("{{{}}}", body)
+            }
+            TextSegment::Text(text) | TextSegment::Drawing(text) => text.clone(),
+            TextSegment::HardBreak => Self::HARD_BREAK.to_string(),
+            TextSegment::SoftBreak => Self::SOFT_BREAK.to_string(),
+            TextSegment::HardSpace => Self::HARD_SPACE.to_string(),
+        }
+    }
+
+    fn escape(pair: &str) -> Option<Self> {
+        match pair {
+            Self::HARD_BREAK => Some(TextSegment::HardBreak),
+            Self::SOFT_BREAK => Some(TextSegment::SoftBreak),
+            Self::HARD_SPACE => Some(TextSegment::HardSpace),
+            _ => None,
+        }
+    }
+}
+
+impl AssText {
+    pub fn parse(text: &str) -> Self {
+        let mut parsed = Self::default();
+        let mut drawing = false;
+        let mut rest = text;
+        while !rest.is_empty() {
+            match rest.find('{') {
+                Some(open) if rest[open..].contains('}') => {
+                    parsed.push_plain(&rest[..open], drawing);
+                    let close = open + rest[open..].find('}').unwrap_or_default();
+                    let block = Self::parse_block(&rest[open + 1..close]);
+                    drawing = Self::drawing_state(&block, drawing);
+                    parsed.segments.push(block);
+                    rest = &rest[close + 1..];
+                }
+                _ => {
+                    parsed.push_plain(rest, drawing);
+                    rest = "";
+                }
+            }
+        }
+        parsed
+    }
+
+    fn parse_block(content: &str) -> TextSegment {
+        TextSegment::Override(
+            split_items(content)
+                .into_iter()
+                .map(OverrideItem::parse)
+                .collect(),
+        )
+    }
+
+    fn drawing_state(block: &TextSegment, current: bool) -> bool {
+        let TextSegment::Override(items) = block else {
+            return current;
+        };
+        items.iter().fold(current, |state, item| match item {
+            OverrideItem::Tag(tag) => match tag.kind {
+                TagKind::Drawing(scale) => scale > 0,
+                _ => state,
+            },
+            OverrideItem::Comment(_) => state,
+        })
+    }
+
+    fn push_run(&mut self, run: &str, drawing: bool) {
+        if run.is_empty() {
+            return;
+        }
+        self.segments.push(match drawing {
+            true => TextSegment::Drawing(run.to_string()),
+            false => TextSegment::Text(run.to_string()),
+        });
+    }
+
+    fn push_plain(&mut self, plain: &str, drawing: bool) {
+        let mut start = 0;
+        for (idx, _) in plain.match_indices('\\') {
+            if idx < start {
+                continue;
+            }
+            let Some(escape) = plain.get(idx..idx + 2).and_then(TextSegment::escape) else {
+                continue;
+            };
+            self.push_run(&plain[start..idx], drawing);
+            self.segments.push(escape);
+            start = idx + 2;
+        }
+        self.push_run(&plain[start..], drawing);
+    }
+
+    pub fn to_text(&self) -> String {
+        self.segments.iter().map(TextSegment::to_text).collect()
+    }
+
+    pub fn tags(&self) -> impl Iterator<Item = &Tag> {
+        self.segments
+            .iter()
+            .filter_map(|segment| match segment {
+                TextSegment::Override(items) => Some(items),
+                _ => None,
+            })
+            .flatten()
+            .filter_map(|item| match item {
+                OverrideItem::Tag(tag) => Some(tag),
+                OverrideItem::Comment(_) => None,
+            })
+    }
+
+    pub fn tags_mut(&mut self) -> impl Iterator<Item = &mut Tag> {
+        self.segments
+            .iter_mut()
+            .filter_map(|segment| match segment {
+                TextSegment::Override(items) => Some(items),
+                _ => None,
+            })
+            .flatten()
+            .filter_map(|item| match item {
+                OverrideItem::Tag(tag) => Some(tag),
+                OverrideItem::Comment(_) => None,
+            })
+    }
+
+    pub fn override_len(&self) -> usize {
+        self.segments
+            .iter()
+            .filter(|segment| matches!(segment, TextSegment::Override(_)))
+            .map(|segment| segment.to_text().len())
+            .sum()
+    }
+
+    pub fn plain_text(&self) -> String {
+        self.segments
+            .iter()
+            .filter_map(|segment| match segment {
+                TextSegment::Text(text) => Some(text.as_str()),
+                TextSegment::HardBreak | TextSegment::SoftBreak => Some("\n"),
+                TextSegment::HardSpace => Some(" "),
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub fn strip_overrides(&self) -> String {
+        self.segments
+            .iter()
+            .filter(|segment| !matches!(segment, TextSegment::Override(_)))
+            .map(TextSegment::to_text)
+            .collect()
+    }
+
+    pub fn is_blank(&self) -> bool {
+        self.segments.iter().all(|segment| match segment {
+            TextSegment::Text(text) | TextSegment::Drawing(text) => text.trim().is_empty(),
+            _ => true,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,489 @@
+use crate::model::format::ass::style_ass::AssColor;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum KaraokeStyle {
+    Plain,
+    Fill,
+    FillSweep,
+    Outline,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Axis {
+    X,
+    Y,
+    Z,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ClipShape {
+    Rect {
+        x1: f64,
+        y1: f64,
+        x2: f64,
+        y2: f64,
+    },
+    Vector {
+        scale: Option<i32>,
+        commands: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TagKind {
+    Pos {
+        x: f64,
+        y: f64,
+    },
+    Move {
+        x1: f64,
+        y1: f64,
+        x2: f64,
+        y2: f64,
+        times: Option<(i32, i32)>,
+    },
+    Org {
+        x: f64,
+        y: f64,
+    },
+    Fad {
+        fade_in: i32,
+        fade_out: i32,
+    },
+    Fade {
+        values: [i32; 7],
+    },
+    Transform {
+        times: Option<(i32, i32)>,
+        accel: Option<f64>,
+        tags: Vec<Tag>,
+    },
+    Karaoke {
+        style: KaraokeStyle,
+        duration: i32,
+    },
+    FontName(String),
+    FontSize(f64),
+    Colour {
+        index: u8,
+        colour: AssColor,
+    },
+    Alpha {
+        index: Option<u8>,
+        value: u8,
+    },
+    Clip {
+        inverse: bool,
+        shape: ClipShape,
+    },
+    Bold(i32),
+    Italic(bool),
+    Underline(bool),
+    StrikeOut(bool),
+    Border(f64),
+    Shadow(f64),
+    Blur(f64),
+    BlurEdges(f64),
+    ScaleX(f64),
+    ScaleY(f64),
+    Spacing(f64),
+    Rotation {
+        axis: Axis,
+        degrees: f64,
+    },
+    Alignment(u8),
+    WrapStyle(u8),
+    Reset(Option<String>),
+    Drawing(i32),
+    Other {
+        name: String,
+        args: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tag {
+    pub kind: TagKind,
+    raw: Option<String>,
+}
+
+impl Tag {
+    const PREFIX: char = '\\';
+    const NAMES: [&str; 52] = [
+        "iclip", "alpha", "xbord", "ybord", "xshad", "yshad", "clip", "fade", "move", "bord",
+        "shad", "blur", "fscx", "fscy", "pos", "org", "fad", "fsp", "frx", "fry", "frz", "fax",
+        "fay", "pbo", "1c", "2c", "3c", "4c", "1a", "2a", "3a", "4a", "fn", "fs", "fr", "fe", "an",
+        "be", "kf", "ko", "t", "k", "K", "c", "b", "i", "u", "s", "a", "q", "r", "p",
+    ];
+
+    pub fn new(kind: TagKind) -> Self {
+        Self { kind, raw: None }
+    }
+
+    fn split_name(body: &str) -> (&str, &str) {
+        match Self::NAMES.iter().find(|name| body.starts_with(**name)) {
+            Some(name) => body.split_at(name.len()),
+            None => {
+                let end = body
+                    .find(|c: char| !c.is_ascii_alphabetic())
+                    .unwrap_or(body.len());
+                body.split_at(end)
+            }
+        }
+    }
+
+    pub fn parse(raw: &str) -> Self {
+        Self {
+            kind: Self::parse_kind(raw),
+            raw: Some(raw.to_string()),
+        }
+    }
+
+    fn parse_kind(raw: &str) -> TagKind {
+        let body = raw.strip_prefix(Self::PREFIX).unwrap_or(raw);
+        let (name, args) = Self::split_name(body);
+        Self::typed_kind(name, args).unwrap_or_else(|| TagKind::Other {
+            name: name.to_string(),
+            args: args.to_string(),
+        })
+    }
+
+    fn number<T: std::str::FromStr>(args: &str) -> Option<T> {
+        args.trim().parse().ok()
+    }
+
+    fn flag(args: &str) -> Option<bool> {
+        match args.trim() {
+            "0" => Some(false),
+            "1" => Some(true),
+            _ => None,
+        }
+    }
+
+    fn paren_args(args: &str) -> Option<&str> {
+        args.trim().strip_prefix('(')?.strip_suffix(')')
+    }
+
+    fn numbers(args: &str) -> Option<Vec<f64>> {
+        Self::paren_args(args)?
+            .split(',')
+            .map(Self::number)
+            .collect()
+    }
+
+    fn colour(args: &str) -> Option<AssColor> {
+        AssColor::parse(args).filter(|_| !args.trim().is_empty())
+    }
+
+    fn alpha(args: &str) -> Option<u8> {
+        let colour = Self::colour(args)?;
+        Some(colour.r).filter(|_| colour.g == 0 && colour.b == 0 && colour.a == 0)
+    }
+
+    fn index(name: &str) -> Option<u8> {
+        name.chars().next()?.to_digit(10).map(|d| d as u8)
+    }
+
+    fn typed_kind(name: &str, args: &str) -> Option<TagKind> {
+        let kind = match name {
+            "pos" => match Self::numbers(args)?.as_slice() {
+                [x, y] => TagKind::Pos { x: *x, y: *y },
+                _ => return None,
+            },
+            "org" => match Self::numbers(args)?.as_slice() {
+                [x, y] => TagKind::Org { x: *x, y: *y },
+                _ => return None,
+            },
+            "move" => Self::parse_move(args)?,
+            "fad" => match Self::numbers(args)?.as_slice() {
+                [fade_in, fade_out] => TagKind::Fad {
+                    fade_in: *fade_in as i32,
+                    fade_out: *fade_out as i32,
+                },
+                _ => return None,
+            },
+            "fade" => {
+                let numbers = Self::numbers(args)?;
+                let values: [f64; 7] = numbers.try_into().ok()?;
+                TagKind::Fade {
+                    values: values.map(|v| v as i32),
+                }
+            }
+            "t" => Self::parse_transform(args)?,
+            "k" => Self::karaoke(KaraokeStyle::Plain, args)?,
+            "K" => Self::karaoke(KaraokeStyle::FillSweep, args)?,
+            "kf" => Self::karaoke(KaraokeStyle::Fill, args)?,
+            "ko" => Self::karaoke(KaraokeStyle::Outline, args)?,
+            "fn" if !args.is_empty() => TagKind::FontName(args.to_string()),
+            "fs" => TagKind::FontSize(Self::number(args)?),
+            "c" => TagKind::Colour {
+                index: 1,
+                colour: Self::colour(args)?,
+            },
+            "1c" | "2c" | "3c" | "4c" => TagKind::Colour {
+                index: Self::index(name)?,
+                colour: Self::colour(args)?,
+            },
+            "alpha" => TagKind::Alpha {
+                index: None,
+                value: Self::alpha(args)?,
+            },
+            "1a" | "2a" | "3a" | "4a" => TagKind::Alpha {
+                index: Self::index(name),
+                value: Self::alpha(args)?,
+            },
+            "clip" => Self::parse_clip(false, args)?,
+            "iclip" => Self::parse_clip(true, args)?,
+            "b" => TagKind::Bold(Self::number(args)?),
+            "i" => TagKind::Italic(Self::flag(args)?),
+            "u" => TagKind::Underline(Self::flag(args)?),
+            "s" => TagKind::StrikeOut(Self::flag(args)?),
+            "bord" => TagKind::Border(Self::number(args)?),
+            "shad" => TagKind::Shadow(Self::number(args)?),
+            "blur" => TagKind::Blur(Self::number(args)?),
+            "be" => TagKind::BlurEdges(Self::number(args)?),
+            "fscx" => TagKind::ScaleX(Self::number(args)?),
+            "fscy" => TagKind::ScaleY(Self::number(args)?),
+            "fsp" => TagKind::Spacing(Self::number(args)?),
+            "frx" => Self::rotation(Axis::X, args)?,
+            "fry" => Self::rotation(Axis::Y, args)?,
+            "frz" | "fr" => Self::rotation(Axis::Z, args)?,
+            "an" => TagKind::Alignment(Self::number(args).filter(|a| (1..=9).contains(a))?),
+            "q" => TagKind::WrapStyle(Self::number(args)?),
+            "r" => TagKind::Reset(Some(args.to_string()).filter(|s| !s.is_empty())),
+            "p" => TagKind::Drawing(Self::number(args)?),
+            _ => return None,
+        };
+        Some(kind)
+    }
+
+    fn karaoke(style: KaraokeStyle, args: &str) -> Option<TagKind> {
+        Some(TagKind::Karaoke {
+            style,
+            duration: Self::number(args)?,
+        })
+    }
+
+    fn rotation(axis: Axis, args: &str) -> Option<TagKind> {
+        Some(TagKind::Rotation {
+            axis,
+            degrees: Self::number(args)?,
+        })
+    }
+
+    fn parse_move(args: &str) -> Option<TagKind> {
+        match Self::numbers(args)?.as_slice() {
+            [x1, y1, x2, y2] => Some(TagKind::Move {
+                x1: *x1,
+                y1: *y1,
+                x2: *x2,
+                y2: *y2,
+                times: None,
+            }),
+            [x1, y1, x2, y2, t1, t2] => Some(TagKind::Move {
+                x1: *x1,
+                y1: *y1,
+                x2: *x2,
+                y2: *y2,
+                times: Some((*t1 as i32, *t2 as i32)),
+            }),
+            _ => None,
+        }
+    }
+
+    fn parse_clip(inverse: bool, args: &str) -> Option<TagKind> {
+        let inner = Self::paren_args(args)?;
+        let shape = match Self::numbers(args).as_deref() {
+            Some([x1, y1, x2, y2]) => ClipShape::Rect {
+                x1: *x1,
+                y1: *y1,
+                x2: *x2,
+                y2: *y2,
+            },
+            _ => match inner.split_once(',') {
+                Some((scale, commands)) => ClipShape::Vector {
+                    scale: Some(Self::number(scale)?),
+                    commands: commands.to_string(),
+                },
+                None => ClipShape::Vector {
+                    scale: None,
+                    commands: inner.to_string(),
+                },
+            },
+        };
+        Some(TagKind::Clip { inverse, shape })
+    }
+
+    fn parse_transform(args: &str) -> Option<TagKind> {
+        let inner = Self::paren_args(args)?;
+        let split = inner.find(Self::PREFIX)?;
+        let (head, body) = inner.split_at(split);
+        let head = head.trim().strip_suffix(',').unwrap_or(head.trim());
+        let numbers = match head.is_empty() {
+            true => Vec::new(),
+            false => head
+                .split(',')
+                .map(Self::number)
+                .collect::<Option<Vec<f64>>>()?,
+        };
+        let (times, accel) = match numbers.as_slice() {
+            [] => (None, None),
+            [accel] => (None, Some(*accel)),
+            [t1, t2] => (Some((*t1 as i32, *t2 as i32)), None),
+            [t1, t2, accel] => (Some((*t1 as i32, *t2 as i32)), Some(*accel)),
+            _ => return None,
+        };
+        let tags = split_items(body).into_iter().map(Tag::parse).collect();
+        Some(TagKind::Transform { times, accel, tags })
+    }
+
+    pub fn to_text(&self) -> String {
+        match &self.raw {
+            Some(raw) if Self::parse_kind(raw) == self.kind => raw.clone(),
+            _ => self.kind.to_text(),
+        }
+    }
+}
+
+impl TagKind {
+    fn join(values: &[f64]) -> String {
+        let values: Vec<String> = values.iter().map(f64::to_string).collect();
+        values.join(",")
+    }
+
+    fn karaoke_name(style: KaraokeStyle) -> &'static str {
+        match style {
+            KaraokeStyle::Plain => "k",
+            KaraokeStyle::Fill => "kf",
+            KaraokeStyle::FillSweep => "K",
+            KaraokeStyle::Outline => "ko",
+        }
+    }
+
+    fn axis_name(axis: Axis) -> &'static str {
+        match axis {
+            Axis::X => "frx",
+            Axis::Y => "fry",
+            Axis::Z => "frz",
+        }
+    }
+
+    fn flag(value: bool) -> u8 {
+        match value {
+            true => 1,
+            false => 0,
+        }
+    }
+
+    fn clip_text(inverse: bool, shape: &ClipShape) -> String {
+        let name = match inverse {
+            true => "iclip",
+            false => "clip",
+        };
+        let args = match shape {
+            ClipShape::Rect { x1, y1, x2, y2 } => Self::join(&[*x1, *y1, *x2, *y2]),
+            ClipShape::Vector {
+                scale: Some(scale),
+                commands,
+            } => format!("{},{}", scale, commands),
+            ClipShape::Vector {
+                scale: None,
+                commands,
+            } => commands.clone(),
+        };
+        format!("\\{}({})", name, args)
+    }
+
+    fn transform_text(times: &Option<(i32, i32)>, accel: &Option<f64>, tags: &[Tag]) -> String {
+        let mut head = String::new();
+        if let Some((t1, t2)) = times {
+            head.push_str(&format!("{},{},", t1, t2));
+        }
+        if let Some(accel) = accel {
+            head.push_str(&format!("{},", accel));
+        }
+        let body: String = tags.iter().map(Tag::to_text).collect();
+        format!("\\t({}{})", head, body)
+    }
+
+    pub fn to_text(&self) -> String {
+        match self {
+            TagKind::Pos { x, y } => format!("\\pos({})", Self::join(&[*x, *y])),
+            TagKind::Org { x, y } => format!("\\org({})", Self::join(&[*x, *y])),
+            TagKind::Move {
+                x1,
+                y1,
+                x2,
+                y2,
+                times,
+            } => match times {
+                Some((t1, t2)) => format!(
+                    "\\move({},{},{})",
+                    Self::join(&[*x1, *y1, *x2, *y2]),
+                    t1,
+                    t2
+                ),
+                None => format!("\\move({})", Self::join(&[*x1, *y1, *x2, *y2])),
+            },
+            TagKind::Fad { fade_in, fade_out } => format!("\\fad({},{})", fade_in, fade_out),
+            TagKind::Fade { values } => {
+                let values: Vec<String> = values.iter().map(i32::to_string).collect();
+                format!("\\fade({})", values.join(","))
+            }
+            TagKind::Transform { times, accel, tags } => Self::transform_text(times, accel, tags),
+            TagKind::Karaoke { style, duration } => {
+                format!("\\{}{}", Self::karaoke_name(*style), duration)
+            }
+            TagKind::FontName(name) => format!("\\fn{}", name),
+            TagKind::FontSize(size) => format!("\\fs{}", size),
+            TagKind::Colour { index: 1, colour } => format!("\\c{}", colour.to_bgr()),
+            TagKind::Colour { index, colour } => format!("\\{}c{}", index, colour.to_bgr()),
+            TagKind::Alpha { index: None, value } => format!("\\alpha&H{:02X}&", value),
+            TagKind::Alpha {
+                index: Some(index),
+                value,
+            } => format!("\\{}a&H{:02X}&", index, value),
+            TagKind::Clip { inverse, shape } => Self::clip_text(*inverse, shape),
+            TagKind::Bold(weight) => format!("\\b{}", weight),
+            TagKind::Italic(value) => format!("\\i{}", Self::flag(*value)),
+            TagKind::Underline(value) => format!("\\u{}", Self::flag(*value)),
+            TagKind::StrikeOut(value) => format!("\\s{}", Self::flag(*value)),
+            TagKind::Border(value) => format!("\\bord{}", value),
+            TagKind::Shadow(value) => format!("\\shad{}", value),
+            TagKind::Blur(value) => format!("\\blur{}", value),
+            TagKind::BlurEdges(value) => format!("\\be{}", value),
+            TagKind::ScaleX(value) => format!("\\fscx{}", value),
+            TagKind::ScaleY(value) => format!("\\fscy{}", value),
+            TagKind::Spacing(value) => format!("\\fsp{}", value),
+            TagKind::Rotation { axis, degrees } => {
+                format!("\\{}{}", Self::axis_name(*axis), degrees)
+            }
+            TagKind::Alignment(value) => format!("\\an{}", value),
+            TagKind::WrapStyle(value) => format!("\\q{}", value),
+            TagKind::Reset(style) => format!("\\r{}", style.as_deref().unwrap_or_default()),
+            TagKind::Drawing(scale) => format!("\\p{}", scale),
+            TagKind::Other { name, args } => format!("\\{}{}", name, args),
+        }
+    }
+}
+
+pub fn split_items(content: &str) -> Vec<&str> {
+    let mut items = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (idx, c) in content.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            '\\' if depth == 0 && idx > start => {
+                items.push(&content[start..idx]);
+                start = idx;
+            }
+            _ => {}
+        }
+    }
+    if start < content.len() {
+        items.push(&content[start..]);
+    }
+    items
+}
@@ -0,0 +1,168 @@
+use crate::model::format::ass::style_ass::AssColor;
+
+use super::tag::{ClipShape, KaraokeStyle, Tag, TagKind};
+use super::*;
+
+struct TestCase {
+    name: &'static str,
+    input: &'static str,
+}
+
+static ROUND_TRIP_CASES: [TestCase; 8] = [
+    TestCase {
+        name: "plain text",
+        input: "Hello there",
+    },
+    TestCase {
+        name: "positioned sign with fade",
+        input: "{\\an8\\pos(640,50)\\fad(200,300)}Sign text",
+    },
+    TestCase {
+        name: "transform with nested clip",
+        input: "{\\t(0,500,0.5,\\clip(0,0,320,240)\\fscx120)}Zoom",
+    },
+    TestCase {
+        name: "move with times and colours",
+        input: "{\\move(10,20,30.5,40,0,1000)\\1c&H00FF00&\\3a&H80&}Go",
+    },
+    TestCase {
+        name: "comments and unknown tags",
+        input: "{TL note: pun}{\\xyz12\\fe0}Text{\\r}",
+    },
+    TestCase {
+        name: "line breaks and hard spaces",
+        input: "First\\NSecond\\nThird\\hword",
+    },
+    TestCase {
+        name: "lone braces and backslashes",
+        input: "a { b \\ c } d {",
+    },
+    TestCase {
+        name: "drawing mode",
+        input: "{\\p1}m 0 0 l 100 0 100 100{\\p0}After",
+    },
+];
+
+fn run_test_case(test_case: &TestCase) {
+    let text = AssText::parse(test_case.input);
+    assert_eq!(
+        text.to_text(),
+        test_case.input,
+        "Failed at case: {}",
+        test_case.name
+    );
+}
+
+#[test]
+fn test_round_trip_is_lossless() {
+    ROUND_TRIP_CASES.iter().for_each(run_test_case);
+}
+
+#[test]
+fn test_typed_tags() {
+    let text = AssText::parse(ROUND_TRIP_CASES[3].input);
+    let kinds: Vec<&TagKind> = text.tags().map(|tag| &tag.kind).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            &TagKind::Move {
+                x1: 10.0,
+                y1: 20.0,
+                x2: 30.5,
+                y2: 40.0,
+                times: Some((0, 1000)),
+            },
+            &TagKind::Colour {
+                index: 1,
+                colour: AssColor::new(0, 0xFF, 0, 0),
+            },
+            &TagKind::Alpha {
+                index: Some(3),
+                value: 0x80,
+            },
+        ],
+        "Failed at case: {}",
+        ROUND_TRIP_CASES[3].name
+    );
+}
+
+#[test]
+fn test_transform_contains_nested_tags() {
+    let text = AssText::parse(ROUND_TRIP_CASES[2].input);
+    let tag = text.tags().next().unwrap();
+    match &tag.kind {
+        TagKind::Transform { times, accel, tags } => {
+            assert_eq!(*times, Some((0, 500)));
+            assert_eq!(*accel, Some(0.5));
+            assert_eq!(
+                tags[0].kind,
+                TagKind::Clip {
+                    inverse: false,
+                    shape: ClipShape::Rect {
+                        x1: 0.0,
+                        y1: 0.0,
+                        x2: 320.0,
+                        y2: 240.0,
+                    },
+                }
+            );
+            assert_eq!(tags[1].kind, TagKind::ScaleX(120.0));
+        }
+        other => panic!("Expected Transform, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_edited_tag_is_regenerated() {
+    let mut text = AssText::parse("{\\an8\\pos(640.0,50)\\k25}Sign");
+    for tag in text.tags_mut() {
+        match &mut tag.kind {
+            TagKind::Pos { y, .. } => *y = 80.0,
+            TagKind::Karaoke { duration, .. } => *duration = 40,
+            _ => {}
+        }
+    }
+    assert_eq!(text.to_text(), "{\\an8\\pos(640,80)\\k40}Sign");
+}
+
+#[test]
+fn test_new_tags_are_serialized() {
+    let tags = [
+        Tag::new(TagKind::Karaoke {
+            style: KaraokeStyle::Fill,
+            duration: 15,
+        }),
+        Tag::new(TagKind::Colour {
+            index: 3,
+            colour: AssColor::new(0x10, 0x20, 0x30, 0),
+        }),
+        Tag::new(TagKind::Reset(None)),
+    ];
+    let serialized: Vec<String> = tags.iter().map(Tag::to_text).collect();
+    assert_eq!(serialized, vec!["\\kf15", "\\3c&H302010&", "\\r"]);
+}
+
+#[test]
+fn test_segments() {
+    let text = AssText::parse(ROUND_TRIP_CASES[7].input);
+    assert!(matches!(text.segments[1], TextSegment::Drawing(_)));
+    assert_eq!(text.segments[3], TextSegment::Text("After".to_string()));
+
+    let text = AssText::parse(ROUND_TRIP_CASES[5].input);
+    assert_eq!(text.segments[1], TextSegment::HardBreak);
+    assert_eq!(text.segments[3], TextSegment::SoftBreak);
+    assert_eq!(text.segments[5], TextSegment::HardSpace);
+    assert_eq!(text.plain_text(), "First\nSecond\nThird word");
+}
+
+#[test]
+fn test_blank_and_override_length() {
+    let text = AssText::parse("{\\an8}  {\\i1}");
+    assert!(text.is_blank());
+    assert_eq!(text.override_len(), 11);
+    assert!(!AssText::parse(ROUND_TRIP_CASES[0].input).is_blank());
+    assert_eq!(
+        AssText::parse(ROUND_TRIP_CASES[1].input).strip_overrides(),
+        "Sign text"
+    );
+}
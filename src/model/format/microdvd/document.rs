@@ -0,0 +1,7 @@
+use crate::model::format::microdvd::line_microdvd::MicroDvdLine;
+
+#[derive(Debug, Clone)]
+pub struct MicroDvdDocument {
+    pub frame_rate: Option<f64>,
+    pub cues: Vec<MicroDvdLine>,
+}
@@ -0,0 +1,6 @@
+#[derive(Debug, Clone)]
+pub struct MicroDvdLine {
+    pub start_frame: i64,
+    pub end_frame: i64,
+    pub text: String,
+}
@@ -0,0 +1,3 @@
+pub mod document;
+pub mod line_microdvd;
+pub mod parser;
@@ -0,0 +1,122 @@
+use regex::Regex;
+use std::result::Result;
+
+use crate::model::{
+    format::microdvd::{document::MicroDvdDocument, line_microdvd::MicroDvdLine},
+    parse_error::ParserError,
+};
+
+pub struct MicroDvdParser {
+    frame_re: Regex,
+}
+
+impl MicroDvdParser {
+    const BOM: char = '\u{feff}';
+    const LINE_SEPARATOR: &str = "|";
+    const LINE_BREAK: &str = "\n";
+    const HEADER_FRAMES: [i64; 2] = [0, 1];
+    const DEFAULT_DURATION_FRAMES: i64 = 48;
+
+    const FRAME_RE: &str = r"^\{(\d+)\}\{(\d*)\}(.*)$";
+
+    pub fn new() -> Self {
+        Self {
+            frame_re: Regex::new(Self::FRAME_RE).unwrap(),
+        }
+    }
+
+    fn parse_frame(&self, line_number: usize, line: &str, value: &str) -> Result<i64, ParserError> {
+        value
+            .parse()
+            .map_err(|_| ParserError::invalid_number(value).at_line(line_number, line))
+    }
+
+    fn parse_line(
+        &self,
+        line_number: usize,
+        line: &str,
+    ) -> Result<(i64, Option<i64>, String), ParserError> {
+        let caps = self
+            .frame_re
+            .captures(line)
+            .ok_or_else(|| ParserError::FrameLine {
+                found: line.to_string(),
+            })?;
+        let start = self.parse_frame(line_number, line, &caps[1])?;
+        let end = match caps[2].is_empty() {
+            true => None,
+            false => Some(self.parse_frame(line_number, line, &caps[2])?),
+        };
+        let text = caps[3].replace(Self::LINE_SEPARATOR, Self::LINE_BREAK);
+        Ok((start, end, text))
+    }
+
+    fn header_rate(start: i64, end: Option<i64>, text: &str) -> Option<f64> {
+        let is_header = Self::HEADER_FRAMES.contains(&start)
+            && end.is_none_or(|end| Self::HEADER_FRAMES.contains(&end));
+        match is_header {
+            true => text.trim().parse().ok().filter(|fps: &f64| *fps > 0.0),
+            false => None,
+        }
+    }
+
+    pub fn parse_lines(&self, lines: &[String]) -> Result<MicroDvdDocument, ParserError> {
+        let mut frame_rate = None;
+        let mut parsed: Vec<(i64, Option<i64>, String)> = Vec::new();
+        for (idx, line) in lines.iter().enumerate() {
+            let line = line.trim_start_matches(Self::BOM).trim();
+            if line.is_empty() {
+                continue;
+            }
+            let (start, end, text) = self.parse_line(idx + 1, line)?;
+            match Self::header_rate(start, end, &text) {
+                Some(fps) if parsed.is_empty() && frame_rate.is_none() => frame_rate = Some(fps),
+                _ => parsed.push((start, end, text)),
+            }
+        }
+        let next_starts: Vec<Option<i64>> = parsed
+            .iter()
+            .skip(1)
+            .map(|(start, _, _)| Some(*start))
+            .chain(std::iter::once(None))
+            .collect();
+        let cues = parsed
+            .into_iter()
+            .zip(next_starts)
+            .map(|((start, end, text), next)| MicroDvdLine {
+                start_frame: start,
+                end_frame: end
+                    .or(next)
+                    .unwrap_or(start + Self::DEFAULT_DURATION_FRAMES),
+                text,
+            })
+            .collect();
+        Ok(MicroDvdDocument { frame_rate, cues })
+    }
+
+    pub fn to_lines(&self, doc: &MicroDvdDocument) -> Vec<String> {
+        let header = doc
+            .frame_rate
+            .map(|fps| format!("{{1}}{{1}}{}", (fps * 1_000.0).round() / 1_000.0));
+        header
+            .into_iter()
+            .chain(doc.cues.iter().map(|cue| {
+                format!(
+                    "{{{}}}{{{}}}{}",
+                    cue.start_frame,
+                    cue.end_frame,
+                    cue.text.replace(Self::LINE_BREAK, Self::LINE_SEPARATOR)
+                )
+            }))
+            .collect()
+    }
+}
+
+impl Default for MicroDvdParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,141 @@
+use crate::model::{parse_error::ParseRes, timestamp::Timestamp};
+
+use super::*;
+
+struct TestCase {
+    name: &'static str,
+    input: &'static [&'static str],
+    frame_rate: Option<f64>,
+    expected: &'static [(i64, i64, &'static str)],
+}
+
+static HEADER_CASE: TestCase = TestCase {
+    name: "reads the frame rate header and keeps control codes",
+    input: &[
+        "\u{feff}{1}{1}23.976",
+        "{24}{48}{y:i}Local italics|Plain {c:$0000FF}red",
+        "",
+        "{72}{96}{Y:b}Bold everywhere",
+    ],
+    frame_rate: Some(23.976),
+    expected: &[
+        (24, 48, "{y:i}Local italics\nPlain {c:$0000FF}red"),
+        (72, 96, "{Y:b}Bold everywhere"),
+    ],
+};
+
+static OPEN_END_CASE: TestCase = TestCase {
+    name: "fills missing end frames from the next cue or the default duration",
+    input: &["{10}{}First", "{30}{}Second"],
+    frame_rate: None,
+    expected: &[(10, 30, "First"), (30, 78, "Second")],
+};
+
+static LATE_HEADER_CASE: TestCase = TestCase {
+    name: "treats a header line after the first cue as a cue",
+    input: &["{10}{20}First", "{1}{1}25"],
+    frame_rate: None,
+    expected: &[(10, 20, "First"), (1, 1, "25")],
+};
+
+static BAD_RATE_CASE: TestCase = TestCase {
+    name: "keeps a header with an invalid frame rate as a cue",
+    input: &["{1}{1}fast", "{10}{20}First"],
+    frame_rate: None,
+    expected: &[(1, 1, "fast"), (10, 20, "First")],
+};
+
+fn to_lines(input: &[&str]) -> Vec<String> {
+    input.iter().map(|s| s.to_string()).collect()
+}
+
+fn run_test_case(test_case: &TestCase) -> ParseRes<()> {
+    let doc = MicroDvdParser::new().parse_lines(&to_lines(test_case.input))?;
+    let cues: Vec<(i64, i64, &str)> = doc
+        .cues
+        .iter()
+        .map(|cue| (cue.start_frame, cue.end_frame, cue.text.as_str()))
+        .collect();
+    assert_eq!(
+        doc.frame_rate, test_case.frame_rate,
+        "Failed at case: {}",
+        test_case.name
+    );
+    assert_eq!(
+        cues, test_case.expected,
+        "Failed at case: {}",
+        test_case.name
+    );
+    Ok(())
+}
+
+#[test]
+fn test_header() -> ParseRes<()> {
+    run_test_case(&HEADER_CASE)
+}
+
+#[test]
+fn test_open_end() -> ParseRes<()> {
+    run_test_case(&OPEN_END_CASE)
+}
+
+#[test]
+fn test_late_header() -> ParseRes<()> {
+    run_test_case(&LATE_HEADER_CASE)
+}
+
+#[test]
+fn test_bad_rate() -> ParseRes<()> {
+    run_test_case(&BAD_RATE_CASE)
+}
+
+#[test]
+fn test_frames_to_time() -> ParseRes<()> {
+    let parser = MicroDvdParser::new();
+    let with_header = parser.parse_lines(&to_lines(&["{1}{1}25", "{50}{75}Hi"]))?;
+    let without_header = parser.parse_lines(&to_lines(&["{48}{72}Hi"]))?;
+    let times = |doc: &MicroDvdDocument, fps: f64| {
+        let fps = doc.frame_rate.unwrap_or(fps);
+        let cue = &doc.cues[0];
+        (
+            Timestamp::from_frame(cue.start_frame, fps).millis(),
+            Timestamp::from_frame(cue.end_frame, fps).millis(),
+        )
+    };
+    assert_eq!(times(&with_header, 23.976), (2_000, 3_000));
+    assert_eq!(times(&without_header, 24.0), (2_000, 3_000));
+    assert_eq!(times(&without_header, 23.976), (2_002, 3_003));
+    assert_eq!(Timestamp::from_millis(2_002).to_frame(23.976), 48);
+    Ok(())
+}
+
+#[test]
+fn test_round_trip() -> ParseRes<()> {
+    let parser = MicroDvdParser::new();
+    let input = to_lines(&["{1}{1}23.976", "{24}{48}{y:i}One|Two", "{72}{96}Three"]);
+    let doc = parser.parse_lines(&input)?;
+    assert_eq!(parser.to_lines(&doc), input);
+    Ok(())
+}
+
+#[test]
+fn test_error_malformed_line() {
+    let result = MicroDvdParser::new().parse_lines(&to_lines(&["{10}{20}Fine", "[10][20]Nope"]));
+    match result {
+        Err(ParserError::FrameLine { found }) => assert_eq!(found, "[10][20]Nope"),
+        _ => panic!("Expected FrameLine, got {:?}", result),
+    }
+}
+
+#[test]
+fn test_error_bad_frame_number() {
+    let input = to_lines(&["{10}{20}Fine", "{30}{99999999999999999999}Huge"]);
+    let result = MicroDvdParser::new().parse_lines(&input);
+    match result {
+        Err(ParserError::InvalidNumber { location, found }) => {
+            assert_eq!(found, "99999999999999999999");
+            assert_eq!(location.line, 2);
+        }
+        _ => panic!("Expected InvalidNumber, got {:?}", result),
+    }
+}
@@ -1 +1,6 @@
 pub mod ass;
+pub mod microdvd;
+pub mod srt;
+pub mod subviewer;
+pub mod ttml;
+pub mod vtt;
@@ -0,0 +1,6 @@
+use crate::model::{format::srt::parser::SrtParser, stages};
+
+pub type SceneApplier = stages::applier::SceneApplier<SrtParser>;
+
+#[cfg(test)]
+mod tests;
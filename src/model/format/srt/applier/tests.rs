@@ -0,0 +1,61 @@
+use crate::model::parse_error::ParseRes;
+
+use super::*;
+
+struct TestCase {
+    name: &'static str,
+    input: &'static [&'static str],
+    translations: &'static [&'static str],
+    expected: &'static [&'static str],
+}
+
+static SELECTIVE_APPLY_CASE: TestCase = TestCase {
+    name: "only replaces the text of labelled cues and keeps the label",
+    input: &[
+        "1",
+        "00:00:01,000 --> 00:00:03,000",
+        "Normal Subtitle",
+        "",
+        "2",
+        "00:00:20,000 --> 00:00:22,000",
+        "[ADDITIONAL SCENE] Target Scene",
+        "",
+        "3",
+        "00:00:25,000 --> 00:00:27,000",
+        "Another Subtitle",
+    ],
+    translations: &["Escena\\Nprocesada"],
+    expected: &[
+        "1",
+        "00:00:01,000 --> 00:00:03,000",
+        "Normal Subtitle",
+        "",
+        "2",
+        "00:00:20,000 --> 00:00:22,000",
+        "[ADDITIONAL SCENE] Escena",
+        "procesada",
+        "",
+        "3",
+        "00:00:25,000 --> 00:00:27,000",
+        "Another Subtitle",
+    ],
+};
+
+fn run_test_case(test_case: &TestCase) -> ParseRes<()> {
+    let input: Vec<String> = test_case.input.iter().map(|s| s.to_string()).collect();
+    let translations: Vec<String> = test_case
+        .translations
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    let expected: Vec<String> = test_case.expected.iter().map(|s| s.to_string()).collect();
+    let applier = SceneApplier::new();
+    let result = applier.run(&input, &translations)?;
+    assert_eq!(result, expected, "Failed at case: {}", test_case.name);
+    Ok(())
+}
+
+#[test]
+fn test_selective_apply() -> ParseRes<()> {
+    run_test_case(&SELECTIVE_APPLY_CASE)
+}
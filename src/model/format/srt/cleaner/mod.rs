@@ -0,0 +1,6 @@
+use crate::model::{format::srt::parser::SrtParser, stages};
+
+pub type Cleaner = stages::cleaner::Cleaner<SrtParser>;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,116 @@
+use crate::model::parse_error::{ParseRes, ParserError};
+
+use super::*;
+
+struct TestCase {
+    name: &'static str,
+    input: &'static [&'static str],
+    expected: &'static [&'static str],
+}
+
+static DUPLICATE_CASE: TestCase = TestCase {
+    name: "removes consecutive duplicate cues",
+    input: &[
+        "1",
+        "00:00:01,000 --> 00:00:04,000",
+        "Line A",
+        "",
+        "2",
+        "00:00:01,000 --> 00:00:04,000",
+        "Line A",
+        "",
+        "3",
+        "00:00:02,000 --> 00:00:05,000",
+        "Line B",
+    ],
+    expected: &[
+        "1",
+        "00:00:01,000 --> 00:00:04,000",
+        "Line A",
+        "",
+        "2",
+        "00:00:02,000 --> 00:00:05,000",
+        "Line B",
+    ],
+};
+
+static EMPTY_TEXT_CASE: TestCase = TestCase {
+    name: "removes cues that only contain tags",
+    input: &[
+        "1",
+        "00:00:01,000 --> 00:00:02,000",
+        "<i></i>",
+        "",
+        "2",
+        "00:00:02,000 --> 00:00:03,000",
+        "{\\an8}",
+        "",
+        "3",
+        "00:00:03,000 --> 00:00:04,000",
+        "<i>Kept</i>",
+    ],
+    expected: &["1", "00:00:03,000 --> 00:00:04,000", "<i>Kept</i>"],
+};
+
+static CRLF_AND_GAPS_CASE: TestCase = TestCase {
+    name: "tolerates CRLF endings, missing indices and extra blank lines",
+    input: &[
+        "00:00:01,000 --> 00:00:02,000\r",
+        "First line\r",
+        "second line\r",
+        "\r",
+        "",
+        "7\r",
+        "00:00:03,500 --> 00:00:04,250\r",
+        "Third\r",
+    ],
+    expected: &[
+        "1",
+        "00:00:01,000 --> 00:00:02,000",
+        "First line",
+        "second line",
+        "",
+        "2",
+        "00:00:03,500 --> 00:00:04,250",
+        "Third",
+    ],
+};
+
+static MISSING_TIMING_CASE: TestCase = TestCase {
+    name: "fails when a cue has no timing line",
+    input: &["1", "Just text", "More text"],
+    expected: &[],
+};
+
+fn run_test_case(test_case: &TestCase) -> ParseRes<()> {
+    let mut input: Vec<String> = test_case.input.iter().map(|s| s.to_string()).collect();
+    let expected: Vec<String> = test_case.expected.iter().map(|s| s.to_string()).collect();
+    let cleaner = Cleaner::new();
+    cleaner.run(&mut input)?;
+    assert_eq!(input, expected, "Failed at case: {}", test_case.name);
+    Ok(())
+}
+
+#[test]
+fn test_clean_duplicates() -> ParseRes<()> {
+    run_test_case(&DUPLICATE_CASE)
+}
+
+#[test]
+fn test_clean_empty_text() -> ParseRes<()> {
+    run_test_case(&EMPTY_TEXT_CASE)
+}
+
+#[test]
+fn test_clean_crlf_and_gaps() -> ParseRes<()> {
+    run_test_case(&CRLF_AND_GAPS_CASE)
+}
+
+#[test]
+fn test_error_missing_timing() {
+    let result = run_test_case(&MISSING_TIMING_CASE);
+    match result {
+        Err(ParserError::TimingLine { found }) => assert_eq!(found, "1"),
+        _ => panic!("Expected TimingLine, got {:?}", result),
+    }
+}
@@ -0,0 +1,6 @@
+use crate::model::{format::srt::parser::SrtParser, stages};
+
+pub type SceneExtractor = stages::extractor::SceneExtractor<SrtParser>;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,46 @@
+use crate::model::parse_error::ParseRes;
+
+use super::*;
+
+struct TestCase {
+    name: &'static str,
+    input: &'static [&'static str],
+    expected: &'static [&'static str],
+}
+
+static BASIC_CASE: TestCase = TestCase {
+    name: "extracts labelled additional scene cues",
+    input: &[
+        "1",
+        "00:00:00,500 --> 00:00:02,500",
+        "Hey! Are you ready?",
+        "",
+        "2",
+        "00:00:03,000 --> 00:00:05,000",
+        "[ADDITIONAL SCENE] Extra scene line 1",
+        "",
+        "3",
+        "00:00:05,500 --> 00:00:07,000",
+        "[Extra Scene] Extra scene line 2",
+        "second row",
+        "",
+        "4",
+        "00:00:07,500 --> 00:00:09,000",
+        "[Music] Let's go!",
+    ],
+    expected: &["Extra scene line 1", "Extra scene line 2\\Nsecond row"],
+};
+
+fn run_test_case(test_case: &TestCase) -> ParseRes<()> {
+    let input: Vec<String> = test_case.input.iter().map(|s| s.to_string()).collect();
+    let expected: Vec<String> = test_case.expected.iter().map(|s| s.to_string()).collect();
+    let extractor = SceneExtractor::new();
+    let result = extractor.run(&input)?;
+    assert_eq!(result, expected, "Failed at case: {}", test_case.name);
+    Ok(())
+}
+
+#[test]
+fn test_extract_basic() -> ParseRes<()> {
+    run_test_case(&BASIC_CASE)
+}
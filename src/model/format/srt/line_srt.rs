@@ -0,0 +1,25 @@
+use crate::model::{line::Line, timestamp::Timestamp};
+
+#[derive(Debug, Clone)]
+pub struct SrtLine {
+    pub start: Timestamp,
+    pub end: Timestamp,
+    pub name: String,
+    pub text: String,
+}
+
+impl Line for SrtLine {
+    fn start(&self) -> Timestamp {
+        self.start
+    }
+    fn end(&self) -> Timestamp {
+        self.end
+    }
+    fn text(&self) -> &str {
+        &self.text
+    }
+    fn set_times(&mut self, start: Timestamp, end: Timestamp) {
+        self.start = start;
+        self.end = end;
+    }
+}
@@ -0,0 +1,8 @@
+pub mod applier;
+pub mod cleaner;
+pub mod extractor;
+pub mod line_srt;
+pub mod parser;
+pub mod sorter;
+pub mod srt_processor;
+pub mod synchronizer;
@@ -0,0 +1,250 @@
+use regex::Regex;
+use std::result::Result;
+
+use crate::model::{
+    format::srt::line_srt::SrtLine,
+    parse_error::{ParseRes, ParserError},
+    stages::CueFormat,
+    synchronizer::SceneMarker,
+    timestamp::Timestamp,
+};
+
+pub struct SrtParser {
+    tags_re: Regex,
+    scene_label_re: Regex,
+}
+
+impl SrtParser {
+    const TIMING_ARROW: &str = "-->";
+    const LINE_BREAK: &str = "\n";
+    const INLINE_BREAK: &str = "\\N";
+
+    const TAGS_EXPRESSION_RE: &str = r"<[^>]*>|\{.*?\}";
+    const SCENE_NAME: &str = "ADDITIONAL SCENE";
+    const SCENE_LABEL_RE: &str = r"^\[(?i)(additional scene|extra scene|bonus scene|special)\]\s*";
+
+    const EXCESSIVE_TAGS_THRESHOLD: usize = 50;
+
+    pub fn new() -> Self {
+        Self {
+            tags_re: Regex::new(Self::TAGS_EXPRESSION_RE).unwrap(),
+            scene_label_re: Regex::new(Self::SCENE_LABEL_RE).unwrap(),
+        }
+    }
+
+    fn split_blocks<'a>(&self, lines: &'a [String]) -> Vec<(usize, Vec<&'a str>)> {
+        let mut blocks = Vec::new();
+        let mut current = Vec::new();
+        let mut first_line = 0;
+        for (idx, line) in lines.iter().enumerate() {
+            let line = line.trim_end();
+            match line.is_empty() {
+                true if !current.is_empty() => {
+                    blocks.push((first_line, std::mem::take(&mut current)))
+                }
+                true => {}
+                false if current.is_empty() => {
+                    first_line = idx + 1;
+                    current.push(line);
+                }
+                false => current.push(line),
+            }
+        }
+        if !current.is_empty() {
+            blocks.push((first_line, current));
+        }
+        blocks
+    }
+
+    fn timing_index(&self, block: &[&str]) -> Result<usize, ParserError> {
+        match block
+            .iter()
+            .take(2)
+            .position(|l| l.contains(Self::TIMING_ARROW))
+        {
+            Some(idx) => Ok(idx),
+            None => Err(ParserError::TimingLine {
+                found: block[0].to_string(),
+            }),
+        }
+    }
+
+    fn parse_timing(&self, line: &str) -> Result<(Timestamp, Timestamp), ParserError> {
+        let mut parts = line.splitn(2, Self::TIMING_ARROW);
+        let start = parts.next().unwrap_or_default();
+        let rest = parts.next().unwrap_or_default();
+        let end = rest.split_whitespace().next().unwrap_or_default();
+        Ok((self.parse_time(start.trim())?, self.parse_time(end)?))
+    }
+
+    fn split_label(&self, text: &str) -> (String, String) {
+        match self.scene_label_re.captures(text) {
+            Some(caps) => {
+                let label_len = caps.get(0).map_or(0, |m| m.end());
+                (caps[1].to_string(), text[label_len..].to_string())
+            }
+            None => (String::new(), text.to_string()),
+        }
+    }
+
+    fn parse_block(&self, first_line: usize, block: &[&str]) -> Result<SrtLine, ParserError> {
+        let timing_idx = self.timing_index(block)?;
+        let timing = block[timing_idx];
+        let (start, end) = self
+            .parse_timing(timing)
+            .map_err(|e| e.at_line(first_line + timing_idx, timing))?;
+        let text = block[timing_idx + 1..].join(Self::LINE_BREAK);
+        let (name, text) = self.split_label(&text);
+        Ok(SrtLine {
+            start,
+            end,
+            name,
+            text,
+        })
+    }
+
+    pub fn parse_time(&self, t: &str) -> Result<Timestamp, ParserError> {
+        Timestamp::parse_srt(t).ok_or_else(|| ParserError::invalid_timestamp(t))
+    }
+
+    pub fn parse_lines(&self, lines: &[String]) -> Result<Vec<SrtLine>, ParserError> {
+        self.split_blocks(lines)
+            .iter()
+            .map(|(first_line, block)| self.parse_block(*first_line, block))
+            .collect()
+    }
+
+    fn full_text(&self, line: &SrtLine) -> String {
+        match line.name.is_empty() {
+            true => line.text.clone(),
+            false => format!("[{}] {}", line.name, line.text),
+        }
+    }
+
+    fn push_cue(&self, out: &mut Vec<String>, idx: usize, line: &SrtLine) {
+        out.push((idx + 1).to_string());
+        out.push(format!(
+            "{} {} {}",
+            line.start.to_srt(),
+            Self::TIMING_ARROW,
+            line.end.to_srt()
+        ));
+        out.extend(
+            self.full_text(line)
+                .split(Self::LINE_BREAK)
+                .map(String::from),
+        );
+    }
+
+    pub fn to_lines(&self, cues: &[SrtLine]) -> Vec<String> {
+        let mut out = Vec::new();
+        for (idx, line) in cues.iter().enumerate() {
+            if idx > 0 {
+                out.push(String::new());
+            }
+            self.push_cue(&mut out, idx, line);
+        }
+        out
+    }
+
+    pub fn build_line_key(&self, line: &SrtLine) -> String {
+        format!(
+            "{}|{}|{}",
+            line.start.millis(),
+            line.end.millis(),
+            line.text
+        )
+    }
+
+    pub fn is_scene_line(&self, line: &SrtLine) -> bool {
+        !line.name.is_empty()
+    }
+
+    pub fn mark_scene(&self, line: &mut SrtLine) {
+        if !self.is_scene_line(line) {
+            line.name = Self::SCENE_NAME.to_string();
+        }
+    }
+
+    pub fn is_text_empty(&self, line: &SrtLine) -> bool {
+        self.tags_re.replace_all(&line.text, "").trim().is_empty()
+    }
+
+    pub fn has_excessive_tags(&self, text: &str) -> bool {
+        let total_tags_len: usize = self.tags_re.find_iter(text).map(|m| m.as_str().len()).sum();
+        total_tags_len > Self::EXCESSIVE_TAGS_THRESHOLD
+    }
+
+    pub fn to_single_line(&self, text: &str) -> String {
+        text.replace(Self::LINE_BREAK, Self::INLINE_BREAK)
+    }
+
+    pub fn from_single_line(&self, text: &str) -> String {
+        text.replace(Self::INLINE_BREAK, Self::LINE_BREAK)
+    }
+}
+
+impl Default for SrtParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SceneMarker<SrtLine> for SrtParser {
+    fn is_scene_line(&self, line: &SrtLine) -> bool {
+        SrtParser::is_scene_line(self, line)
+    }
+
+    fn mark_scene(&self, line: &mut SrtLine) {
+        SrtParser::mark_scene(self, line)
+    }
+}
+
+impl CueFormat for SrtParser {
+    type Cue = SrtLine;
+    type Document = Vec<SrtLine>;
+
+    fn parse(&self, lines: &[String]) -> ParseRes<Vec<SrtLine>> {
+        self.parse_lines(lines)
+    }
+
+    fn to_lines(&self, doc: &Vec<SrtLine>) -> Vec<String> {
+        SrtParser::to_lines(self, doc)
+    }
+
+    fn cues<'d>(&self, doc: &'d Vec<SrtLine>) -> impl Iterator<Item = &'d SrtLine> {
+        doc.iter()
+    }
+
+    fn cues_mut<'d>(&self, doc: &'d mut Vec<SrtLine>) -> impl Iterator<Item = &'d mut SrtLine> {
+        doc.iter_mut()
+    }
+
+    fn retain_cues(&self, doc: &mut Vec<SrtLine>, keep: impl FnMut(&SrtLine) -> bool) {
+        doc.retain(keep);
+    }
+
+    fn set_text(&self, line: &mut SrtLine, text: String) {
+        line.text = text;
+    }
+
+    fn build_line_key(&self, line: &SrtLine) -> String {
+        SrtParser::build_line_key(self, line)
+    }
+
+    fn is_text_empty(&self, line: &SrtLine) -> bool {
+        SrtParser::is_text_empty(self, line)
+    }
+
+    fn has_excessive_tags(&self, text: &str) -> bool {
+        SrtParser::has_excessive_tags(self, text)
+    }
+
+    fn to_single_line(&self, text: &str) -> String {
+        SrtParser::to_single_line(self, text)
+    }
+
+    fn restore_line_breaks(&self, text: &str) -> String {
+        SrtParser::from_single_line(self, text)
+    }
+}
@@ -0,0 +1,6 @@
+use crate::model::{format::srt::parser::SrtParser, stages};
+
+pub type Sorter = stages::sorter::Sorter<SrtParser>;
+
+#[cfg(test)]
+mod tests;
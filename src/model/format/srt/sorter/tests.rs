@@ -0,0 +1,127 @@
+use crate::model::parse_error::{ParseRes, ParserError};
+
+use super::*;
+
+struct TestCase {
+    name: &'static str,
+    input: &'static [&'static str],
+    expected: &'static [&'static str],
+}
+
+static BASIC_ORDER_CASE: TestCase = TestCase {
+    name: "orders cues by start time and renumbers them",
+    input: &[
+        "1",
+        "00:00:05,000 --> 00:00:06,000",
+        "Line 3",
+        "",
+        "2",
+        "00:00:01,000 --> 00:00:02,000",
+        "Line 1",
+        "",
+        "3",
+        "00:00:03,000 --> 00:00:04,000",
+        "Line 2",
+        "with a second row",
+    ],
+    expected: &[
+        "1",
+        "00:00:01,000 --> 00:00:02,000",
+        "Line 1",
+        "",
+        "2",
+        "00:00:03,000 --> 00:00:04,000",
+        "Line 2",
+        "with a second row",
+        "",
+        "3",
+        "00:00:05,000 --> 00:00:06,000",
+        "Line 3",
+    ],
+};
+
+static STABLE_ORDER_CASE: TestCase = TestCase {
+    name: "keeps the original order of cues that start together",
+    input: &[
+        "1",
+        "00:00:02,000 --> 00:00:03,000",
+        "Top",
+        "",
+        "2",
+        "00:00:02,000 --> 00:00:04,000",
+        "Bottom",
+        "",
+        "3",
+        "00:00:01,000 --> 00:00:02,000",
+        "First",
+    ],
+    expected: &[
+        "1",
+        "00:00:01,000 --> 00:00:02,000",
+        "First",
+        "",
+        "2",
+        "00:00:02,000 --> 00:00:03,000",
+        "Top",
+        "",
+        "3",
+        "00:00:02,000 --> 00:00:04,000",
+        "Bottom",
+    ],
+};
+
+static INVALID_TIME_CASE: TestCase = TestCase {
+    name: "fails on a malformed timestamp",
+    input: &["1", "00:00:0x,000 --> 00:00:02,000", "Broken"],
+    expected: &[],
+};
+
+static OVERFLOW_TIME_CASE: TestCase = TestCase {
+    name: "fails on a timestamp past the representable range",
+    input: &["1", "99999999999999:00:00,000 --> 00:00:02,000", "Broken"],
+    expected: &[],
+};
+
+fn run_test_case(test_case: &TestCase) -> ParseRes<()> {
+    let input: Vec<String> = test_case.input.iter().map(|s| s.to_string()).collect();
+    let expected: Vec<String> = test_case.expected.iter().map(|s| s.to_string()).collect();
+    let sorter = Sorter::new();
+    let result = sorter.run(&input)?;
+    assert_eq!(result, expected, "Failed at case: {}", test_case.name);
+    Ok(())
+}
+
+#[test]
+fn test_basic_order() -> ParseRes<()> {
+    run_test_case(&BASIC_ORDER_CASE)
+}
+
+#[test]
+fn test_stable_order() -> ParseRes<()> {
+    run_test_case(&STABLE_ORDER_CASE)
+}
+
+#[test]
+fn test_error_invalid_time() {
+    let result = run_test_case(&INVALID_TIME_CASE);
+    match result {
+        Err(ParserError::InvalidTimestamp { location, found }) => {
+            assert_eq!(found, "00:00:0x,000");
+            assert_eq!(location.line, 2);
+            assert_eq!(location.column, 0);
+        }
+        _ => panic!("Expected InvalidTimestamp, got {:?}", result),
+    }
+}
+
+#[test]
+fn test_error_overflowing_time() {
+    let result = run_test_case(&OVERFLOW_TIME_CASE);
+    match result {
+        Err(ParserError::InvalidTimestamp { location, found }) => {
+            assert_eq!(found, "99999999999999:00:00,000");
+            assert_eq!(location.line, 2);
+        }
+        _ => panic!("Expected InvalidTimestamp, got {:?}", result),
+    }
+}
@@ -0,0 +1,109 @@
+use crate::model::{
+    format::srt::{
+        applier::SceneApplier, cleaner::Cleaner, extractor::SceneExtractor, line_srt::SrtLine,
+        parser::SrtParser, sorter::Sorter, synchronizer::Synchronizer,
+    },
+    parse_error::ParserError,
+    retiming::{
+        anchors::{Anchor, AnchorMap},
+        keyframes::{KeyframeSnap, SnapSummary},
+    },
+    subtitle_processor::{ProcRes, SubtitleProcessor},
+    sync_options::SyncOptions,
+    sync_result::SyncResult,
+    timestamp::Timestamp,
+    translator::{instructor::Instructor, translator::Translator},
+};
+
+pub struct SrtProcessor {
+    sync: SyncOptions,
+}
+
+impl SrtProcessor {
+    pub fn new() -> Self {
+        Self {
+            sync: SyncOptions::default(),
+        }
+    }
+
+    pub fn with_sync(mut self, sync: SyncOptions) -> Self {
+        self.sync = sync;
+        self
+    }
+}
+
+impl Default for SrtProcessor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SubtitleProcessor for SrtProcessor {
+    type Error = ParserError;
+
+    fn synchronize(
+        &self,
+        l_a: &[String],
+        l_b: &[String],
+    ) -> ProcRes<SyncResult<Vec<String>>, Self::Error> {
+        Synchronizer::new(self.sync).run(l_a, l_b)
+    }
+
+    fn retime(&self, lines: &[String], anchors: &[Anchor]) -> ProcRes<Vec<String>, Self::Error> {
+        let parser = SrtParser::new();
+        let mut cues = parser.parse_lines(lines)?;
+        let refs: Vec<&SrtLine> = cues.iter().collect();
+        let map = AnchorMap::new(anchors, &refs)?;
+        map.retime(cues.iter_mut());
+        Ok(parser.to_lines(&cues))
+    }
+
+    fn timings(&self, lines: &[String]) -> ProcRes<Vec<(Timestamp, Timestamp)>, Self::Error> {
+        let cues = SrtParser::new().parse_lines(lines)?;
+        Ok(cues.iter().map(|cue| (cue.start, cue.end)).collect())
+    }
+
+    fn snap_keyframes(
+        &self,
+        lines: &[String],
+        snap: &KeyframeSnap,
+    ) -> ProcRes<(Vec<String>, SnapSummary), Self::Error> {
+        let parser = SrtParser::new();
+        let mut cues = parser.parse_lines(lines)?;
+        let summary = snap.retime(cues.iter_mut());
+        Ok((parser.to_lines(&cues), summary))
+    }
+
+    fn get_lines_to_translate(&self, lines: &mut Vec<String>) -> ProcRes<Vec<String>, Self::Error> {
+        let additional_scenes = SceneExtractor::new().run(lines)?;
+        Ok(Instructor::new().run(&additional_scenes))
+    }
+
+    fn apply_translation(
+        &self,
+        lines: &mut Vec<String>,
+        translations: &[String],
+    ) -> ProcRes<Vec<String>, Self::Error> {
+        SceneApplier::new().run(lines, translations)
+    }
+
+    fn translate_internal(&self, lines: &mut Vec<String>) -> ProcRes<Vec<String>, Self::Error> {
+        let to_translate = SceneExtractor::new().run(lines)?;
+        let translations = Translator::new().run(&to_translate);
+        self.apply_translation(lines, &translations)
+    }
+
+    fn apply_style(&self, lines: &[String]) -> ProcRes<Vec<String>, Self::Error> {
+        Ok(lines.to_vec())
+    }
+
+    fn preprocessing(&self, lines: &mut Vec<String>) -> ProcRes<Vec<String>, Self::Error> {
+        Cleaner::new().run(lines)?;
+        Sorter::new().run(lines)
+    }
+
+    fn has_additional_scene(&self, lines: &[String]) -> ProcRes<bool, Self::Error> {
+        let additional_scenes = SceneExtractor::new().run(lines)?;
+        Ok(!additional_scenes.is_empty())
+    }
+}
@@ -0,0 +1,37 @@
+use crate::model::{
+    format::srt::{line_srt::SrtLine, parser::SrtParser},
+    parse_error::ParseRes,
+    sync_options::SyncOptions,
+    sync_result::SyncResult,
+    synchronizer::LineSynchronizer,
+};
+
+pub struct Synchronizer {
+    parser: SrtParser,
+    options: SyncOptions,
+}
+
+impl Synchronizer {
+    pub fn new(options: SyncOptions) -> Self {
+        Self {
+            parser: SrtParser::new(),
+            options,
+        }
+    }
+
+    pub fn run(
+        &self,
+        source_a: &[String],
+        source_b: &[String],
+    ) -> ParseRes<SyncResult<Vec<String>>> {
+        let cues_a = self.parser.parse_lines(source_a)?;
+        let cues_b = self.parser.parse_lines(source_b)?;
+        let refs_a: Vec<&SrtLine> = cues_a.iter().collect();
+        let refs_b: Vec<&SrtLine> = cues_b.iter().collect();
+        let synced = LineSynchronizer::new(&self.parser, self.options).run(&refs_a, &refs_b);
+        Ok(synced.map(|synced| self.parser.to_lines(&synced.into_lines())))
+    }
+}
+
+#[cfg(test)]
+mod tests;
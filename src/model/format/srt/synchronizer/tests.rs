@@ -0,0 +1,416 @@
+use crate::model::{
+    parse_error::ParserError,
+    sync_options::SyncMode,
+    sync_report::DeltaChange,
+    sync_result::{BlockPlacement, PlacedBlock},
+    timestamp::Timestamp,
+};
+
+use super::*;
+
+struct TestCase {
+    name: &'static str,
+    input_a: &'static [&'static str],
+    input_b: &'static [&'static str],
+    expected: &'static [&'static str],
+}
+
+static START_LATER_CASE: TestCase = TestCase {
+    name: "inserts an additional scene and shifts the following cues",
+    input_a: &[
+        "1",
+        "00:00:01,100 --> 00:00:03,100",
+        "Hola",
+        "",
+        "2",
+        "00:00:04,100 --> 00:00:05,100",
+        "¿Cómo estás?",
+        "",
+        "3",
+        "00:00:05,500 --> 00:00:07,500",
+        "Bien, gracias",
+    ],
+    input_b: &[
+        "1",
+        "00:00:01,000 --> 00:00:03,000",
+        "Hello",
+        "",
+        "2",
+        "00:00:04,000 --> 00:00:06,000",
+        "How are you?",
+        "",
+        "3",
+        "00:00:06,500 --> 00:00:08,000",
+        "[ADDITIONAL SCENE] Extra scene",
+        "",
+        "4",
+        "00:00:08,500 --> 00:00:09,500",
+        "[ADDITIONAL SCENE] Extra continuation",
+        "",
+        "5",
+        "00:00:10,000 --> 00:00:12,000",
+        "Fine, thanks",
+    ],
+    expected: &[
+        "1",
+        "00:00:01,000 --> 00:00:03,000",
+        "Hola",
+        "",
+        "2",
+        "00:00:04,000 --> 00:00:05,000",
+        "¿Cómo estás?",
+        "",
+        "3",
+        "00:00:06,500 --> 00:00:08,000",
+        "[ADDITIONAL SCENE] Extra scene",
+        "",
+        "4",
+        "00:00:08,500 --> 00:00:09,500",
+        "[ADDITIONAL SCENE] Extra continuation",
+        "",
+        "5",
+        "00:00:10,000 --> 00:00:12,000",
+        "Bien, gracias",
+    ],
+};
+
+static NO_SCENES_CASE: TestCase = TestCase {
+    name: "applies the initial delta when B has no additional scenes",
+    input_a: &[
+        "1",
+        "00:00:00,500 --> 00:00:02,500",
+        "<i>Line 1</i>",
+        "",
+        "2",
+        "00:00:03,000 --> 00:00:05,500",
+        "Line 2",
+    ],
+    input_b: &[
+        "1",
+        "00:00:01,500 --> 00:00:03,000",
+        "Line 1 B",
+        "",
+        "2",
+        "00:00:04,000 --> 00:00:07,000",
+        "Line 2 B",
+    ],
+    expected: &[
+        "1",
+        "00:00:01,500 --> 00:00:03,500",
+        "<i>Line 1</i>",
+        "",
+        "2",
+        "00:00:04,000 --> 00:00:06,500",
+        "Line 2",
+    ],
+};
+
+static ANCHOR_CASE: TestCase = TestCase {
+    name: "anchors the initial delta on a later cue and keeps end times",
+    input_a: &[
+        "1",
+        "00:00:00,500 --> 00:00:02,500",
+        "Line 1",
+        "",
+        "2",
+        "00:00:03,000 --> 00:00:05,500",
+        "Line 2",
+    ],
+    input_b: &[
+        "1",
+        "00:00:01,500 --> 00:00:03,000",
+        "Line 1 B",
+        "",
+        "2",
+        "00:00:03,500 --> 00:00:07,000",
+        "Line 2 B",
+    ],
+    expected: &[
+        "1",
+        "00:00:01,000 --> 00:00:02,500",
+        "Line 1",
+        "",
+        "2",
+        "00:00:03,500 --> 00:00:05,500",
+        "Line 2",
+    ],
+};
+
+static UNMATCHED_BLOCK_CASE: TestCase = TestCase {
+    name: "places a block whose anchor never matches by nearest timestamp",
+    input_a: &[
+        "1",
+        "00:00:01,000 --> 00:00:02,000",
+        "Hola",
+        "",
+        "2",
+        "00:00:03,000 --> 00:00:04,000",
+        "¿Cómo estás?",
+        "",
+        "3",
+        "00:00:10,000 --> 00:00:11,000",
+        "Bien, gracias",
+    ],
+    input_b: &[
+        "1",
+        "00:00:01,000 --> 00:00:02,000",
+        "Hello",
+        "",
+        "2",
+        "00:00:05,000 --> 00:00:06,000",
+        "How are you?",
+        "",
+        "3",
+        "00:00:06,000 --> 00:00:07,000",
+        "[ADDITIONAL SCENE] Extra scene",
+        "",
+        "4",
+        "00:00:12,000 --> 00:00:13,000",
+        "Fine, thanks",
+    ],
+    expected: &[
+        "1",
+        "00:00:01,000 --> 00:00:02,000",
+        "Hola",
+        "",
+        "2",
+        "00:00:03,000 --> 00:00:04,000",
+        "¿Cómo estás?",
+        "",
+        "3",
+        "00:00:06,000 --> 00:00:07,000",
+        "[ADDITIONAL SCENE] Extra scene",
+        "",
+        "4",
+        "00:00:10,000 --> 00:00:11,000",
+        "Bien, gracias",
+    ],
+};
+
+static COLD_OPEN_CASE: TestCase = TestCase {
+    name: "inserts a cold open that only exists in B before the first cue",
+    input_a: &[
+        "1",
+        "00:00:01,000 --> 00:00:02,000",
+        "Hola",
+        "",
+        "2",
+        "00:00:03,000 --> 00:00:04,000",
+        "Adiós",
+    ],
+    input_b: &[
+        "1",
+        "00:00:01,000 --> 00:00:03,000",
+        "[ADDITIONAL SCENE] Prologue",
+        "",
+        "2",
+        "00:00:10,000 --> 00:00:11,000",
+        "Hello",
+        "",
+        "3",
+        "00:00:12,000 --> 00:00:13,000",
+        "Bye",
+    ],
+    expected: &[
+        "1",
+        "00:00:01,000 --> 00:00:03,000",
+        "[ADDITIONAL SCENE] Prologue",
+        "",
+        "2",
+        "00:00:10,000 --> 00:00:11,000",
+        "Hola",
+        "",
+        "3",
+        "00:00:12,000 --> 00:00:13,000",
+        "Adiós",
+    ],
+};
+
+static POST_CREDITS_CASE: TestCase = TestCase {
+    name: "inserts a post-credits scene and keeps later A cues after it",
+    input_a: &[
+        "1",
+        "00:00:01,000 --> 00:00:02,000",
+        "Hola",
+        "",
+        "2",
+        "00:00:03,000 --> 00:00:04,000",
+        "Avance",
+    ],
+    input_b: &[
+        "1",
+        "00:00:02,000 --> 00:00:03,000",
+        "Hello",
+        "",
+        "2",
+        "00:00:04,000 --> 00:00:06,000",
+        "[ADDITIONAL SCENE] Post-credits",
+    ],
+    expected: &[
+        "1",
+        "00:00:02,000 --> 00:00:03,000",
+        "Hola",
+        "",
+        "2",
+        "00:00:04,000 --> 00:00:06,000",
+        "[ADDITIONAL SCENE] Post-credits",
+        "",
+        "3",
+        "00:00:06,000 --> 00:00:07,000",
+        "Avance",
+    ],
+};
+
+static ALIGNMENT_CASE: TestCase = TestCase {
+    name: "aligns by timing and text and tags cues that only exist in B",
+    input_a: &[
+        "1",
+        "00:00:01,000 --> 00:00:02,000",
+        "Hello there",
+        "",
+        "2",
+        "00:00:04,000 --> 00:00:05,000",
+        "Fine, thanks",
+    ],
+    input_b: &[
+        "1",
+        "00:00:11,000 --> 00:00:12,000",
+        "Hello there",
+        "",
+        "2",
+        "00:00:12,500 --> 00:00:13,500",
+        "Nobody expected this",
+        "",
+        "3",
+        "00:00:14,000 --> 00:00:15,000",
+        "<i>Fine</i>, thanks",
+    ],
+    expected: &[
+        "1",
+        "00:00:11,000 --> 00:00:12,000",
+        "Hello there",
+        "",
+        "2",
+        "00:00:12,500 --> 00:00:13,500",
+        "[ADDITIONAL SCENE] Nobody expected this",
+        "",
+        "3",
+        "00:00:14,000 --> 00:00:15,000",
+        "Fine, thanks",
+    ],
+};
+
+static CORRUPT_TIMING_CASE: TestCase = TestCase {
+    name: "fails when input A has a broken timing line",
+    input_a: &["1", "00:00:01,000 -> 00:00:02,000", "Broken"],
+    input_b: &["1", "00:00:01,000 --> 00:00:02,000", "Ok"],
+    expected: &[],
+};
+
+fn run_test_case(test_case: &TestCase) -> ParseRes<()> {
+    run_test_case_with(test_case, SyncOptions::default())
+}
+
+fn run_test_case_with(test_case: &TestCase, options: SyncOptions) -> ParseRes<()> {
+    let input_a: Vec<String> = test_case.input_a.iter().map(|s| s.to_string()).collect();
+    let input_b: Vec<String> = test_case.input_b.iter().map(|s| s.to_string()).collect();
+    let expected: Vec<String> = test_case.expected.iter().map(|s| s.to_string()).collect();
+    let synchronizer = Synchronizer::new(options);
+    let result = synchronizer.run(&input_a, &input_b)?.output;
+    assert_eq!(result, expected, "Failed at case: {}", test_case.name);
+    Ok(())
+}
+
+#[test]
+fn test_sync_inserts_additional_scene() -> ParseRes<()> {
+    run_test_case(&START_LATER_CASE)
+}
+
+#[test]
+fn test_sync_without_scenes() -> ParseRes<()> {
+    run_test_case(&NO_SCENES_CASE)
+}
+
+#[test]
+fn test_sync_with_custom_options() -> ParseRes<()> {
+    let options = SyncOptions::new().with_anchor_line(1).with_shift_end(false);
+    run_test_case_with(&ANCHOR_CASE, options)
+}
+
+#[test]
+fn test_sync_places_unmatched_block() -> ParseRes<()> {
+    run_test_case(&UNMATCHED_BLOCK_CASE)
+}
+
+#[test]
+fn test_sync_inserts_cold_open() -> ParseRes<()> {
+    run_test_case(&COLD_OPEN_CASE)
+}
+
+#[test]
+fn test_sync_inserts_post_credits_scene() -> ParseRes<()> {
+    run_test_case(&POST_CREDITS_CASE)
+}
+
+#[test]
+fn test_alignment_mode_inserts_untagged_scene() -> ParseRes<()> {
+    let options = SyncOptions::new().with_mode(SyncMode::TimingAndText);
+    run_test_case_with(&ALIGNMENT_CASE, options)
+}
+
+#[test]
+fn test_sync_reports_block_placement() -> ParseRes<()> {
+    let input_a: Vec<String> = START_LATER_CASE
+        .input_a
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    let input_b: Vec<String> = START_LATER_CASE
+        .input_b
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    let result = Synchronizer::new(SyncOptions::default()).run(&input_a, &input_b)?;
+    let expected = vec![
+        PlacedBlock::new(
+            BlockPlacement::Inserted,
+            Timestamp::from_millis(6500),
+            Timestamp::from_millis(9500),
+            2,
+        )
+        .with_after_line(Some(1))
+        .with_confidence(1.0),
+    ];
+    assert_eq!(result.report.blocks, expected);
+    assert_eq!(result.report.initial_delta, Timestamp::from_millis(-100));
+    let changes = vec![DeltaChange {
+        line: 1,
+        delta: Timestamp::from_millis(4500),
+    }];
+    assert_eq!(result.report.delta_changes, changes);
+    assert!(result.report.overlaps.is_empty());
+
+    let input_a: Vec<String> = UNMATCHED_BLOCK_CASE
+        .input_a
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    let input_b: Vec<String> = UNMATCHED_BLOCK_CASE
+        .input_b
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    let result = Synchronizer::new(SyncOptions::default()).run(&input_a, &input_b)?;
+    assert_eq!(result.report.count(BlockPlacement::Fallback), 1);
+    assert_eq!(result.report.blocks[0].start, Timestamp::from_millis(6000));
+    Ok(())
+}
+
+#[test]
+fn test_error_on_corrupt_timing() {
+    let result = run_test_case(&CORRUPT_TIMING_CASE);
+    match result {
+        Err(ParserError::TimingLine { found }) => assert_eq!(found, "1"),
+        _ => panic!("Expected TimingLine, got {:?}", result),
+    }
+}
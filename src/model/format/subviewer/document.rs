@@ -0,0 +1,7 @@
+use crate::model::format::subviewer::line_subviewer::SubViewerLine;
+
+#[derive(Debug, Clone)]
+pub struct SubViewerDocument {
+    pub header: Vec<String>,
+    pub cues: Vec<SubViewerLine>,
+}
@@ -0,0 +1,136 @@
+use std::result::Result;
+
+use crate::model::{
+    format::subviewer::{document::SubViewerDocument, line_subviewer::SubViewerLine},
+    parse_error::ParserError,
+    timestamp::Timestamp,
+};
+
+pub struct SubViewerParser;
+
+impl SubViewerParser {
+    const BOM: char = '\u{feff}';
+    const HEADER_PREFIX: char = '[';
+    const TIMING_SEPARATOR: char = ',';
+    const LINE_SEPARATOR: &str = "[br]";
+    const LINE_BREAK: &str = "\n";
+    const DEFAULT_HEADER: [&str; 12] = [
+        "[INFORMATION]",
+        "[TITLE]",
+        "[AUTHOR]",
+        "[SOURCE]",
+        "[PRG]",
+        "[FILEPATH]",
+        "[DELAY]0",
+        "[CD TRACK]0",
+        "[COMMENT]",
+        "[END INFORMATION]",
+        "[SUBTITLE]",
+        "[COLF]&HFFFFFF,[STYLE]no,[SIZE]18,[FONT]Arial",
+    ];
+
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn split_blocks<'a>(&self, lines: &'a [String]) -> Vec<(usize, Vec<&'a str>)> {
+        let mut blocks = Vec::new();
+        let mut current = Vec::new();
+        let mut first_line = 0;
+        for (idx, line) in lines.iter().enumerate() {
+            let line = line.trim_start_matches(Self::BOM).trim_end();
+            match line.is_empty() {
+                true if !current.is_empty() => {
+                    blocks.push((first_line, std::mem::take(&mut current)))
+                }
+                true => {}
+                false if current.is_empty() => {
+                    first_line = idx + 1;
+                    current.push(line);
+                }
+                false => current.push(line),
+            }
+        }
+        if !current.is_empty() {
+            blocks.push((first_line, current));
+        }
+        blocks
+    }
+
+    fn parse_time(&self, t: &str) -> Result<Timestamp, ParserError> {
+        Timestamp::parse_srt(t).ok_or_else(|| ParserError::invalid_timestamp(t))
+    }
+
+    fn parse_timing(&self, line: &str) -> Result<(Timestamp, Timestamp), ParserError> {
+        let (start, end) = line
+            .split_once(Self::TIMING_SEPARATOR)
+            .ok_or_else(|| ParserError::invalid_timestamp(line))?;
+        Ok((self.parse_time(start.trim())?, self.parse_time(end.trim())?))
+    }
+
+    fn parse_block(
+        &self,
+        first_line: usize,
+        block: &[&str],
+        doc: &mut SubViewerDocument,
+    ) -> Result<(), ParserError> {
+        let header_len = match doc.cues.is_empty() {
+            true => block
+                .iter()
+                .take_while(|line| line.starts_with(Self::HEADER_PREFIX))
+                .count(),
+            false => 0,
+        };
+        doc.header
+            .extend(block[..header_len].iter().map(|line| line.to_string()));
+        let Some(timing) = block.get(header_len) else {
+            return Ok(());
+        };
+        let (start, end) = self
+            .parse_timing(timing)
+            .map_err(|e| e.at_line(first_line + header_len, timing))?;
+        let text = block[header_len + 1..]
+            .join(Self::LINE_BREAK)
+            .replace(Self::LINE_SEPARATOR, Self::LINE_BREAK);
+        doc.cues.push(SubViewerLine { start, end, text });
+        Ok(())
+    }
+
+    pub fn parse_lines(&self, lines: &[String]) -> Result<SubViewerDocument, ParserError> {
+        let mut doc = SubViewerDocument {
+            header: Vec::new(),
+            cues: Vec::new(),
+        };
+        for (first_line, block) in self.split_blocks(lines) {
+            self.parse_block(first_line, &block, &mut doc)?;
+        }
+        Ok(doc)
+    }
+
+    pub fn to_lines(&self, doc: &SubViewerDocument) -> Vec<String> {
+        let mut out: Vec<String> = match doc.header.is_empty() {
+            true => Self::DEFAULT_HEADER.iter().map(|s| s.to_string()).collect(),
+            false => doc.header.clone(),
+        };
+        for cue in &doc.cues {
+            out.push(String::new());
+            out.push(format!(
+                "{}{}{}",
+                cue.start.to_subviewer(),
+                Self::TIMING_SEPARATOR,
+                cue.end.to_subviewer()
+            ));
+            out.push(cue.text.replace(Self::LINE_BREAK, Self::LINE_SEPARATOR));
+        }
+        out
+    }
+}
+
+impl Default for SubViewerParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests;
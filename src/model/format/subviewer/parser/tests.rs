@@ -0,0 +1,135 @@
+use crate::model::parse_error::ParseRes;
+
+use super::*;
+
+struct TestCase {
+    name: &'static str,
+    input: &'static [&'static str],
+    header: &'static [&'static str],
+    expected: &'static [(i64, i64, &'static str)],
+}
+
+static HEADER_CASE: TestCase = TestCase {
+    name: "splits the information header from the cues",
+    input: &[
+        "\u{feff}[INFORMATION]",
+        "[TITLE]Episode 1",
+        "[END INFORMATION]",
+        "[SUBTITLE]",
+        "00:00:01.50,00:00:02.00",
+        "First[br]line",
+        "",
+        "00:01:02.35,00:01:04.00",
+        "Second",
+        "continued",
+    ],
+    header: &[
+        "[INFORMATION]",
+        "[TITLE]Episode 1",
+        "[END INFORMATION]",
+        "[SUBTITLE]",
+    ],
+    expected: &[
+        (1_500, 2_000, "First\nline"),
+        (62_350, 64_000, "Second\ncontinued"),
+    ],
+};
+
+static NO_HEADER_CASE: TestCase = TestCase {
+    name: "reads cues without a header",
+    input: &["", "00:00:01.00 , 00:00:02.00", "Plain", "", ""],
+    header: &[],
+    expected: &[(1_000, 2_000, "Plain")],
+};
+
+fn to_lines(input: &[&str]) -> Vec<String> {
+    input.iter().map(|s| s.to_string()).collect()
+}
+
+fn run_test_case(test_case: &TestCase) -> ParseRes<()> {
+    let doc = SubViewerParser::new().parse_lines(&to_lines(test_case.input))?;
+    let cues: Vec<(i64, i64, &str)> = doc
+        .cues
+        .iter()
+        .map(|cue| (cue.start.millis(), cue.end.millis(), cue.text.as_str()))
+        .collect();
+    assert_eq!(
+        doc.header, test_case.header,
+        "Failed at case: {}",
+        test_case.name
+    );
+    assert_eq!(
+        cues, test_case.expected,
+        "Failed at case: {}",
+        test_case.name
+    );
+    Ok(())
+}
+
+#[test]
+fn test_header() -> ParseRes<()> {
+    run_test_case(&HEADER_CASE)
+}
+
+#[test]
+fn test_no_header() -> ParseRes<()> {
+    run_test_case(&NO_HEADER_CASE)
+}
+
+#[test]
+fn test_round_trip() -> ParseRes<()> {
+    let parser = SubViewerParser::new();
+    let input = to_lines(&[
+        "[INFORMATION]",
+        "[END INFORMATION]",
+        "",
+        "00:00:01.50,00:00:02.00",
+        "One[br]Two",
+    ]);
+    let doc = parser.parse_lines(&input)?;
+    assert_eq!(parser.to_lines(&doc), input);
+    Ok(())
+}
+
+#[test]
+fn test_default_header() -> ParseRes<()> {
+    let parser = SubViewerParser::new();
+    let doc = parser.parse_lines(&to_lines(&["00:00:01.00,00:00:02.00", "Hi"]))?;
+    let lines = parser.to_lines(&doc);
+    assert_eq!(lines.first().map(String::as_str), Some("[INFORMATION]"));
+    assert_eq!(lines[lines.len() - 2..], ["00:00:01.00,00:00:02.00", "Hi"]);
+    Ok(())
+}
+
+#[test]
+fn test_error_invalid_time() {
+    let input = to_lines(&[
+        "[SUBTITLE]",
+        "00:00:01.00,00:00:02.00",
+        "Fine",
+        "",
+        "00:0x:03.00,00:00:04.00",
+        "Bad",
+    ]);
+    let result = SubViewerParser::new().parse_lines(&input);
+    match result {
+        Err(ParserError::InvalidTimestamp { location, found }) => {
+            assert_eq!(found, "00:0x:03.00");
+            assert_eq!(location.line, 5);
+        }
+        _ => panic!("Expected InvalidTimestamp, got {:?}", result),
+    }
+}
+
+#[test]
+fn test_error_missing_separator() {
+    let input = to_lines(&["00:00:01.00 00:00:02.00", "Bad"]);
+    let result = SubViewerParser::new().parse_lines(&input);
+    match result {
+        Err(ParserError::InvalidTimestamp { location, found }) => {
+            assert_eq!(found, "00:00:01.00 00:00:02.00");
+            assert_eq!(location.line, 1);
+        }
+        _ => panic!("Expected InvalidTimestamp, got {:?}", result),
+    }
+}
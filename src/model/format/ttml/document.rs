@@ -0,0 +1,66 @@
+use crate::model::timestamp::Timestamp;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TtmlProfile {
+    Dfxp,
+    EbuTt,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TtmlTiming {
+    Clock,
+    Frames(f64),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TtmlElement {
+    pub id: String,
+    pub attributes: Vec<(String, String)>,
+}
+
+impl TtmlElement {
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TtmlParagraph {
+    pub begin: Timestamp,
+    pub end: Timestamp,
+    pub region: Option<String>,
+    pub style: Option<String>,
+    pub text: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct TtmlDocument {
+    pub profile: TtmlProfile,
+    pub timing: TtmlTiming,
+    pub styles: Vec<TtmlElement>,
+    pub regions: Vec<TtmlElement>,
+    pub paragraphs: Vec<TtmlParagraph>,
+}
+
+impl TtmlDocument {
+    pub fn new(profile: TtmlProfile, timing: TtmlTiming) -> Self {
+        Self {
+            profile,
+            timing,
+            styles: Vec::new(),
+            regions: Vec::new(),
+            paragraphs: Vec::new(),
+        }
+    }
+
+    pub fn style(&self, id: &str) -> Option<&TtmlElement> {
+        self.styles.iter().find(|style| style.id == id)
+    }
+
+    pub fn region(&self, id: &str) -> Option<&TtmlElement> {
+        self.regions.iter().find(|region| region.id == id)
+    }
+}
@@ -0,0 +1,3 @@
+pub mod document;
+pub mod parser;
+pub mod xml;
@@ -0,0 +1,386 @@
+use std::result::Result;
+
+use crate::model::{
+    format::ttml::{
+        document::{TtmlDocument, TtmlElement, TtmlParagraph, TtmlProfile, TtmlTiming},
+        xml::{XmlReader, XmlToken},
+    },
+    parse_error::ParserError,
+    timestamp::Timestamp,
+};
+
+pub struct TtmlParser {
+    reader: XmlReader,
+}
+
+#[derive(Default)]
+struct Scope {
+    region: Option<String>,
+    style: Option<String>,
+}
+
+impl TtmlParser {
+    const XML_DECLARATION: &str = r#"<?xml version="1.0" encoding="UTF-8"?>"#;
+    const TTML_NAMESPACE: &str = "http://www.w3.org/ns/ttml";
+    const EBU_NAMESPACE: &str = "urn:ebu:tt";
+    const STYLING_PREFIX: &str = "tts:";
+    const CELL_RESOLUTION: &str = "32 15";
+    const NTSC_MULTIPLIER: &str = "1000 1001";
+    const NTSC_FACTOR: f64 = 1.001;
+    const INDENT: &str = "  ";
+    const LINE_BREAK: &str = "\n";
+    const BREAK_ELEMENT: &str = "<br/>";
+    const PARAGRAPH_ID_PREFIX: &str = "sub";
+
+    pub fn new() -> Self {
+        Self {
+            reader: XmlReader::new(),
+        }
+    }
+
+    fn styling(attributes: &[(String, String)]) -> Vec<(String, String)> {
+        attributes
+            .iter()
+            .filter_map(|(key, value)| {
+                key.strip_prefix(Self::STYLING_PREFIX)
+                    .map(|name| (name.to_string(), value.clone()))
+            })
+            .collect()
+    }
+
+    fn frame_rate(attributes: &[(String, String)]) -> Option<f64> {
+        let nominal: f64 = XmlReader::attribute(attributes, "frameRate")?
+            .parse()
+            .ok()?;
+        let multiplier = XmlReader::attribute(attributes, "frameRateMultiplier")
+            .and_then(|value| value.split_once(' '))
+            .and_then(|(n, d)| Some(n.parse::<f64>().ok()? / d.parse::<f64>().ok()?))
+            .unwrap_or(1.0);
+        Some(nominal * multiplier).filter(|fps| *fps > 0.0)
+    }
+
+    fn read_root(&self, attributes: &[(String, String)], doc: &mut TtmlDocument) -> f64 {
+        let is_ebu = attributes
+            .iter()
+            .any(|(_, value)| value.starts_with(Self::EBU_NAMESPACE));
+        doc.profile = match is_ebu {
+            true => TtmlProfile::EbuTt,
+            false => TtmlProfile::Dfxp,
+        };
+        let fps = Self::frame_rate(attributes);
+        doc.timing = fps.map_or(TtmlTiming::Clock, TtmlTiming::Frames);
+        XmlReader::attribute(attributes, "tickRate")
+            .and_then(|value| value.parse().ok())
+            .or(fps)
+            .unwrap_or(1.0)
+    }
+
+    fn parse_time(
+        value: &str,
+        doc: &TtmlDocument,
+        tick_rate: f64,
+    ) -> Result<Timestamp, ParserError> {
+        let fps = match doc.timing {
+            TtmlTiming::Frames(fps) => fps,
+            TtmlTiming::Clock => Timestamp::FILM_FRAME_RATE,
+        };
+        Timestamp::parse_ttml(value, fps, tick_rate)
+            .ok_or_else(|| ParserError::invalid_timestamp(value))
+    }
+
+    fn timing(
+        attributes: &[(String, String)],
+        doc: &TtmlDocument,
+        tick_rate: f64,
+    ) -> Result<(Timestamp, Timestamp), ParserError> {
+        let begin = XmlReader::attribute(attributes, "begin").unwrap_or_default();
+        let begin = Self::parse_time(begin, doc, tick_rate)?;
+        let end = match (
+            XmlReader::attribute(attributes, "end"),
+            XmlReader::attribute(attributes, "dur"),
+        ) {
+            (Some(end), _) => Self::parse_time(end, doc, tick_rate)?,
+            (None, Some(dur)) => begin + Self::parse_time(dur, doc, tick_rate)?,
+            (None, None) => return Err(ParserError::invalid_timestamp("")),
+        };
+        Ok((begin, end))
+    }
+
+    fn collapse(text: &str) -> String {
+        let mut out = String::new();
+        let mut space = false;
+        for c in text.chars() {
+            match c.is_whitespace() {
+                true if !space => {
+                    out.push(' ');
+                    space = true;
+                }
+                true => {}
+                false => {
+                    out.push(c);
+                    space = false;
+                }
+            }
+        }
+        out
+    }
+
+    fn span(attributes: &[(String, String)], doc: &TtmlDocument) -> String {
+        let referenced = XmlReader::attribute(attributes, "style")
+            .and_then(|id| doc.style(id))
+            .map(|style| style.attributes.clone())
+            .unwrap_or_default();
+        let rendered: Vec<String> = referenced
+            .into_iter()
+            .chain(Self::styling(attributes))
+            .map(|(key, value)| format!(" {}=\"{}\"", key, value))
+            .collect();
+        format!("<span{}>", rendered.concat())
+    }
+
+    fn read_paragraph<'a>(
+        tokens: &mut impl Iterator<Item = &'a XmlToken>,
+        doc: &TtmlDocument,
+    ) -> String {
+        let mut text = String::new();
+        for token in tokens.by_ref() {
+            match token {
+                XmlToken::Text(value) => text.push_str(&Self::collapse(value)),
+                XmlToken::Open { name, .. } if name == "br" => text.push_str(Self::LINE_BREAK),
+                XmlToken::Open {
+                    name,
+                    attributes,
+                    self_closing: false,
+                } if name == "span" => text.push_str(&Self::span(attributes, doc)),
+                XmlToken::Close(name) if name == "span" => text.push_str("</span>"),
+                XmlToken::Close(name) if name == "p" => break,
+                _ => {}
+            }
+        }
+        text.split(Self::LINE_BREAK)
+            .map(str::trim)
+            .collect::<Vec<&str>>()
+            .join(Self::LINE_BREAK)
+    }
+
+    fn element(attributes: &[(String, String)]) -> TtmlElement {
+        TtmlElement {
+            id: XmlReader::attribute(attributes, "id")
+                .unwrap_or_default()
+                .to_string(),
+            attributes: Self::styling(attributes),
+        }
+    }
+
+    pub fn parse_lines(&self, lines: &[String]) -> Result<TtmlDocument, ParserError> {
+        let tokens = self.reader.read(&lines.join(Self::LINE_BREAK));
+        let mut doc = TtmlDocument::new(TtmlProfile::Dfxp, TtmlTiming::Clock);
+        let mut tick_rate = 1.0;
+        let mut scopes: Vec<Scope> = Vec::new();
+        let mut open_region: Option<usize> = None;
+        let mut tokens = tokens.iter();
+        while let Some(token) = tokens.next() {
+            match token {
+                XmlToken::Open {
+                    name, attributes, ..
+                } if name == "tt" => tick_rate = self.read_root(attributes, &mut doc),
+                XmlToken::Open {
+                    name,
+                    attributes,
+                    self_closing,
+                } if name == "region" => {
+                    doc.regions.push(Self::element(attributes));
+                    open_region = (!self_closing).then_some(doc.regions.len() - 1);
+                }
+                XmlToken::Close(name) if name == "region" => open_region = None,
+                XmlToken::Open {
+                    name, attributes, ..
+                } if name == "style" => match (open_region, Self::element(attributes)) {
+                    (Some(idx), style) if style.id.is_empty() => {
+                        doc.regions[idx].attributes.extend(style.attributes)
+                    }
+                    (_, style) => doc.styles.push(style),
+                },
+                XmlToken::Open {
+                    name,
+                    attributes,
+                    self_closing: false,
+                } if name == "body" || name == "div" => {
+                    let parent = scopes.last();
+                    scopes.push(Scope {
+                        region: XmlReader::attribute(attributes, "region")
+                            .map(String::from)
+                            .or(parent.and_then(|s| s.region.clone())),
+                        style: XmlReader::attribute(attributes, "style")
+                            .map(String::from)
+                            .or(parent.and_then(|s| s.style.clone())),
+                    });
+                }
+                XmlToken::Close(name) if name == "body" || name == "div" => {
+                    scopes.pop();
+                }
+                XmlToken::Open {
+                    name,
+                    attributes,
+                    self_closing: false,
+                } if name == "p" => {
+                    let (begin, end) = Self::timing(attributes, &doc, tick_rate)?;
+                    let scope = scopes.last();
+                    let paragraph = TtmlParagraph {
+                        begin,
+                        end,
+                        region: XmlReader::attribute(attributes, "region")
+                            .map(String::from)
+                            .or(scope.and_then(|s| s.region.clone())),
+                        style: XmlReader::attribute(attributes, "style")
+                            .map(String::from)
+                            .or(scope.and_then(|s| s.style.clone())),
+                        text: Self::read_paragraph(&mut tokens, &doc),
+                    };
+                    doc.paragraphs.push(paragraph);
+                }
+                _ => {}
+            }
+        }
+        Ok(doc)
+    }
+
+    fn time(&self, timestamp: Timestamp, timing: TtmlTiming) -> String {
+        match timing {
+            TtmlTiming::Clock => timestamp.to_vtt(),
+            TtmlTiming::Frames(fps) => timestamp.to_smpte(fps),
+        }
+    }
+
+    fn root_attributes(&self, doc: &TtmlDocument) -> Vec<String> {
+        let mut attributes = vec![
+            format!("xmlns=\"{}\"", Self::TTML_NAMESPACE),
+            format!("xmlns:tts=\"{}#styling\"", Self::TTML_NAMESPACE),
+            format!("xmlns:ttp=\"{}#parameter\"", Self::TTML_NAMESPACE),
+        ];
+        if doc.profile == TtmlProfile::EbuTt {
+            attributes.push(format!("xmlns:ttm=\"{}#metadata\"", Self::TTML_NAMESPACE));
+            attributes.push(format!("xmlns:ebuttm=\"{}:metadata\"", Self::EBU_NAMESPACE));
+        }
+        attributes.push("xml:lang=\"\"".to_string());
+        attributes.push("ttp:timeBase=\"media\"".to_string());
+        attributes.push(format!("ttp:cellResolution=\"{}\"", Self::CELL_RESOLUTION));
+        if let TtmlTiming::Frames(fps) = doc.timing {
+            match (fps - fps.round()).abs() < 0.001 {
+                true => attributes.push(format!("ttp:frameRate=\"{}\"", fps.round())),
+                false => {
+                    let nominal = (fps * Self::NTSC_FACTOR).round();
+                    attributes.push(format!("ttp:frameRate=\"{}\"", nominal));
+                    attributes.push(format!(
+                        "ttp:frameRateMultiplier=\"{}\"",
+                        Self::NTSC_MULTIPLIER
+                    ));
+                }
+            }
+        }
+        attributes
+    }
+
+    fn element_line(&self, depth: usize, name: &str, element: &TtmlElement) -> String {
+        let attributes: Vec<String> = element
+            .attributes
+            .iter()
+            .map(|(key, value)| {
+                format!(
+                    " {}{}=\"{}\"",
+                    Self::STYLING_PREFIX,
+                    key,
+                    XmlReader::escape(value)
+                )
+            })
+            .collect();
+        format!(
+            "{}<{} xml:id=\"{}\"{}/>",
+            Self::INDENT.repeat(depth),
+            name,
+            element.id,
+            attributes.concat()
+        )
+    }
+
+    fn push_head(&self, out: &mut Vec<String>, doc: &TtmlDocument) {
+        let indent = |depth: usize, line: &str| format!("{}{}", Self::INDENT.repeat(depth), line);
+        out.push(indent(1, "<head>"));
+        if doc.profile == TtmlProfile::EbuTt {
+            out.push(indent(2, "<metadata>"));
+            out.push(indent(3, "<ebuttm:documentMetadata>"));
+            out.push(indent(
+                4,
+                "<ebuttm:documentEbuttVersion>v1.0</ebuttm:documentEbuttVersion>",
+            ));
+            out.push(indent(3, "</ebuttm:documentMetadata>"));
+            out.push(indent(2, "</metadata>"));
+        }
+        if !doc.styles.is_empty() {
+            out.push(indent(2, "<styling>"));
+            out.extend(doc.styles.iter().map(|s| self.element_line(3, "style", s)));
+            out.push(indent(2, "</styling>"));
+        }
+        if !doc.regions.is_empty() {
+            out.push(indent(2, "<layout>"));
+            out.extend(
+                doc.regions
+                    .iter()
+                    .map(|r| self.element_line(3, "region", r)),
+            );
+            out.push(indent(2, "</layout>"));
+        }
+        out.push(indent(1, "</head>"));
+    }
+
+    fn paragraph_line(&self, idx: usize, paragraph: &TtmlParagraph, timing: TtmlTiming) -> String {
+        let reference = |name: &str, value: &Option<String>| {
+            value
+                .as_ref()
+                .map(|id| format!(" {}=\"{}\"", name, id))
+                .unwrap_or_default()
+        };
+        format!(
+            "{}<p xml:id=\"{}{}\" begin=\"{}\" end=\"{}\"{}{}>{}</p>",
+            Self::INDENT.repeat(3),
+            Self::PARAGRAPH_ID_PREFIX,
+            idx + 1,
+            self.time(paragraph.begin, timing),
+            self.time(paragraph.end, timing),
+            reference("region", &paragraph.region),
+            reference("style", &paragraph.style),
+            paragraph
+                .text
+                .replace(Self::LINE_BREAK, Self::BREAK_ELEMENT)
+        )
+    }
+
+    pub fn to_lines(&self, doc: &TtmlDocument) -> Vec<String> {
+        let mut out = vec![
+            Self::XML_DECLARATION.to_string(),
+            format!("<tt {}>", self.root_attributes(doc).join(" ")),
+        ];
+        self.push_head(&mut out, doc);
+        out.push(format!("{}<body>", Self::INDENT));
+        out.push(format!("{}<div>", Self::INDENT.repeat(2)));
+        out.extend(
+            doc.paragraphs
+                .iter()
+                .enumerate()
+                .map(|(idx, p)| self.paragraph_line(idx, p, doc.timing)),
+        );
+        out.push(format!("{}</div>", Self::INDENT.repeat(2)));
+        out.push(format!("{}</body>", Self::INDENT));
+        out.push("</tt>".to_string());
+        out
+    }
+}
+
+impl Default for TtmlParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,201 @@
+use crate::model::parse_error::ParseRes;
+
+use super::*;
+
+struct TestCase {
+    name: &'static str,
+    root: &'static str,
+    begin: &'static str,
+    end: &'static str,
+    expected: Option<(i64, i64)>,
+}
+
+static TIMING_CASES: &[TestCase] = &[
+    TestCase {
+        name: "clock times",
+        root: "",
+        begin: "00:00:01.5",
+        end: "00:01:02",
+        expected: Some((1_500, 62_000)),
+    },
+    TestCase {
+        name: "hour, minute, second and millisecond offsets",
+        root: "",
+        begin: "0.5m",
+        end: "1h",
+        expected: Some((30_000, 3_600_000)),
+    },
+    TestCase {
+        name: "second and millisecond offsets",
+        root: "",
+        begin: "1.5s",
+        end: "2500ms",
+        expected: Some((1_500, 2_500)),
+    },
+    TestCase {
+        name: "frame offsets use the document frame rate",
+        root: r#"ttp:frameRate="25""#,
+        begin: "50f",
+        end: "75f",
+        expected: Some((2_000, 3_000)),
+    },
+    TestCase {
+        name: "SMPTE frames",
+        root: r#"ttp:frameRate="25""#,
+        begin: "00:00:01:12",
+        end: "00:00:02:00",
+        expected: Some((1_480, 2_000)),
+    },
+    TestCase {
+        name: "SMPTE frames with an NTSC multiplier",
+        root: r#"ttp:frameRate="30" ttp:frameRateMultiplier="1000 1001""#,
+        begin: "00:00:01:12",
+        end: "00:00:02:00",
+        expected: Some((1_400, 2_000)),
+    },
+    TestCase {
+        name: "ticks use the tick rate",
+        root: r#"ttp:tickRate="10000000""#,
+        begin: "15000000t",
+        end: "20000000t",
+        expected: Some((1_500, 2_000)),
+    },
+    TestCase {
+        name: "ticks fall back to the frame rate",
+        root: r#"ttp:frameRate="25""#,
+        begin: "25t",
+        end: "50t",
+        expected: Some((1_000, 2_000)),
+    },
+    TestCase {
+        name: "ticks without any rate count seconds",
+        root: "",
+        begin: "1t",
+        end: "2t",
+        expected: Some((1_000, 2_000)),
+    },
+    TestCase {
+        name: "unknown unit",
+        root: "",
+        begin: "1s",
+        end: "3x",
+        expected: None,
+    },
+    TestCase {
+        name: "negative offset",
+        root: "",
+        begin: "-1s",
+        end: "2s",
+        expected: None,
+    },
+];
+
+fn document(root: &str, body: &[&str]) -> Vec<String> {
+    let mut lines = vec![format!(
+        r#"<tt xmlns="http://www.w3.org/ns/ttml" {}>"#,
+        root
+    )];
+    lines.push("<body><div>".to_string());
+    lines.extend(body.iter().map(|s| s.to_string()));
+    lines.push("</div></body></tt>".to_string());
+    lines
+}
+
+fn run_timing_case(test_case: &TestCase) {
+    let paragraph = format!(
+        r#"<p begin="{}" end="{}">Hi</p>"#,
+        test_case.begin, test_case.end
+    );
+    let result = TtmlParser::new().parse_lines(&document(test_case.root, &[&paragraph]));
+    let times = result.ok().map(|doc| {
+        let p = &doc.paragraphs[0];
+        (p.begin.millis(), p.end.millis())
+    });
+    assert_eq!(
+        times, test_case.expected,
+        "Failed at case: {}",
+        test_case.name
+    );
+}
+
+#[test]
+fn test_timing() {
+    for test_case in TIMING_CASES {
+        run_timing_case(test_case);
+    }
+}
+
+#[test]
+fn test_duration() -> ParseRes<()> {
+    let doc =
+        TtmlParser::new().parse_lines(&document("", &[r#"<p begin="1s" dur="1.5s">Hi</p>"#]))?;
+    assert_eq!(doc.paragraphs[0].end.millis(), 2_500);
+    Ok(())
+}
+
+#[test]
+fn test_error_unknown_unit() {
+    let result =
+        TtmlParser::new().parse_lines(&document("", &[r#"<p begin="2y" end="3s">Hi</p>"#]));
+    match result {
+        Err(ParserError::InvalidTimestamp { found, .. }) => assert_eq!(found, "2y"),
+        _ => panic!(
+            "Expected InvalidTimestamp, got {:?}",
+            result.map(|doc| doc.paragraphs.len())
+        ),
+    }
+}
+
+#[test]
+fn test_error_missing_end() {
+    let result = TtmlParser::new().parse_lines(&document("", &[r#"<p begin="1s">Hi</p>"#]));
+    assert!(matches!(result, Err(ParserError::InvalidTimestamp { .. })));
+}
+
+#[test]
+fn test_frame_timing() -> ParseRes<()> {
+    let parser = TtmlParser::new();
+    let clock = parser.parse_lines(&document("", &[]))?;
+    let frames = parser.parse_lines(&document(
+        r#"ttp:frameRate="24" ttp:frameRateMultiplier="1000 1001""#,
+        &[],
+    ))?;
+    assert_eq!(clock.timing, TtmlTiming::Clock);
+    match frames.timing {
+        TtmlTiming::Frames(fps) => assert!((fps - Timestamp::FILM_FRAME_RATE).abs() < 0.001),
+        TtmlTiming::Clock => panic!("Expected frame timing"),
+    }
+    Ok(())
+}
+
+#[test]
+fn test_styles_regions_and_text() -> ParseRes<()> {
+    let lines = vec![
+        r#"<tt xmlns="http://www.w3.org/ns/ttml" xmlns:tts="http://www.w3.org/ns/ttml#styling">"#
+            .to_string(),
+        "<head><styling>".to_string(),
+        r#"<style xml:id="s1" tts:color="yellow"/>"#.to_string(),
+        "</styling><layout>".to_string(),
+        r#"<region xml:id="top"><style tts:displayAlign="before"/></region>"#.to_string(),
+        "</layout></head>".to_string(),
+        r#"<body region="top"><div style="s1">"#.to_string(),
+        r#"<p begin="1s" end="2s">  One <span tts:fontStyle="italic">two</span><br/>  three </p>"#
+            .to_string(),
+        "</div></body></tt>".to_string(),
+    ];
+    let doc = TtmlParser::new().parse_lines(&lines)?;
+    assert_eq!(doc.profile, TtmlProfile::Dfxp);
+    assert_eq!(doc.style("s1").and_then(|s| s.get("color")), Some("yellow"));
+    assert_eq!(
+        doc.region("top").and_then(|r| r.get("displayAlign")),
+        Some("before")
+    );
+    let paragraph = &doc.paragraphs[0];
+    assert_eq!(paragraph.region.as_deref(), Some("top"));
+    assert_eq!(paragraph.style.as_deref(), Some("s1"));
+    assert_eq!(
+        paragraph.text,
+        "One <span fontStyle=\"italic\">two</span>\nthree"
+    );
+    Ok(())
+}
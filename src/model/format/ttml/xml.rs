@@ -0,0 +1,123 @@
+use regex::Regex;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum XmlToken {
+    Open {
+        name: String,
+        attributes: Vec<(String, String)>,
+        self_closing: bool,
+    },
+    Close(String),
+    Text(String),
+}
+
+pub struct XmlReader {
+    attribute_re: Regex,
+}
+
+impl XmlReader {
+    const COMMENT_START: &str = "<!--";
+    const COMMENT_END: &str = "-->";
+    const CDATA_START: &str = "<![CDATA[";
+    const CDATA_END: &str = "]]>";
+    const ATTRIBUTE_RE: &str = r#"([\w:.-]+)\s*=\s*(?:"([^"]*)"|'([^']*)')"#;
+
+    pub fn new() -> Self {
+        Self {
+            attribute_re: Regex::new(Self::ATTRIBUTE_RE).unwrap(),
+        }
+    }
+
+    pub fn local_name(name: &str) -> &str {
+        name.rsplit(':').next().unwrap_or(name)
+    }
+
+    pub fn attribute<'a>(attributes: &'a [(String, String)], name: &str) -> Option<&'a str> {
+        attributes
+            .iter()
+            .find(|(key, _)| Self::local_name(key) == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn escape(text: &str) -> String {
+        text.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+    }
+
+    fn attributes(&self, body: &str) -> Vec<(String, String)> {
+        self.attribute_re
+            .captures_iter(body)
+            .map(|caps| {
+                let value = caps.get(2).or(caps.get(3)).map_or("", |m| m.as_str());
+                (caps[1].to_string(), value.to_string())
+            })
+            .collect()
+    }
+
+    fn element(&self, body: &str) -> Option<XmlToken> {
+        if let Some(name) = body.strip_prefix('/') {
+            return Some(XmlToken::Close(Self::local_name(name.trim()).to_string()));
+        }
+        if body.starts_with(['?', '!']) {
+            return None;
+        }
+        let self_closing = body.ends_with('/');
+        let body = body.trim_end_matches('/');
+        let name = body.split_whitespace().next().unwrap_or_default();
+        Some(XmlToken::Open {
+            name: Self::local_name(name).to_string(),
+            attributes: self.attributes(&body[name.len()..]),
+            self_closing,
+        })
+    }
+
+    fn skip_block<'a>(rest: &'a str, start: &str, end: &str) -> Option<(&'a str, &'a str)> {
+        let body = rest.strip_prefix(start)?;
+        let close = body.find(end)?;
+        Some((&body[..close], &body[close + end.len()..]))
+    }
+
+    pub fn read(&self, source: &str) -> Vec<XmlToken> {
+        let mut tokens = Vec::new();
+        let mut rest = source;
+        while let Some(open) = rest.find('<') {
+            if open > 0 {
+                tokens.push(XmlToken::Text(rest[..open].to_string()));
+            }
+            rest = &rest[open..];
+            if let Some((_, after)) = Self::skip_block(rest, Self::COMMENT_START, Self::COMMENT_END)
+            {
+                rest = after;
+                continue;
+            }
+            if let Some((data, after)) = Self::skip_block(rest, Self::CDATA_START, Self::CDATA_END)
+            {
+                tokens.push(XmlToken::Text(Self::escape(data)));
+                rest = after;
+                continue;
+            }
+            let Some(close) = rest.find('>') else {
+                break;
+            };
+            if let Some(token) = self.element(rest[1..close].trim()) {
+                tokens.push(token);
+            }
+            rest = &rest[close + 1..];
+        }
+        if !rest.is_empty() {
+            tokens.push(XmlToken::Text(rest.to_string()));
+        }
+        tokens
+    }
+}
+
+impl Default for XmlReader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,101 @@
+use super::*;
+
+struct TestCase {
+    name: &'static str,
+    input: &'static str,
+    expected: Vec<XmlToken>,
+}
+
+fn open(name: &str, attributes: &[(&str, &str)], self_closing: bool) -> XmlToken {
+    XmlToken::Open {
+        name: name.to_string(),
+        attributes: attributes
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect(),
+        self_closing,
+    }
+}
+
+fn text(value: &str) -> XmlToken {
+    XmlToken::Text(value.to_string())
+}
+
+fn close(name: &str) -> XmlToken {
+    XmlToken::Close(name.to_string())
+}
+
+fn cases() -> Vec<TestCase> {
+    vec![
+        TestCase {
+            name: "elements, attributes and text",
+            input: r#"<?xml version="1.0"?><tt:p xml:id="a" begin='1s'>Hi<br/>there</tt:p>"#,
+            expected: vec![
+                open("p", &[("xml:id", "a"), ("begin", "1s")], false),
+                text("Hi"),
+                open("br", &[], true),
+                text("there"),
+                close("p"),
+            ],
+        },
+        TestCase {
+            name: "comments are dropped and CDATA is escaped",
+            input: "<p><!-- note <b> -->a<![CDATA[<b> & c]]></p>",
+            expected: vec![
+                open("p", &[], false),
+                text("a"),
+                text("&lt;b&gt; &amp; c"),
+                close("p"),
+            ],
+        },
+        TestCase {
+            name: "unquoted attributes are ignored",
+            input: "<p begin=1s end=\"2s\">",
+            expected: vec![open("p", &[("end", "2s")], false)],
+        },
+        TestCase {
+            name: "an unterminated tag is kept as text",
+            input: "a<p begin=\"1s\"",
+            expected: vec![text("a"), text("<p begin=\"1s\"")],
+        },
+        TestCase {
+            name: "an unterminated comment is read up to the next bracket",
+            input: "<!-- open > tail",
+            expected: vec![text(" tail")],
+        },
+        TestCase {
+            name: "an unterminated CDATA section is read up to the next bracket",
+            input: "<![CDATA[<b> tail",
+            expected: vec![text(" tail")],
+        },
+    ]
+}
+
+#[test]
+fn test_read() {
+    let reader = XmlReader::new();
+    for test_case in cases() {
+        assert_eq!(
+            reader.read(test_case.input),
+            test_case.expected,
+            "Failed at case: {}",
+            test_case.name
+        );
+    }
+}
+
+#[test]
+fn test_attribute_lookup() {
+    let attributes = vec![
+        ("ttp:frameRate".to_string(), "25".to_string()),
+        ("xml:id".to_string(), "sub1".to_string()),
+    ];
+    assert_eq!(XmlReader::attribute(&attributes, "frameRate"), Some("25"));
+    assert_eq!(XmlReader::attribute(&attributes, "id"), Some("sub1"));
+    assert_eq!(XmlReader::attribute(&attributes, "tickRate"), None);
+    assert_eq!(XmlReader::local_name("tts:color"), "color");
+    assert_eq!(
+        XmlReader::escape("<a href=\"x\">&</a>"),
+        "&lt;a href=&quot;x&quot;&gt;&amp;&lt;/a&gt;"
+    );
+}
@@ -0,0 +1,6 @@
+use crate::model::{format::vtt::parser::VttParser, stages};
+
+pub type SceneApplier = stages::applier::SceneApplier<VttParser>;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,52 @@
+use crate::model::parse_error::ParseRes;
+
+use super::*;
+
+struct TestCase {
+    name: &'static str,
+    input: &'static [&'static str],
+    translations: &'static [&'static str],
+    expected: &'static [&'static str],
+}
+
+static SELECTIVE_APPLY_CASE: TestCase = TestCase {
+    name: "only replaces the text of additional scene cues and keeps the voice",
+    input: &[
+        "WEBVTT",
+        "",
+        "00:00:01.000 --> 00:00:03.000",
+        "Normal Subtitle",
+        "",
+        "00:00:20.000 --> 00:00:22.000 align:start",
+        "<v ADDITIONAL SCENE>Target Scene",
+    ],
+    translations: &["Escena procesada"],
+    expected: &[
+        "WEBVTT",
+        "",
+        "00:00:01.000 --> 00:00:03.000",
+        "Normal Subtitle",
+        "",
+        "00:00:20.000 --> 00:00:22.000 align:start",
+        "<v ADDITIONAL SCENE>Escena procesada",
+    ],
+};
+
+fn run_test_case(test_case: &TestCase) -> ParseRes<()> {
+    let input: Vec<String> = test_case.input.iter().map(|s| s.to_string()).collect();
+    let translations: Vec<String> = test_case
+        .translations
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    let expected: Vec<String> = test_case.expected.iter().map(|s| s.to_string()).collect();
+    let applier = SceneApplier::new();
+    let result = applier.run(&input, &translations)?;
+    assert_eq!(result, expected, "Failed at case: {}", test_case.name);
+    Ok(())
+}
+
+#[test]
+fn test_selective_apply() -> ParseRes<()> {
+    run_test_case(&SELECTIVE_APPLY_CASE)
+}
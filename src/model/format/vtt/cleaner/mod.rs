@@ -0,0 +1,6 @@
+use crate::model::{format::vtt::parser::VttParser, stages};
+
+pub type Cleaner = stages::cleaner::Cleaner<VttParser>;
+
+#[cfg(test)]
+mod tests;
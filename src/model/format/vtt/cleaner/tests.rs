@@ -0,0 +1,113 @@
+use crate::model::parse_error::{ParseRes, ParserError};
+
+use super::*;
+
+struct TestCase {
+    name: &'static str,
+    input: &'static [&'static str],
+    expected: &'static [&'static str],
+}
+
+static ROUND_TRIP_CASE: TestCase = TestCase {
+    name: "keeps header, NOTE, STYLE, settings and voice spans untouched",
+    input: &[
+        "WEBVTT - Episode 1",
+        "Kind: captions",
+        "",
+        "STYLE",
+        "::cue(.loud) {",
+        "  color: yellow;",
+        "}",
+        "",
+        "NOTE translator check",
+        "this line",
+        "",
+        "intro-1",
+        "00:00:01.000 --> 00:00:04.000 line:0 position:50% align:center",
+        "<v.loud Roger>Hello there",
+        "second row</v>",
+        "",
+        "01:02.500 --> 01:04.000",
+        "<i>Short timestamp</i>",
+    ],
+    expected: &[
+        "WEBVTT - Episode 1",
+        "Kind: captions",
+        "",
+        "STYLE",
+        "::cue(.loud) {",
+        "  color: yellow;",
+        "}",
+        "",
+        "NOTE translator check",
+        "this line",
+        "",
+        "intro-1",
+        "00:00:01.000 --> 00:00:04.000 line:0 position:50% align:center",
+        "<v.loud Roger>Hello there",
+        "second row</v>",
+        "",
+        "00:01:02.500 --> 00:01:04.000",
+        "<i>Short timestamp</i>",
+    ],
+};
+
+static DUPLICATE_AND_EMPTY_CASE: TestCase = TestCase {
+    name: "removes duplicate and empty cues but keeps notes",
+    input: &[
+        "WEBVTT",
+        "",
+        "00:00:01.000 --> 00:00:02.000",
+        "Line A",
+        "",
+        "00:00:01.000 --> 00:00:02.000",
+        "Line A",
+        "",
+        "NOTE keep me",
+        "",
+        "00:00:03.000 --> 00:00:04.000",
+        "<b></b>",
+    ],
+    expected: &[
+        "WEBVTT",
+        "",
+        "00:00:01.000 --> 00:00:02.000",
+        "Line A",
+        "",
+        "NOTE keep me",
+    ],
+};
+
+static MISSING_SIGNATURE_CASE: TestCase = TestCase {
+    name: "fails when the WEBVTT signature is missing",
+    input: &["00:00:01.000 --> 00:00:02.000", "Line"],
+    expected: &[],
+};
+
+fn run_test_case(test_case: &TestCase) -> ParseRes<()> {
+    let mut input: Vec<String> = test_case.input.iter().map(|s| s.to_string()).collect();
+    let expected: Vec<String> = test_case.expected.iter().map(|s| s.to_string()).collect();
+    let cleaner = Cleaner::new();
+    cleaner.run(&mut input)?;
+    assert_eq!(input, expected, "Failed at case: {}", test_case.name);
+    Ok(())
+}
+
+#[test]
+fn test_clean_round_trip() -> ParseRes<()> {
+    run_test_case(&ROUND_TRIP_CASE)
+}
+
+#[test]
+fn test_clean_duplicates_and_empty() -> ParseRes<()> {
+    run_test_case(&DUPLICATE_AND_EMPTY_CASE)
+}
+
+#[test]
+fn test_error_missing_signature() {
+    let result = run_test_case(&MISSING_SIGNATURE_CASE);
+    match result {
+        Err(ParserError::MissingSignature) => {}
+        _ => panic!("Expected MissingSignature, got {:?}", result),
+    }
+}
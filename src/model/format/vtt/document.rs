@@ -0,0 +1,31 @@
+use crate::model::format::vtt::line_vtt::VttLine;
+
+#[derive(Debug, Clone)]
+pub enum VttBlock {
+    Note(Vec<String>),
+    Style(Vec<String>),
+    Region(Vec<String>),
+    Cue(VttLine),
+}
+
+#[derive(Debug, Clone)]
+pub struct VttDocument {
+    pub header: Vec<String>,
+    pub blocks: Vec<VttBlock>,
+}
+
+impl VttDocument {
+    pub fn cues(&self) -> impl Iterator<Item = &VttLine> {
+        self.blocks.iter().filter_map(|b| match b {
+            VttBlock::Cue(cue) => Some(cue),
+            _ => None,
+        })
+    }
+
+    pub fn cues_mut(&mut self) -> impl Iterator<Item = &mut VttLine> {
+        self.blocks.iter_mut().filter_map(|b| match b {
+            VttBlock::Cue(cue) => Some(cue),
+            _ => None,
+        })
+    }
+}
@@ -0,0 +1,6 @@
+use crate::model::{format::vtt::parser::VttParser, stages};
+
+pub type SceneExtractor = stages::extractor::SceneExtractor<VttParser>;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,28 @@
+use crate::model::{line::Line, timestamp::Timestamp};
+
+#[derive(Debug, Clone)]
+pub struct VttLine {
+    pub identifier: Option<String>,
+    pub start: Timestamp,
+    pub end: Timestamp,
+    pub settings: String,
+    pub voice_classes: String,
+    pub voice: String,
+    pub text: String,
+}
+
+impl Line for VttLine {
+    fn start(&self) -> Timestamp {
+        self.start
+    }
+    fn end(&self) -> Timestamp {
+        self.end
+    }
+    fn text(&self) -> &str {
+        &self.text
+    }
+    fn set_times(&mut self, start: Timestamp, end: Timestamp) {
+        self.start = start;
+        self.end = end;
+    }
+}
@@ -0,0 +1,10 @@
+pub mod applier;
+pub mod cleaner;
+pub mod document;
+pub mod extractor;
+pub mod line_vtt;
+pub mod parser;
+pub mod sorter;
+pub mod stylist;
+pub mod synchronizer;
+pub mod vtt_processor;
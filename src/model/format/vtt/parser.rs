@@ -0,0 +1,322 @@
+use regex::Regex;
+use std::result::Result;
+
+use crate::model::{
+    format::vtt::{
+        document::{VttBlock, VttDocument},
+        line_vtt::VttLine,
+    },
+    parse_error::{ParseRes, ParserError},
+    stages::CueFormat,
+    synchronizer::SceneMarker,
+    timestamp::Timestamp,
+};
+
+pub struct VttParser {
+    tags_re: Regex,
+    voice_re: Regex,
+    additional_scene_re: Regex,
+}
+
+impl VttParser {
+    const SIGNATURE: &str = "WEBVTT";
+    const BOM: char = '\u{feff}';
+    const NOTE_KEYWORD: &str = "NOTE";
+    const STYLE_KEYWORD: &str = "STYLE";
+    const REGION_KEYWORD: &str = "REGION";
+    const TIMING_ARROW: &str = "-->";
+    const LINE_BREAK: &str = "\n";
+    const INLINE_BREAK: &str = "\\N";
+
+    const TAGS_EXPRESSION_RE: &str = r"<[^>]*>";
+    const VOICE_RE: &str = r"^<v(\.[^\s>]*)?[ \t]+([^>]*)>";
+    const SCENE_NAME: &str = "ADDITIONAL SCENE";
+    const ADDITIONAL_SCENE_RE: &str = r"(?i)(additional scene|extra scene|bonus scene|special)";
+
+    const EXCESSIVE_TAGS_THRESHOLD: usize = 50;
+
+    pub fn new() -> Self {
+        Self {
+            tags_re: Regex::new(Self::TAGS_EXPRESSION_RE).unwrap(),
+            voice_re: Regex::new(Self::VOICE_RE).unwrap(),
+            additional_scene_re: Regex::new(Self::ADDITIONAL_SCENE_RE).unwrap(),
+        }
+    }
+
+    fn split_blocks<'a>(&self, lines: &'a [String]) -> Vec<(usize, Vec<&'a str>)> {
+        let mut blocks = Vec::new();
+        let mut current = Vec::new();
+        let mut first_line = 0;
+        for (idx, line) in lines.iter().enumerate() {
+            let line = line.trim_end();
+            match line.is_empty() {
+                true if !current.is_empty() => {
+                    blocks.push((first_line, std::mem::take(&mut current)))
+                }
+                true => {}
+                false if current.is_empty() => {
+                    first_line = idx + 1;
+                    current.push(line);
+                }
+                false => current.push(line),
+            }
+        }
+        if !current.is_empty() {
+            blocks.push((first_line, current));
+        }
+        blocks
+    }
+
+    fn is_keyword_block(first: &str, keyword: &str) -> bool {
+        match first.strip_prefix(keyword) {
+            Some(rest) => rest.is_empty() || rest.starts_with([' ', '\t']),
+            None => false,
+        }
+    }
+
+    fn parse_header(&self, block: Option<&Vec<&str>>) -> Result<Vec<String>, ParserError> {
+        let block = block.ok_or(ParserError::MissingSignature)?;
+        let first = block[0].trim_start_matches(Self::BOM);
+        match Self::is_keyword_block(first, Self::SIGNATURE) {
+            true => Ok(std::iter::once(first)
+                .chain(block[1..].iter().copied())
+                .map(String::from)
+                .collect()),
+            false => Err(ParserError::MissingSignature),
+        }
+    }
+
+    fn timing_index(&self, block: &[&str]) -> Result<usize, ParserError> {
+        match block
+            .iter()
+            .take(2)
+            .position(|l| l.contains(Self::TIMING_ARROW))
+        {
+            Some(idx) => Ok(idx),
+            None => Err(ParserError::TimingLine {
+                found: block[0].to_string(),
+            }),
+        }
+    }
+
+    fn parse_timing(&self, line: &str) -> Result<(Timestamp, Timestamp, String), ParserError> {
+        let (start, rest) = line
+            .split_once(Self::TIMING_ARROW)
+            .unwrap_or((line, Default::default()));
+        let rest = rest.trim_start();
+        let (end, settings) = rest.split_once([' ', '\t']).unwrap_or((rest, ""));
+        let start = self.parse_time(start.trim())?;
+        let end = self.parse_time(end)?;
+        Ok((start, end, settings.trim().to_string()))
+    }
+
+    fn split_voice(&self, payload: &str) -> (String, String, String) {
+        match self.voice_re.captures(payload) {
+            Some(caps) => {
+                let tag_len = caps.get(0).map_or(0, |m| m.end());
+                let classes = caps.get(1).map_or("", |m| m.as_str()).to_string();
+                (classes, caps[2].to_string(), payload[tag_len..].to_string())
+            }
+            None => (String::new(), String::new(), payload.to_string()),
+        }
+    }
+
+    fn parse_cue(&self, first_line: usize, block: &[&str]) -> Result<VttLine, ParserError> {
+        let timing_idx = self.timing_index(block)?;
+        let identifier = block[..timing_idx].first().map(|s| s.to_string());
+        let timing = block[timing_idx];
+        let (start, end, settings) = self
+            .parse_timing(timing)
+            .map_err(|e| e.at_line(first_line + timing_idx, timing))?;
+        let payload = block[timing_idx + 1..].join(Self::LINE_BREAK);
+        let (voice_classes, voice, text) = self.split_voice(&payload);
+        Ok(VttLine {
+            identifier,
+            start,
+            end,
+            settings,
+            voice_classes,
+            voice,
+            text,
+        })
+    }
+
+    fn parse_block(&self, first_line: usize, block: &[&str]) -> Result<VttBlock, ParserError> {
+        let lines = || block.iter().map(|s| s.to_string()).collect();
+        match block[0] {
+            first if Self::is_keyword_block(first, Self::NOTE_KEYWORD) => {
+                Ok(VttBlock::Note(lines()))
+            }
+            first if first == Self::STYLE_KEYWORD => Ok(VttBlock::Style(lines())),
+            first if first == Self::REGION_KEYWORD => Ok(VttBlock::Region(lines())),
+            _ => Ok(VttBlock::Cue(self.parse_cue(first_line, block)?)),
+        }
+    }
+
+    pub fn parse(&self, lines: &[String]) -> Result<VttDocument, ParserError> {
+        let blocks = self.split_blocks(lines);
+        let header = self.parse_header(blocks.first().map(|(_, block)| block))?;
+        let blocks = blocks[1..]
+            .iter()
+            .map(|(first_line, block)| self.parse_block(*first_line, block))
+            .collect::<Result<Vec<VttBlock>, ParserError>>()?;
+        Ok(VttDocument { header, blocks })
+    }
+
+    pub fn parse_time(&self, t: &str) -> Result<Timestamp, ParserError> {
+        Timestamp::parse_vtt(t).ok_or_else(|| ParserError::invalid_timestamp(t))
+    }
+
+    fn timing_line(&self, cue: &VttLine) -> String {
+        let timing = format!(
+            "{} {} {}",
+            cue.start.to_vtt(),
+            Self::TIMING_ARROW,
+            cue.end.to_vtt()
+        );
+        match cue.settings.is_empty() {
+            true => timing,
+            false => format!("{} {}", timing, cue.settings),
+        }
+    }
+
+    fn payload(&self, cue: &VttLine) -> String {
+        match cue.voice.is_empty() {
+            true => cue.text.clone(),
+            false => format!("<v{} {}>{}", cue.voice_classes, cue.voice, cue.text),
+        }
+    }
+
+    fn push_cue(&self, out: &mut Vec<String>, cue: &VttLine) {
+        out.extend(cue.identifier.clone());
+        out.push(self.timing_line(cue));
+        out.extend(self.payload(cue).split(Self::LINE_BREAK).map(String::from));
+    }
+
+    fn push_block(&self, out: &mut Vec<String>, block: &VttBlock) {
+        match block {
+            VttBlock::Note(lines) | VttBlock::Style(lines) | VttBlock::Region(lines) => {
+                out.extend(lines.iter().cloned())
+            }
+            VttBlock::Cue(cue) => self.push_cue(out, cue),
+        }
+    }
+
+    pub fn to_lines(&self, doc: &VttDocument) -> Vec<String> {
+        let mut out = doc.header.clone();
+        for block in &doc.blocks {
+            out.push(String::new());
+            self.push_block(&mut out, block);
+        }
+        out
+    }
+
+    pub fn build_line_key(&self, cue: &VttLine) -> String {
+        format!(
+            "{}|{}|{}|{}",
+            cue.start.millis(),
+            cue.end.millis(),
+            cue.voice,
+            cue.text
+        )
+    }
+
+    pub fn is_scene_line(&self, cue: &VttLine) -> bool {
+        self.additional_scene_re.is_match(&cue.voice)
+    }
+
+    pub fn mark_scene(&self, cue: &mut VttLine) {
+        if !self.is_scene_line(cue) {
+            cue.voice = Self::SCENE_NAME.to_string();
+        }
+    }
+
+    pub fn is_text_empty(&self, cue: &VttLine) -> bool {
+        self.tags_re.replace_all(&cue.text, "").trim().is_empty()
+    }
+
+    pub fn has_excessive_tags(&self, text: &str) -> bool {
+        let total_tags_len: usize = self.tags_re.find_iter(text).map(|m| m.as_str().len()).sum();
+        total_tags_len > Self::EXCESSIVE_TAGS_THRESHOLD
+    }
+
+    pub fn to_single_line(&self, text: &str) -> String {
+        text.replace(Self::LINE_BREAK, Self::INLINE_BREAK)
+    }
+
+    pub fn from_single_line(&self, text: &str) -> String {
+        text.replace(Self::INLINE_BREAK, Self::LINE_BREAK)
+    }
+
+    pub fn style_keyword(&self) -> String {
+        Self::STYLE_KEYWORD.to_string()
+    }
+}
+
+impl Default for VttParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SceneMarker<VttLine> for VttParser {
+    fn is_scene_line(&self, cue: &VttLine) -> bool {
+        VttParser::is_scene_line(self, cue)
+    }
+
+    fn mark_scene(&self, cue: &mut VttLine) {
+        VttParser::mark_scene(self, cue)
+    }
+}
+
+impl CueFormat for VttParser {
+    type Cue = VttLine;
+    type Document = VttDocument;
+
+    fn parse(&self, lines: &[String]) -> ParseRes<VttDocument> {
+        VttParser::parse(self, lines)
+    }
+
+    fn to_lines(&self, doc: &VttDocument) -> Vec<String> {
+        VttParser::to_lines(self, doc)
+    }
+
+    fn cues<'d>(&self, doc: &'d VttDocument) -> impl Iterator<Item = &'d VttLine> {
+        doc.cues()
+    }
+
+    fn cues_mut<'d>(&self, doc: &'d mut VttDocument) -> impl Iterator<Item = &'d mut VttLine> {
+        doc.cues_mut()
+    }
+
+    fn retain_cues(&self, doc: &mut VttDocument, mut keep: impl FnMut(&VttLine) -> bool) {
+        doc.blocks.retain(|block| match block {
+            VttBlock::Cue(cue) => keep(cue),
+            _ => true,
+        });
+    }
+
+    fn set_text(&self, cue: &mut VttLine, text: String) {
+        cue.text = text;
+    }
+
+    fn build_line_key(&self, cue: &VttLine) -> String {
+        VttParser::build_line_key(self, cue)
+    }
+
+    fn is_text_empty(&self, cue: &VttLine) -> bool {
+        VttParser::is_text_empty(self, cue)
+    }
+
+    fn has_excessive_tags(&self, text: &str) -> bool {
+        VttParser::has_excessive_tags(self, text)
+    }
+
+    fn to_single_line(&self, text: &str) -> String {
+        VttParser::to_single_line(self, text)
+    }
+
+    fn restore_line_breaks(&self, text: &str) -> String {
+        VttParser::from_single_line(self, text)
+    }
+}
@@ -0,0 +1,6 @@
+use crate::model::{format::vtt::parser::VttParser, stages};
+
+pub type Sorter = stages::sorter::Sorter<VttParser>;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,74 @@
+use crate::model::parse_error::{ParseRes, ParserError};
+
+use super::*;
+
+struct TestCase {
+    name: &'static str,
+    input: &'static [&'static str],
+    expected: &'static [&'static str],
+}
+
+static BASIC_ORDER_CASE: TestCase = TestCase {
+    name: "orders cues while NOTE blocks keep their place",
+    input: &[
+        "WEBVTT",
+        "",
+        "NOTE first",
+        "",
+        "00:00:05.000 --> 00:00:06.000",
+        "Line 3",
+        "",
+        "00:00:01.000 --> 00:00:02.000",
+        "Line 1",
+        "",
+        "00:00:03.000 --> 00:00:04.000",
+        "Line 2",
+    ],
+    expected: &[
+        "WEBVTT",
+        "",
+        "NOTE first",
+        "",
+        "00:00:01.000 --> 00:00:02.000",
+        "Line 1",
+        "",
+        "00:00:03.000 --> 00:00:04.000",
+        "Line 2",
+        "",
+        "00:00:05.000 --> 00:00:06.000",
+        "Line 3",
+    ],
+};
+
+static INVALID_TIME_CASE: TestCase = TestCase {
+    name: "fails on a timestamp without milliseconds",
+    input: &["WEBVTT", "", "00:00:01 --> 00:00:02.000", "Broken"],
+    expected: &[],
+};
+
+fn run_test_case(test_case: &TestCase) -> ParseRes<()> {
+    let input: Vec<String> = test_case.input.iter().map(|s| s.to_string()).collect();
+    let expected: Vec<String> = test_case.expected.iter().map(|s| s.to_string()).collect();
+    let sorter = Sorter::new();
+    let result = sorter.run(&input)?;
+    assert_eq!(result, expected, "Failed at case: {}", test_case.name);
+    Ok(())
+}
+
+#[test]
+fn test_basic_order() -> ParseRes<()> {
+    run_test_case(&BASIC_ORDER_CASE)
+}
+
+#[test]
+fn test_error_invalid_time() {
+    let result = run_test_case(&INVALID_TIME_CASE);
+    match result {
+        Err(ParserError::InvalidTimestamp { location, found }) => {
+            assert_eq!(found, "00:00:01");
+            assert_eq!(location.line, 3);
+            assert_eq!(location.column, 0);
+        }
+        _ => panic!("Expected InvalidTimestamp, got {:?}", result),
+    }
+}
@@ -0,0 +1,86 @@
+use crate::model::{
+    format::{
+        ass::stylist::style_type::StyleType,
+        vtt::{
+            document::{VttBlock, VttDocument},
+            parser::VttParser,
+        },
+    },
+    parse_error::ParseRes,
+};
+
+pub struct Stylist {
+    parser: VttParser,
+    rules: Vec<&'static str>,
+}
+
+impl Stylist {
+    const MAIN_RULES: [&str; 5] = [
+        "::cue {",
+        "  font-family: \"Trebuchet MS\", sans-serif;",
+        "  color: #FFFFFF;",
+        "  text-shadow: 0 0 2px #000000;",
+        "}",
+    ];
+    const SECOND_RULES: [&str; 5] = [
+        "::cue {",
+        "  font-family: \"Roboto\", sans-serif;",
+        "  color: #FFFFFF;",
+        "  text-shadow: 0 0 1.5px #000000;",
+        "}",
+    ];
+
+    pub fn new(style_type: &StyleType) -> Self {
+        let rules = match style_type {
+            StyleType::Main => Self::MAIN_RULES.to_vec(),
+            StyleType::Second => Self::SECOND_RULES.to_vec(),
+        };
+        Self {
+            parser: VttParser::new(),
+            rules,
+        }
+    }
+
+    fn style_block(&self) -> VttBlock {
+        let mut lines = vec![self.parser.style_keyword()];
+        lines.extend(self.rules.iter().map(|r| r.to_string()));
+        VttBlock::Style(lines)
+    }
+
+    fn insert_position(&self, doc: &VttDocument) -> usize {
+        let first_cue = doc
+            .blocks
+            .iter()
+            .position(|b| matches!(b, VttBlock::Cue(_)))
+            .unwrap_or(doc.blocks.len());
+        doc.blocks[..first_cue]
+            .iter()
+            .rposition(|b| matches!(b, VttBlock::Style(_)))
+            .map_or(0, |idx| idx + 1)
+    }
+
+    fn is_known_style(block: &VttBlock) -> bool {
+        let VttBlock::Style(lines) = block else {
+            return false;
+        };
+        let rules = lines.get(1..).unwrap_or_default();
+        [Self::MAIN_RULES, Self::SECOND_RULES]
+            .iter()
+            .any(|known| rules.iter().map(String::as_str).eq(known.iter().copied()))
+    }
+
+    pub fn run(&self, lines: &[String]) -> ParseRes<Vec<String>> {
+        let mut doc = self.parser.parse(lines)?;
+        match doc.blocks.iter().position(Self::is_known_style) {
+            Some(idx) => doc.blocks[idx] = self.style_block(),
+            None => {
+                let idx = self.insert_position(&doc);
+                doc.blocks.insert(idx, self.style_block());
+            }
+        }
+        Ok(self.parser.to_lines(&doc))
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,97 @@
+use super::*;
+
+struct TestCase {
+    name: &'static str,
+    input: &'static [&'static str],
+    expected: &'static [&'static str],
+}
+
+static MAIN_CASE: TestCase = TestCase {
+    name: "adds the Main cue style right after the header",
+    input: &["WEBVTT", "", "00:00:01.000 --> 00:00:02.000", "Line 1"],
+    expected: &[
+        "WEBVTT",
+        "",
+        "STYLE",
+        "::cue {",
+        "  font-family: \"Trebuchet MS\", sans-serif;",
+        "  color: #FFFFFF;",
+        "  text-shadow: 0 0 2px #000000;",
+        "}",
+        "",
+        "00:00:01.000 --> 00:00:02.000",
+        "Line 1",
+    ],
+};
+
+static EXISTING_STYLE_CASE: TestCase = TestCase {
+    name: "adds the Second cue style after the existing STYLE blocks",
+    input: &[
+        "WEBVTT",
+        "",
+        "STYLE",
+        "::cue(b) { color: red; }",
+        "",
+        "NOTE after styles",
+        "",
+        "00:00:01.000 --> 00:00:02.000",
+        "Line 1",
+    ],
+    expected: &[
+        "WEBVTT",
+        "",
+        "STYLE",
+        "::cue(b) { color: red; }",
+        "",
+        "STYLE",
+        "::cue {",
+        "  font-family: \"Roboto\", sans-serif;",
+        "  color: #FFFFFF;",
+        "  text-shadow: 0 0 1.5px #000000;",
+        "}",
+        "",
+        "NOTE after styles",
+        "",
+        "00:00:01.000 --> 00:00:02.000",
+        "Line 1",
+    ],
+};
+
+fn run_test_case(test_case: &TestCase, style_type: &StyleType) -> ParseRes<()> {
+    let input: Vec<String> = test_case.input.iter().map(|s| s.to_string()).collect();
+    let expected: Vec<String> = test_case.expected.iter().map(|s| s.to_string()).collect();
+    let stylist = Stylist::new(style_type);
+    let result = stylist.run(&input)?;
+    assert_eq!(result, expected, "Failed at case: {}", test_case.name);
+    Ok(())
+}
+
+#[test]
+fn test_main_style() -> ParseRes<()> {
+    run_test_case(&MAIN_CASE, &StyleType::Main)
+}
+
+#[test]
+fn test_existing_style() -> ParseRes<()> {
+    run_test_case(&EXISTING_STYLE_CASE, &StyleType::Second)
+}
+
+#[test]
+fn test_running_twice_keeps_one_style() -> ParseRes<()> {
+    let input: Vec<String> = MAIN_CASE.input.iter().map(|s| s.to_string()).collect();
+    let expected: Vec<String> = MAIN_CASE.expected.iter().map(|s| s.to_string()).collect();
+    let stylist = Stylist::new(&StyleType::Main);
+    let once = stylist.run(&input)?;
+    assert_eq!(stylist.run(&once)?, expected);
+    Ok(())
+}
+
+#[test]
+fn test_switching_style_replaces_the_block() -> ParseRes<()> {
+    let input: Vec<String> = MAIN_CASE.expected.iter().map(|s| s.to_string()).collect();
+    let result = Stylist::new(&StyleType::Second).run(&input)?;
+    let styles = result.iter().filter(|line| *line == "STYLE").count();
+    assert_eq!(styles, 1);
+    assert!(result.contains(&"  font-family: \"Roboto\", sans-serif;".to_string()));
+    Ok(())
+}
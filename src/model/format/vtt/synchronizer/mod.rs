@@ -0,0 +1,60 @@
+use crate::model::{
+    format::vtt::{
+        document::{VttBlock, VttDocument},
+        line_vtt::VttLine,
+        parser::VttParser,
+    },
+    parse_error::ParseRes,
+    sync_options::SyncOptions,
+    sync_result::SyncResult,
+    synchronizer::{LineSynchronizer, SyncedLines},
+};
+
+pub struct Synchronizer {
+    parser: VttParser,
+    options: SyncOptions,
+}
+
+impl Synchronizer {
+    pub fn new(options: SyncOptions) -> Self {
+        Self {
+            parser: VttParser::new(),
+            options,
+        }
+    }
+
+    fn rebuild(&self, doc_a: &VttDocument, synced: SyncedLines<VttLine>) -> VttDocument {
+        let mut leading = Some(synced.leading);
+        let mut groups = synced.groups.into_iter();
+        let mut blocks = Vec::with_capacity(doc_a.blocks.len());
+        for block in &doc_a.blocks {
+            match block {
+                VttBlock::Cue(_) => {
+                    let cues = leading.take().into_iter().chain(groups.next()).flatten();
+                    blocks.extend(cues.map(VttBlock::Cue))
+                }
+                other => blocks.push(other.clone()),
+            }
+        }
+        VttDocument {
+            header: doc_a.header.clone(),
+            blocks,
+        }
+    }
+
+    pub fn run(
+        &self,
+        source_a: &[String],
+        source_b: &[String],
+    ) -> ParseRes<SyncResult<Vec<String>>> {
+        let doc_a = self.parser.parse(source_a)?;
+        let doc_b = self.parser.parse(source_b)?;
+        let cues_a: Vec<&VttLine> = doc_a.cues().collect();
+        let cues_b: Vec<&VttLine> = doc_b.cues().collect();
+        let synced = LineSynchronizer::new(&self.parser, self.options).run(&cues_a, &cues_b);
+        Ok(synced.map(|synced| self.parser.to_lines(&self.rebuild(&doc_a, synced))))
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,73 @@
+use super::*;
+
+struct TestCase {
+    name: &'static str,
+    input_a: &'static [&'static str],
+    input_b: &'static [&'static str],
+    expected: &'static [&'static str],
+}
+
+static START_LATER_CASE: TestCase = TestCase {
+    name: "inserts the voiced additional scene and keeps A's notes",
+    input_a: &[
+        "WEBVTT",
+        "",
+        "00:00:01.100 --> 00:00:03.100",
+        "Hola",
+        "",
+        "NOTE check this line",
+        "",
+        "00:00:04.100 --> 00:00:05.100 align:start",
+        "¿Cómo estás?",
+        "",
+        "00:00:05.500 --> 00:00:07.500",
+        "Bien, gracias",
+    ],
+    input_b: &[
+        "WEBVTT",
+        "",
+        "00:00:01.000 --> 00:00:03.000",
+        "Hello",
+        "",
+        "00:00:04.000 --> 00:00:06.000",
+        "How are you?",
+        "",
+        "00:00:06.500 --> 00:00:08.000",
+        "<v ADDITIONAL SCENE>Extra scene",
+        "",
+        "00:00:10.000 --> 00:00:12.000",
+        "Fine, thanks",
+    ],
+    expected: &[
+        "WEBVTT",
+        "",
+        "00:00:01.000 --> 00:00:03.000",
+        "Hola",
+        "",
+        "NOTE check this line",
+        "",
+        "00:00:04.000 --> 00:00:05.000 align:start",
+        "¿Cómo estás?",
+        "",
+        "00:00:06.500 --> 00:00:08.000",
+        "<v ADDITIONAL SCENE>Extra scene",
+        "",
+        "00:00:10.000 --> 00:00:12.000",
+        "Bien, gracias",
+    ],
+};
+
+fn run_test_case(test_case: &TestCase) -> ParseRes<()> {
+    let input_a: Vec<String> = test_case.input_a.iter().map(|s| s.to_string()).collect();
+    let input_b: Vec<String> = test_case.input_b.iter().map(|s| s.to_string()).collect();
+    let expected: Vec<String> = test_case.expected.iter().map(|s| s.to_string()).collect();
+    let synchronizer = Synchronizer::new(SyncOptions::default());
+    let result = synchronizer.run(&input_a, &input_b)?.output;
+    assert_eq!(result, expected, "Failed at case: {}", test_case.name);
+    Ok(())
+}
+
+#[test]
+fn test_sync_inserts_additional_scene() -> ParseRes<()> {
+    run_test_case(&START_LATER_CASE)
+}
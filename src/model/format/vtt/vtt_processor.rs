@@ -0,0 +1,127 @@
+use crate::model::{
+    format::{
+        ass::stylist::style_type::StyleType,
+        vtt::{
+            applier::SceneApplier, cleaner::Cleaner, extractor::SceneExtractor, line_vtt::VttLine,
+            parser::VttParser, sorter::Sorter, stylist::Stylist, synchronizer::Synchronizer,
+        },
+    },
+    parse_error::ParserError,
+    retiming::{
+        anchors::{Anchor, AnchorMap},
+        keyframes::{KeyframeSnap, SnapSummary},
+    },
+    subtitle_processor::{ProcRes, SubtitleProcessor},
+    sync_options::SyncOptions,
+    sync_result::SyncResult,
+    timestamp::Timestamp,
+    translator::{instructor::Instructor, translator::Translator},
+};
+
+pub struct VttProcessor {
+    style_type: Option<StyleType>,
+    sync: SyncOptions,
+}
+
+impl VttProcessor {
+    pub fn new() -> Self {
+        Self {
+            style_type: None,
+            sync: SyncOptions::default(),
+        }
+    }
+
+    fn identify_style(&self, s: &str) -> StyleType {
+        match s {
+            "1" => StyleType::Main,
+            _ => StyleType::Second,
+        }
+    }
+
+    pub fn with_style(mut self, style_name: Option<String>) -> Self {
+        self.style_type = style_name.map(|s| self.identify_style(&s));
+        self
+    }
+
+    pub fn with_sync(mut self, sync: SyncOptions) -> Self {
+        self.sync = sync;
+        self
+    }
+}
+
+impl Default for VttProcessor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SubtitleProcessor for VttProcessor {
+    type Error = ParserError;
+
+    fn synchronize(
+        &self,
+        l_a: &[String],
+        l_b: &[String],
+    ) -> ProcRes<SyncResult<Vec<String>>, Self::Error> {
+        Synchronizer::new(self.sync).run(l_a, l_b)
+    }
+
+    fn retime(&self, lines: &[String], anchors: &[Anchor]) -> ProcRes<Vec<String>, Self::Error> {
+        let parser = VttParser::new();
+        let mut doc = parser.parse(lines)?;
+        let cues: Vec<&VttLine> = doc.cues().collect();
+        let map = AnchorMap::new(anchors, &cues)?;
+        map.retime(doc.cues_mut());
+        Ok(parser.to_lines(&doc))
+    }
+
+    fn timings(&self, lines: &[String]) -> ProcRes<Vec<(Timestamp, Timestamp)>, Self::Error> {
+        let doc = VttParser::new().parse(lines)?;
+        Ok(doc.cues().map(|cue| (cue.start, cue.end)).collect())
+    }
+
+    fn snap_keyframes(
+        &self,
+        lines: &[String],
+        snap: &KeyframeSnap,
+    ) -> ProcRes<(Vec<String>, SnapSummary), Self::Error> {
+        let parser = VttParser::new();
+        let mut doc = parser.parse(lines)?;
+        let summary = snap.retime(doc.cues_mut());
+        Ok((parser.to_lines(&doc), summary))
+    }
+
+    fn get_lines_to_translate(&self, lines: &mut Vec<String>) -> ProcRes<Vec<String>, Self::Error> {
+        let additional_scenes = SceneExtractor::new().run(lines)?;
+        Ok(Instructor::new().run(&additional_scenes))
+    }
+
+    fn apply_translation(
+        &self,
+        lines: &mut Vec<String>,
+        translations: &[String],
+    ) -> ProcRes<Vec<String>, Self::Error> {
+        SceneApplier::new().run(lines, translations)
+    }
+
+    fn translate_internal(&self, lines: &mut Vec<String>) -> ProcRes<Vec<String>, Self::Error> {
+        let to_translate = SceneExtractor::new().run(lines)?;
+        let translations = Translator::new().run(&to_translate);
+        self.apply_translation(lines, &translations)
+    }
+
+    fn apply_style(&self, lines: &[String]) -> ProcRes<Vec<String>, Self::Error> {
+        let style = self.style_type.as_ref().unwrap_or(&StyleType::Main);
+        Stylist::new(style).run(lines)
+    }
+
+    fn preprocessing(&self, lines: &mut Vec<String>) -> ProcRes<Vec<String>, Self::Error> {
+        Cleaner::new().run(lines)?;
+        Sorter::new().run(lines)
+    }
+
+    fn has_additional_scene(&self, lines: &[String]) -> ProcRes<bool, Self::Error> {
+        let additional_scenes = SceneExtractor::new().run(lines)?;
+        Ok(!additional_scenes.is_empty())
+    }
+}
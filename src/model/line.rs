@@ -1,5 +1,8 @@
+use crate::model::timestamp::Timestamp;
+
 pub trait Line {
-    fn start(&self) -> f64;
-    fn end(&self) -> f64;
+    fn start(&self) -> Timestamp;
+    fn end(&self) -> Timestamp;
     fn text(&self) -> &str;
+    fn set_times(&mut self, start: Timestamp, end: Timestamp);
 }
@@ -1,6 +1,16 @@
+pub mod aligner;
+pub mod audio;
 pub mod format;
 pub mod line;
+pub mod parse_error;
 pub mod repository;
+pub mod retiming;
+pub mod stages;
 pub mod subtitle_persistence;
 pub mod subtitle_processor;
+pub mod sync_options;
+pub mod sync_report;
+pub mod sync_result;
+pub mod synchronizer;
+pub mod timestamp;
 pub mod translator;
@@ -0,0 +1,236 @@
+use std::fmt::{Display, Formatter, Result};
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SourceLocation {
+    pub path: String,
+    pub line: usize,
+    pub column: usize,
+    pub width: usize,
+    pub source: String,
+}
+
+impl SourceLocation {
+    pub fn new(line: usize, source: &str) -> Self {
+        let indent = source.len() - source.trim_start().len();
+        Self {
+            path: String::new(),
+            line,
+            column: source[..indent].chars().count(),
+            width: source.trim().chars().count(),
+            source: source.to_string(),
+        }
+    }
+
+    pub fn highlight(mut self, text: &str) -> Self {
+        if let Some(idx) = self.source.find(text)
+            && !text.is_empty()
+        {
+            self.column = self.source[..idx].chars().count();
+            self.width = text.chars().count();
+        }
+        self
+    }
+}
+
+#[derive(Debug)]
+pub enum ParserError {
+    MissingFields {
+        expected: usize,
+        found: usize,
+    },
+    DialoguePrefix,
+    TimingLine {
+        found: String,
+    },
+    FrameLine {
+        found: String,
+    },
+    InvalidTimestamp {
+        location: SourceLocation,
+        found: String,
+    },
+    InvalidNumber {
+        location: SourceLocation,
+        found: String,
+    },
+    UnknownSection {
+        location: SourceLocation,
+    },
+    MissingSignature,
+    MissingColumn {
+        column: String,
+    },
+    UnknownColumn {
+        column: String,
+    },
+    MisplacedText,
+    InvalidAttachment {
+        name: String,
+    },
+    InvalidAudio {
+        reason: String,
+    },
+    InvalidKeyframes {
+        reason: String,
+    },
+    InvalidSetting {
+        location: SourceLocation,
+        found: String,
+    },
+}
+
+impl Display for ParserError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            ParserError::MissingFields { expected, found } => {
+                write!(f, "Missing Fields: Expected {}, found {}", expected, found)
+            }
+            ParserError::DialoguePrefix => {
+                write!(
+                    f,
+                    "Prefix Error: Line does not start with an event type such as 'Dialogue:'"
+                )
+            }
+            ParserError::TimingLine { found } => {
+                write!(
+                    f,
+                    "Timing Error: Expected 'start --> end', found '{}'",
+                    found
+                )
+            }
+            ParserError::FrameLine { found } => {
+                write!(
+                    f,
+                    "Timing Error: Expected '{{start}}{{end}}text', found '{}'",
+                    found
+                )
+            }
+            ParserError::InvalidTimestamp { found, .. } => {
+                write!(f, "Time Error: '{}' is not a valid timestamp", found)
+            }
+            ParserError::InvalidNumber { found, .. } => {
+                write!(f, "Number Error: '{}' is not a valid number", found)
+            }
+            ParserError::UnknownSection { location } => {
+                write!(
+                    f,
+                    "Section Error: '{}' is not a valid section header",
+                    location.source.trim()
+                )
+            }
+            ParserError::MissingSignature => {
+                write!(f, "Header Error: File does not start with 'WEBVTT'")
+            }
+            ParserError::MissingColumn { column } => {
+                write!(f, "Format Error: Events format has no '{}' column", column)
+            }
+            ParserError::UnknownColumn { column } => {
+                write!(f, "Format Error: Unknown events column '{}'", column)
+            }
+            ParserError::MisplacedText => {
+                write!(f, "Format Error: 'Text' must be the last events column")
+            }
+            ParserError::InvalidAttachment { name } => {
+                write!(
+                    f,
+                    "Attachment Error: '{}' does not contain valid uuencoded data",
+                    name
+                )
+            }
+            ParserError::InvalidAudio { reason } => {
+                write!(f, "Audio Error: {}", reason)
+            }
+            ParserError::InvalidKeyframes { reason } => {
+                write!(f, "Keyframe Error: {}", reason)
+            }
+            ParserError::InvalidSetting { found, .. } => {
+                write!(f, "Config Error: '{}' is not a valid setting", found)
+            }
+        }
+    }
+}
+
+impl ParserError {
+    pub fn invalid_timestamp(found: &str) -> Self {
+        ParserError::InvalidTimestamp {
+            location: SourceLocation::default(),
+            found: found.to_string(),
+        }
+    }
+
+    pub fn invalid_number(found: &str) -> Self {
+        ParserError::InvalidNumber {
+            location: SourceLocation::default(),
+            found: found.to_string(),
+        }
+    }
+
+    pub fn invalid_setting(found: &str) -> Self {
+        ParserError::InvalidSetting {
+            location: SourceLocation::default(),
+            found: found.to_string(),
+        }
+    }
+
+    pub fn invalid_audio(reason: &str) -> Self {
+        ParserError::InvalidAudio {
+            reason: reason.to_string(),
+        }
+    }
+
+    pub fn invalid_keyframes(reason: &str) -> Self {
+        ParserError::InvalidKeyframes {
+            reason: reason.to_string(),
+        }
+    }
+
+    pub fn location(&self) -> Option<&SourceLocation> {
+        match self {
+            ParserError::InvalidTimestamp { location, .. }
+            | ParserError::InvalidNumber { location, .. }
+            | ParserError::InvalidSetting { location, .. }
+            | ParserError::UnknownSection { location } => Some(location),
+            _ => None,
+        }
+    }
+
+    fn location_mut(&mut self) -> Option<&mut SourceLocation> {
+        match self {
+            ParserError::InvalidTimestamp { location, .. }
+            | ParserError::InvalidNumber { location, .. }
+            | ParserError::InvalidSetting { location, .. }
+            | ParserError::UnknownSection { location } => Some(location),
+            _ => None,
+        }
+    }
+
+    fn found(&self) -> Option<&str> {
+        match self {
+            ParserError::InvalidTimestamp { found, .. }
+            | ParserError::InvalidNumber { found, .. }
+            | ParserError::InvalidSetting { found, .. } => Some(found),
+            _ => None,
+        }
+    }
+
+    pub fn at_line(mut self, line: usize, source: &str) -> Self {
+        let found = self.found().unwrap_or_default().to_string();
+        if let Some(location) = self.location_mut()
+            && location.line == 0
+        {
+            *location = SourceLocation::new(line, source).highlight(&found);
+        }
+        self
+    }
+
+    pub fn with_path(mut self, path: &str) -> Self {
+        if let Some(location) = self.location_mut()
+            && location.path.is_empty()
+        {
+            location.path = path.to_string();
+        }
+        self
+    }
+}
+
+pub type ParseRes<T> = std::result::Result<T, ParserError>;
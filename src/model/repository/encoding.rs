@@ -0,0 +1,190 @@
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TextEncoding {
+    #[default]
+    Utf8,
+    Utf8Bom,
+    Utf16Le,
+    Utf16Be,
+    Windows1252,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LineEnding {
+    #[default]
+    Lf,
+    CrLf,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FileEncoding {
+    pub text: TextEncoding,
+    pub line_ending: LineEnding,
+}
+
+impl TextEncoding {
+    const UTF8_BOM: &[u8] = &[0xEF, 0xBB, 0xBF];
+    const UTF16LE_BOM: &[u8] = &[0xFF, 0xFE];
+    const UTF16BE_BOM: &[u8] = &[0xFE, 0xFF];
+    const UTF16_SAMPLE_BYTES: usize = 512;
+
+    const WINDOWS_1252_HIGH: [char; 32] = [
+        '\u{20AC}', '\u{0081}', '\u{201A}', '\u{0192}', '\u{201E}', '\u{2026}', '\u{2020}',
+        '\u{2021}', '\u{02C6}', '\u{2030}', '\u{0160}', '\u{2039}', '\u{0152}', '\u{008D}',
+        '\u{017D}', '\u{008F}', '\u{0090}', '\u{2018}', '\u{2019}', '\u{201C}', '\u{201D}',
+        '\u{2022}', '\u{2013}', '\u{2014}', '\u{02DC}', '\u{2122}', '\u{0161}', '\u{203A}',
+        '\u{0153}', '\u{009D}', '\u{017E}', '\u{0178}',
+    ];
+
+    pub fn bom(self) -> &'static [u8] {
+        match self {
+            TextEncoding::Utf8Bom => Self::UTF8_BOM,
+            TextEncoding::Utf16Le => Self::UTF16LE_BOM,
+            TextEncoding::Utf16Be => Self::UTF16BE_BOM,
+            TextEncoding::Utf8 | TextEncoding::Windows1252 => &[],
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            TextEncoding::Utf8 => "UTF-8",
+            TextEncoding::Utf8Bom => "UTF-8 with BOM",
+            TextEncoding::Utf16Le => "UTF-16 LE",
+            TextEncoding::Utf16Be => "UTF-16 BE",
+            TextEncoding::Windows1252 => "Windows-1252",
+        }
+    }
+
+    fn detect_utf16(bytes: &[u8]) -> Option<Self> {
+        let sample = &bytes[..bytes.len().min(Self::UTF16_SAMPLE_BYTES)];
+        let zeros_at = |parity: usize| {
+            sample
+                .iter()
+                .skip(parity)
+                .step_by(2)
+                .filter(|b| **b == 0)
+                .count()
+        };
+        let threshold = sample.len() / 4;
+        match (zeros_at(0), zeros_at(1)) {
+            _ if sample.len() < 2 => None,
+            (_, odd) if odd > threshold => Some(TextEncoding::Utf16Le),
+            (even, _) if even > threshold => Some(TextEncoding::Utf16Be),
+            _ => None,
+        }
+    }
+
+    pub fn detect(bytes: &[u8]) -> Self {
+        match bytes {
+            _ if bytes.starts_with(Self::UTF8_BOM) => TextEncoding::Utf8Bom,
+            _ if bytes.starts_with(Self::UTF16LE_BOM) => TextEncoding::Utf16Le,
+            _ if bytes.starts_with(Self::UTF16BE_BOM) => TextEncoding::Utf16Be,
+            _ => Self::detect_utf16(bytes).unwrap_or(match std::str::from_utf8(bytes) {
+                Ok(_) => TextEncoding::Utf8,
+                Err(_) => TextEncoding::Windows1252,
+            }),
+        }
+    }
+
+    fn decode_utf16(bytes: &[u8], unit: fn([u8; 2]) -> u16) -> Option<String> {
+        if !bytes.len().is_multiple_of(2) {
+            return None;
+        }
+        let units = bytes.chunks_exact(2).map(|pair| unit([pair[0], pair[1]]));
+        char::decode_utf16(units)
+            .collect::<Result<String, _>>()
+            .ok()
+    }
+
+    fn decode_windows_1252(bytes: &[u8]) -> String {
+        bytes
+            .iter()
+            .map(|b| match b {
+                0x80..=0x9F => Self::WINDOWS_1252_HIGH[(b - 0x80) as usize],
+                _ => *b as char,
+            })
+            .collect()
+    }
+
+    pub fn decode(self, bytes: &[u8]) -> Option<String> {
+        let bytes = bytes.strip_prefix(self.bom()).unwrap_or(bytes);
+        match self {
+            TextEncoding::Utf8 | TextEncoding::Utf8Bom => String::from_utf8(bytes.to_vec()).ok(),
+            TextEncoding::Utf16Le => Self::decode_utf16(bytes, u16::from_le_bytes),
+            TextEncoding::Utf16Be => Self::decode_utf16(bytes, u16::from_be_bytes),
+            TextEncoding::Windows1252 => Some(Self::decode_windows_1252(bytes)),
+        }
+    }
+
+    fn encode_windows_1252(c: char) -> Option<u8> {
+        match c as u32 {
+            code @ (0x00..=0x7F | 0xA0..=0xFF) => Some(code as u8),
+            _ => Self::WINDOWS_1252_HIGH
+                .iter()
+                .position(|high| *high == c)
+                .map(|idx| 0x80 + idx as u8),
+        }
+    }
+
+    pub fn encode(self, text: &str) -> Option<Vec<u8>> {
+        let body = match self {
+            TextEncoding::Utf8 | TextEncoding::Utf8Bom => text.as_bytes().to_vec(),
+            TextEncoding::Utf16Le => text.encode_utf16().flat_map(u16::to_le_bytes).collect(),
+            TextEncoding::Utf16Be => text.encode_utf16().flat_map(u16::to_be_bytes).collect(),
+            TextEncoding::Windows1252 => text
+                .chars()
+                .map(Self::encode_windows_1252)
+                .collect::<Option<Vec<u8>>>()?,
+        };
+        Some([self.bom(), &body].concat())
+    }
+}
+
+impl LineEnding {
+    pub fn detect(text: &str) -> Self {
+        let breaks = text.matches('\n').count();
+        let crlf = text.matches("\r\n").count();
+        match breaks > 0 && crlf * 2 >= breaks {
+            true => LineEnding::CrLf,
+            false => LineEnding::Lf,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::CrLf => "\r\n",
+        }
+    }
+}
+
+impl FileEncoding {
+    pub fn new(text: TextEncoding, line_ending: LineEnding) -> Self {
+        Self { text, line_ending }
+    }
+
+    pub fn with_text(mut self, text: TextEncoding) -> Self {
+        self.text = text;
+        self
+    }
+
+    pub fn decode(bytes: &[u8]) -> Option<(Vec<String>, Self)> {
+        let text = TextEncoding::detect(bytes);
+        let content = text.decode(bytes)?;
+        let encoding = Self::new(text, LineEnding::detect(&content));
+        Some((content.lines().map(String::from).collect(), encoding))
+    }
+
+    pub fn encode<'a, I>(&self, lines: I) -> Option<Vec<u8>>
+    where
+        I: IntoIterator<Item = &'a String>,
+    {
+        let content: String = lines
+            .into_iter()
+            .flat_map(|line| [line.as_str(), self.line_ending.as_str()])
+            .collect();
+        self.text.encode(&content)
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,124 @@
+use super::*;
+
+struct TestCase {
+    name: &'static str,
+    input: &'static [u8],
+    encoding: FileEncoding,
+    lines: &'static [&'static str],
+}
+
+static UTF8_CASE: TestCase = TestCase {
+    name: "plain utf-8 with lf",
+    input: b"[Script Info]\nTitle: Caf\xC3\xA9\n",
+    encoding: FileEncoding {
+        text: TextEncoding::Utf8,
+        line_ending: LineEnding::Lf,
+    },
+    lines: &["[Script Info]", "Title: Café"],
+};
+
+static UTF8_BOM_CASE: TestCase = TestCase {
+    name: "utf-8 bom is stripped from the first line",
+    input: b"\xEF\xBB\xBF[Script Info]\r\nTitle: Test\r\n",
+    encoding: FileEncoding {
+        text: TextEncoding::Utf8Bom,
+        line_ending: LineEnding::CrLf,
+    },
+    lines: &["[Script Info]", "Title: Test"],
+};
+
+static UTF16LE_CASE: TestCase = TestCase {
+    name: "utf-16 le with bom",
+    input: b"\xFF\xFE[\x00E\x00]\x00\r\x00\n\x00\xE9\x00\r\x00\n\x00",
+    encoding: FileEncoding {
+        text: TextEncoding::Utf16Le,
+        line_ending: LineEnding::CrLf,
+    },
+    lines: &["[E]", "é"],
+};
+
+static UTF16BE_NO_BOM_CASE: TestCase = TestCase {
+    name: "utf-16 be detected without a bom",
+    input: b"\x001\x00\n\x00A\x00\n",
+    encoding: FileEncoding {
+        text: TextEncoding::Utf16Be,
+        line_ending: LineEnding::Lf,
+    },
+    lines: &["1", "A"],
+};
+
+static WINDOWS_1252_CASE: TestCase = TestCase {
+    name: "legacy windows-1252 srt",
+    input: b"1\r\n00:00:01,000 --> 00:00:02,000\r\n\x93Caf\xE9\x94 \x80\r\n",
+    encoding: FileEncoding {
+        text: TextEncoding::Windows1252,
+        line_ending: LineEnding::CrLf,
+    },
+    lines: &[
+        "1",
+        "00:00:01,000 --> 00:00:02,000",
+        "\u{201C}Café\u{201D} €",
+    ],
+};
+
+static CASES: &[&TestCase] = &[
+    &UTF8_CASE,
+    &UTF8_BOM_CASE,
+    &UTF16LE_CASE,
+    &UTF16BE_NO_BOM_CASE,
+    &WINDOWS_1252_CASE,
+];
+
+fn to_lines(test_case: &TestCase) -> Vec<String> {
+    test_case.lines.iter().map(|s| s.to_string()).collect()
+}
+
+#[test]
+fn test_decode_detects_encoding() {
+    for case in CASES {
+        let (lines, encoding) = FileEncoding::decode(case.input).expect(case.name);
+        assert_eq!(lines, to_lines(case), "Failed at case: {}", case.name);
+        assert_eq!(encoding, case.encoding, "Failed at case: {}", case.name);
+    }
+}
+
+#[test]
+fn test_encode_reproduces_bytes() {
+    for case in [
+        &UTF8_CASE,
+        &UTF8_BOM_CASE,
+        &UTF16LE_CASE,
+        &WINDOWS_1252_CASE,
+    ] {
+        let bytes = case.encoding.encode(&to_lines(case));
+        assert_eq!(
+            bytes.as_deref(),
+            Some(case.input),
+            "Failed at case: {}",
+            case.name
+        );
+    }
+}
+
+#[test]
+fn test_convert_to_target_encoding() {
+    let (lines, encoding) = FileEncoding::decode(WINDOWS_1252_CASE.input).unwrap();
+    let bytes = encoding
+        .with_text(TextEncoding::Utf8)
+        .encode(&lines)
+        .unwrap();
+    let text = String::from_utf8(bytes).unwrap();
+    assert!(text.ends_with("\u{201C}Café\u{201D} €\r\n"));
+}
+
+#[test]
+fn test_unmappable_character_fails() {
+    let lines = vec!["日本語".to_string()];
+    let encoding = FileEncoding::new(TextEncoding::Windows1252, LineEnding::Lf);
+    assert_eq!(encoding.encode(&lines), None);
+}
+
+#[test]
+fn test_invalid_utf16_fails() {
+    assert_eq!(TextEncoding::Utf16Le.decode(b"\xFF\xFEA"), None);
+}
@@ -1,7 +1,9 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::fs;
 
-use crate::model::repository::repository_error::{RepoRes, RepositoryError};
+use crate::model::repository::{
+    encoding::FileEncoding,
+    repository_error::{RepoRes, RepositoryError},
+};
 
 pub struct FileReader {
     path: String,
@@ -15,16 +17,19 @@ impl FileReader {
     }
 
     pub fn read_lines(&self) -> RepoRes<Vec<String>> {
-        let file = File::open(&self.path).map_err(|_| RepositoryError::SourceNotFound {
+        Ok(self.read_lines_with_encoding()?.0)
+    }
+
+    pub fn read_bytes(&self) -> RepoRes<Vec<u8>> {
+        fs::read(&self.path).map_err(|_| RepositoryError::SourceNotFound {
             context: self.path.clone(),
-        })?;
+        })
+    }
 
-        let reader = BufReader::new(file);
-        reader
-            .lines()
-            .collect::<std::io::Result<Vec<String>>>()
-            .map_err(|_| RepositoryError::ReadError {
-                context: self.path.clone(),
-            })
+    pub fn read_lines_with_encoding(&self) -> RepoRes<(Vec<String>, FileEncoding)> {
+        let bytes = self.read_bytes()?;
+        FileEncoding::decode(&bytes).ok_or_else(|| RepositoryError::ReadError {
+            context: self.path.clone(),
+        })
     }
 }
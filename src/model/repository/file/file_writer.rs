@@ -1,23 +1,47 @@
 use std::fs::OpenOptions;
 use std::io::Write;
 
-use crate::model::repository::repository_error::{RepoRes, RepositoryError};
+use crate::model::repository::{
+    encoding::FileEncoding,
+    repository_error::{RepoRes, RepositoryError},
+};
 
 pub struct FileWriter {
     path: String,
+    encoding: FileEncoding,
 }
 
 impl FileWriter {
     pub fn new(path: &str) -> Self {
         Self {
             path: path.to_string(),
+            encoding: FileEncoding::default(),
         }
     }
 
+    pub fn with_encoding(mut self, encoding: FileEncoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
     pub fn write_lines<'a, I>(&self, lines: I) -> RepoRes<()>
     where
         I: IntoIterator<Item = &'a String>,
     {
+        let bytes = self
+            .encoding
+            .encode(lines)
+            .ok_or_else(|| RepositoryError::InvalidFormat {
+                details: format!(
+                    "{} cannot be represented in {}",
+                    self.path,
+                    self.encoding.text.name()
+                ),
+            })?;
+        self.write_bytes(&bytes)
+    }
+
+    pub fn write_bytes(&self, bytes: &[u8]) -> RepoRes<()> {
         let mut file = OpenOptions::new()
             .create(true)
             .write(true)
@@ -26,12 +50,9 @@ impl FileWriter {
             .map_err(|_| RepositoryError::SaveFailed {
                 context: self.path.clone(),
             })?;
-
-        for l in lines {
-            writeln!(file, "{}", l).map_err(|_| RepositoryError::SaveFailed {
+        file.write_all(bytes)
+            .map_err(|_| RepositoryError::SaveFailed {
                 context: self.path.clone(),
-            })?;
-        }
-        Ok(())
+            })
     }
 }
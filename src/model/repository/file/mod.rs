@@ -3,6 +3,7 @@ pub mod file_writer;
 
 use super::SubtitleRepository;
 use crate::model::repository::{
+    encoding::FileEncoding,
     file::{file_reader::FileReader, file_writer::FileWriter},
     repository_error::RepoRes,
 };
@@ -15,12 +16,26 @@ impl FileRepository {
     }
 }
 
+impl Default for FileRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl SubtitleRepository for FileRepository {
-    fn get_all(&self, path: &str) -> RepoRes<Vec<String>> {
-        FileReader::new(path).read_lines()
+    fn get_all_with_encoding(&self, path: &str) -> RepoRes<(Vec<String>, FileEncoding)> {
+        FileReader::new(path).read_lines_with_encoding()
+    }
+
+    fn save_with_encoding(&self, path: &str, lines: &[String], enc: FileEncoding) -> RepoRes<()> {
+        FileWriter::new(path).with_encoding(enc).write_lines(lines)
+    }
+
+    fn get_bytes(&self, path: &str) -> RepoRes<Vec<u8>> {
+        FileReader::new(path).read_bytes()
     }
 
-    fn save(&self, path: &str, lines: &[String]) -> RepoRes<()> {
-        FileWriter::new(path).write_lines(lines)
+    fn save_bytes(&self, path: &str, bytes: &[u8]) -> RepoRes<()> {
+        FileWriter::new(path).write_bytes(bytes)
     }
 }
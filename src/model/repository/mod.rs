@@ -1,9 +1,21 @@
-use crate::model::repository::repository_error::RepoRes;
+use crate::model::repository::{encoding::FileEncoding, repository_error::RepoRes};
 
+pub mod encoding;
 pub mod file;
 pub mod repository_error;
 
 pub trait SubtitleRepository {
-    fn get_all(&self, id: &str) -> RepoRes<Vec<String>>;
-    fn save(&self, id: &str, lines: &[String]) -> RepoRes<()>;
+    fn get_all_with_encoding(&self, id: &str) -> RepoRes<(Vec<String>, FileEncoding)>;
+    fn save_with_encoding(&self, id: &str, lines: &[String], encoding: FileEncoding)
+    -> RepoRes<()>;
+    fn get_bytes(&self, id: &str) -> RepoRes<Vec<u8>>;
+    fn save_bytes(&self, id: &str, bytes: &[u8]) -> RepoRes<()>;
+
+    fn get_all(&self, id: &str) -> RepoRes<Vec<String>> {
+        Ok(self.get_all_with_encoding(id)?.0)
+    }
+
+    fn save(&self, id: &str, lines: &[String]) -> RepoRes<()> {
+        self.save_with_encoding(id, lines, FileEncoding::default())
+    }
 }
@@ -0,0 +1,120 @@
+use crate::model::{
+    line::Line,
+    parse_error::{ParseRes, ParserError},
+    timestamp::Timestamp,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AnchorSource {
+    Line(usize),
+    Time(Timestamp),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Anchor {
+    pub source: AnchorSource,
+    pub target: Timestamp,
+}
+
+impl Anchor {
+    const COMMENT: char = '#';
+    const SEPARATORS: [&'static str; 3] = ["=>", "-->", "->"];
+
+    pub fn new(source: AnchorSource, target: Timestamp) -> Self {
+        Self { source, target }
+    }
+
+    fn parse_time(token: &str) -> ParseRes<Timestamp> {
+        Timestamp::parse_srt(token)
+            .or_else(|| Timestamp::parse_vtt(token))
+            .ok_or_else(|| ParserError::invalid_timestamp(token))
+    }
+
+    fn parse_source(token: &str) -> ParseRes<AnchorSource> {
+        if token.contains(':') {
+            return Self::parse_time(token).map(AnchorSource::Time);
+        }
+        match token.parse::<usize>() {
+            Ok(line) if line > 0 => Ok(AnchorSource::Line(line - 1)),
+            _ => Err(ParserError::invalid_number(token)),
+        }
+    }
+
+    pub fn parse(line: &str) -> ParseRes<Option<Self>> {
+        let content = line.split(Self::COMMENT).next().unwrap_or_default();
+        let content = Self::SEPARATORS
+            .iter()
+            .fold(content.to_string(), |acc, sep| acc.replace(sep, " "));
+        let tokens: Vec<&str> = content.split_whitespace().collect();
+        match tokens.as_slice() {
+            [] => Ok(None),
+            [source, target] => Ok(Some(Self::new(
+                Self::parse_source(source)?,
+                Self::parse_time(target)?,
+            ))),
+            _ => Err(ParserError::invalid_timestamp(content.trim())),
+        }
+    }
+
+    pub fn parse_lines(lines: &[String]) -> ParseRes<Vec<Self>> {
+        lines
+            .iter()
+            .enumerate()
+            .filter_map(|(i, line)| {
+                Self::parse(line)
+                    .map_err(|e| e.at_line(i + 1, line))
+                    .transpose()
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnchorMap {
+    points: Vec<(Timestamp, Timestamp)>,
+}
+
+impl AnchorMap {
+    pub fn new<L: Line>(anchors: &[Anchor], lines: &[&L]) -> ParseRes<Self> {
+        let mut points = anchors
+            .iter()
+            .map(|anchor| match anchor.source {
+                AnchorSource::Time(time) => Ok((time, anchor.target)),
+                AnchorSource::Line(idx) => lines
+                    .get(idx)
+                    .map(|line| (line.start(), anchor.target))
+                    .ok_or_else(|| ParserError::invalid_number(&(idx + 1).to_string())),
+            })
+            .collect::<ParseRes<Vec<_>>>()?;
+        points.sort_by_key(|point| point.0);
+        points.dedup_by_key(|point| point.0);
+        Ok(Self { points })
+    }
+
+    pub fn apply(&self, time: Timestamp) -> Timestamp {
+        let next = self.points.partition_point(|point| point.0 <= time);
+        match (
+            next.checked_sub(1).map(|i| self.points[i]),
+            self.points.get(next),
+        ) {
+            (Some((from_a, from_b)), Some(&(to_a, to_b))) => {
+                let progress = (time - from_a).millis() as f64 / (to_a - from_a).millis() as f64;
+                let span = (to_b - from_b).millis() as f64;
+                from_b + Timestamp::from_millis((progress * span).round() as i64)
+            }
+            (Some((a, b)), None) | (None, Some(&(a, b))) => time + (b - a),
+            (None, None) => time,
+        }
+    }
+
+    pub fn retime<'l, L: Line + 'l>(&self, lines: impl IntoIterator<Item = &'l mut L>) {
+        for line in lines {
+            let start = self.apply(line.start());
+            let end = self.apply(line.end()).max(start);
+            line.set_times(start, end);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
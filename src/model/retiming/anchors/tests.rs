@@ -0,0 +1,124 @@
+use crate::model::format::srt::line_srt::SrtLine;
+
+use super::*;
+
+struct TestCase {
+    name: &'static str,
+    input: &'static str,
+    expected: Option<(AnchorSource, i64)>,
+}
+
+static PARSE_CASES: &[TestCase] = &[
+    TestCase {
+        name: "line number and ASS time",
+        input: "12 0:01:23.45",
+        expected: Some((AnchorSource::Line(11), 83_450)),
+    },
+    TestCase {
+        name: "SRT times with arrow",
+        input: "00:01:20,000 -> 00:01:23,450",
+        expected: Some((AnchorSource::Time(Timestamp::from_millis(80_000)), 83_450)),
+    },
+    TestCase {
+        name: "short VTT times with fat arrow and comment",
+        input: "01:20.500 => 01:22.000 # cut eyecatch",
+        expected: Some((AnchorSource::Time(Timestamp::from_millis(80_500)), 82_000)),
+    },
+    TestCase {
+        name: "VTT cue timing arrow",
+        input: "00:01:20.000 --> 00:01:23.450",
+        expected: Some((AnchorSource::Time(Timestamp::from_millis(80_000)), 83_450)),
+    },
+    TestCase {
+        name: "comment only",
+        input: "# recap ends here",
+        expected: None,
+    },
+    TestCase {
+        name: "blank line",
+        input: "   ",
+        expected: None,
+    },
+];
+
+fn ms(millis: i64) -> Timestamp {
+    Timestamp::from_millis(millis)
+}
+
+fn run_test_case(test_case: &TestCase) -> ParseRes<()> {
+    let anchor = Anchor::parse(test_case.input)?;
+    let expected = test_case
+        .expected
+        .map(|(source, target)| Anchor::new(source, ms(target)));
+    assert_eq!(anchor, expected, "Failed at case: {}", test_case.name);
+    Ok(())
+}
+
+#[test]
+fn test_parses_anchor_lines() -> ParseRes<()> {
+    for test_case in PARSE_CASES {
+        run_test_case(test_case)?;
+    }
+    Ok(())
+}
+
+#[test]
+fn test_reports_invalid_anchor_lines() {
+    let lines = vec!["1 0:00:01.00".to_string(), "0 0:00:02.00".to_string()];
+    match Anchor::parse_lines(&lines) {
+        Err(ParserError::InvalidNumber { location, found }) => {
+            assert_eq!(found, "0");
+            assert_eq!(location.line, 2);
+        }
+        other => panic!("expected an invalid number, got {:?}", other),
+    }
+    match Anchor::parse("5 later") {
+        Err(ParserError::InvalidTimestamp { found, .. }) => assert_eq!(found, "later"),
+        other => panic!("expected an invalid timestamp, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_interpolates_between_anchors() -> ParseRes<()> {
+    let anchors = [
+        Anchor::new(AnchorSource::Time(ms(10_000)), ms(11_000)),
+        Anchor::new(AnchorSource::Time(ms(40_000)), ms(45_000)),
+    ];
+    let map = AnchorMap::new::<SrtLine>(&anchors, &[])?;
+    assert_eq!(map.apply(ms(4_000)), ms(5_000));
+    assert_eq!(map.apply(ms(10_000)), ms(11_000));
+    assert_eq!(map.apply(ms(25_000)), ms(28_000));
+    assert_eq!(map.apply(ms(40_000)), ms(45_000));
+    assert_eq!(map.apply(ms(50_000)), ms(55_000));
+    Ok(())
+}
+
+#[test]
+fn test_retimes_lines_from_line_anchors() -> ParseRes<()> {
+    let mut cues: Vec<SrtLine> = [(1_000, 2_000), (5_000, 6_000), (9_000, 10_000)]
+        .iter()
+        .map(|&(start, end)| SrtLine {
+            start: ms(start),
+            end: ms(end),
+            name: String::new(),
+            text: "Line".to_string(),
+        })
+        .collect();
+    let anchors = [
+        Anchor::new(AnchorSource::Line(2), ms(13_000)),
+        Anchor::new(AnchorSource::Line(0), ms(1_000)),
+    ];
+    let refs: Vec<&SrtLine> = cues.iter().collect();
+    let out_of_range = [Anchor::new(AnchorSource::Line(3), ms(0))];
+    assert!(AnchorMap::new(&out_of_range, &refs).is_err());
+    let map = AnchorMap::new(&anchors, &refs)?;
+    map.retime(cues.iter_mut());
+    let timings: Vec<(Timestamp, Timestamp)> = cues.iter().map(|c| (c.start, c.end)).collect();
+    let expected = vec![
+        (ms(1_000), ms(2_500)),
+        (ms(7_000), ms(8_500)),
+        (ms(13_000), ms(14_000)),
+    ];
+    assert_eq!(timings, expected);
+    Ok(())
+}
@@ -0,0 +1,179 @@
+use crate::model::{
+    aligner::{AlignStep, Aligner},
+    line::Line,
+    sync_options::{SyncMode, SyncOptions},
+    timestamp::Timestamp,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrameRateConversion {
+    pub source_fps: f64,
+    pub target_fps: f64,
+    pub pivot: Timestamp,
+}
+
+impl FrameRateConversion {
+    pub fn new(source_fps: f64, target_fps: f64) -> Self {
+        Self {
+            source_fps,
+            target_fps,
+            pivot: Timestamp::ZERO,
+        }
+    }
+
+    pub fn with_pivot(mut self, pivot: Timestamp) -> Self {
+        self.pivot = pivot;
+        self
+    }
+
+    pub fn factor(&self) -> f64 {
+        self.source_fps / self.target_fps
+    }
+
+    pub fn apply(&self, time: Timestamp) -> Timestamp {
+        let scaled = (time - self.pivot).millis() as f64 * self.factor();
+        self.pivot + Timestamp::from_millis(scaled.round() as i64)
+    }
+
+    pub fn retime<'l, L: Line + 'l>(&self, lines: impl IntoIterator<Item = &'l mut L>) {
+        for line in lines {
+            let (start, end) = (self.apply(line.start()), self.apply(line.end()));
+            line.set_times(start, end);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FrameRateMode {
+    Keep,
+    Suggest,
+    Auto,
+    Convert(FrameRateConversion),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpeedupDetection {
+    pub conversion: FrameRateConversion,
+    pub measured: Option<f64>,
+    pub applied: bool,
+}
+
+pub struct SpeedupDetector;
+
+impl SpeedupDetector {
+    const WINDOW: usize = 8;
+    const MIN_SAMPLES: usize = 3;
+    const MIN_INTERVAL: Timestamp = Timestamp::from_millis(2000);
+    const MAX_DEVIATION: f64 = 0.01;
+    const RATES: [f64; 3] = [Timestamp::FILM_FRAME_RATE, 24.0, 25.0];
+
+    const MAX_OUTLIER: f64 = 0.05;
+
+    fn pairs<L: Line>(a: &[&L], b: &[&L]) -> Vec<(Timestamp, Timestamp)> {
+        let aligner = Aligner::new(SyncOptions::new().with_mode(SyncMode::Timing));
+        aligner
+            .run(a, b)
+            .into_iter()
+            .filter_map(|step| match step {
+                AlignStep::Match(i, j) => Some((a[i].start(), b[j].start())),
+                AlignStep::OnlyA(_) | AlignStep::OnlyB(_) => None,
+            })
+            .collect()
+    }
+
+    fn ratios<L: Line>(a: &[&L], b: &[&L]) -> Vec<f64> {
+        let pairs = Self::pairs(a, b);
+        pairs
+            .windows(Self::WINDOW + 1)
+            .filter_map(|window| {
+                let (first, last) = (window[0], window[Self::WINDOW]);
+                let span_a = last.0 - first.0;
+                let span_b = last.1 - first.1;
+                match span_a >= Self::MIN_INTERVAL && span_b > Timestamp::ZERO {
+                    true => Some(span_b.millis() as f64 / span_a.millis() as f64),
+                    false => None,
+                }
+            })
+            .collect()
+    }
+
+    pub fn measure<L: Line>(a: &[&L], b: &[&L]) -> Option<f64> {
+        let mut ratios = Self::ratios(a, b);
+        if ratios.len() < Self::MIN_SAMPLES {
+            return None;
+        }
+        ratios.sort_by(f64::total_cmp);
+        let median = ratios[ratios.len() / 2];
+        let inliers: Vec<f64> = ratios
+            .into_iter()
+            .filter(|ratio| (ratio / median - 1.0).abs() <= Self::MAX_OUTLIER)
+            .collect();
+        match inliers.len() >= Self::MIN_SAMPLES {
+            true => Some(inliers.iter().sum::<f64>() / inliers.len() as f64),
+            false => None,
+        }
+    }
+
+    fn candidates() -> impl Iterator<Item = FrameRateConversion> {
+        Self::RATES.into_iter().flat_map(|source| {
+            Self::RATES
+                .into_iter()
+                .map(move |target| FrameRateConversion::new(source, target))
+        })
+    }
+
+    pub fn detect<L: Line>(a: &[&L], b: &[&L]) -> Option<SpeedupDetection> {
+        let measured = Self::measure(a, b)?;
+        let deviation = |c: &FrameRateConversion| (c.factor() / measured - 1.0).abs();
+        let best = Self::candidates()
+            .filter(|c| c.source_fps != c.target_fps)
+            .min_by(|x, y| deviation(x).total_cmp(&deviation(y)))?;
+        let unchanged = (measured - 1.0).abs();
+        match deviation(&best) < Self::MAX_DEVIATION && 2.0 * deviation(&best) < unchanged {
+            true => Some(SpeedupDetection {
+                conversion: best,
+                measured: Some(measured),
+                applied: false,
+            }),
+            false => None,
+        }
+    }
+}
+
+impl FrameRateMode {
+    pub fn run<L: Line>(
+        self,
+        a: &mut [L],
+        b: &[&L],
+        is_scene: impl Fn(&L) -> bool,
+    ) -> Option<SpeedupDetection> {
+        let detection = {
+            let a: Vec<&L> = a.iter().collect();
+            let normal_b: Vec<&L> = b.iter().filter(|line| !is_scene(line)).copied().collect();
+            match self {
+                Self::Keep => None,
+                Self::Suggest => SpeedupDetector::detect(&a, &normal_b),
+                Self::Auto => {
+                    SpeedupDetector::detect(&a, &normal_b).map(|detection| SpeedupDetection {
+                        applied: true,
+                        ..detection
+                    })
+                }
+                Self::Convert(conversion) => Some(SpeedupDetection {
+                    conversion,
+                    measured: SpeedupDetector::measure(&a, &normal_b),
+                    applied: true,
+                }),
+            }
+        };
+        if let Some(detection) = detection
+            && detection.applied
+        {
+            detection.conversion.retime(a.iter_mut());
+        }
+        detection
+    }
+}
+
+#[cfg(test)]
+mod tests;
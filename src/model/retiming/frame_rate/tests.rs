@@ -0,0 +1,144 @@
+use crate::model::format::srt::line_srt::SrtLine;
+
+use super::*;
+
+struct TestCase {
+    name: &'static str,
+    ratio: f64,
+    expected: Option<(f64, f64)>,
+}
+
+static DETECTION_CASES: &[TestCase] = &[
+    TestCase {
+        name: "PAL master against a film text",
+        ratio: Timestamp::FILM_FRAME_RATE / 25.0,
+        expected: Some((Timestamp::FILM_FRAME_RATE, 25.0)),
+    },
+    TestCase {
+        name: "PAL master against a 24fps text",
+        ratio: 24.0 / 25.0,
+        expected: Some((24.0, 25.0)),
+    },
+    TestCase {
+        name: "24fps master against a film text",
+        ratio: Timestamp::FILM_FRAME_RATE / 24.0,
+        expected: Some((Timestamp::FILM_FRAME_RATE, 24.0)),
+    },
+    TestCase {
+        name: "same frame rate with an offset",
+        ratio: 1.0,
+        expected: None,
+    },
+];
+
+fn cue(start: i64, end: i64, name: &str) -> SrtLine {
+    SrtLine {
+        start: Timestamp::from_millis(start),
+        end: Timestamp::from_millis(end),
+        name: name.to_string(),
+        text: "Line".to_string(),
+    }
+}
+
+fn text_a() -> Vec<SrtLine> {
+    (0..60)
+        .map(|i| cue(5_000 + i * 3_100, 7_000 + i * 3_100, ""))
+        .collect()
+}
+
+fn master_b(a: &[SrtLine], ratio: f64) -> Vec<SrtLine> {
+    let scale = |t: Timestamp| (t.millis() as f64 * ratio).round() as i64 + 1_500;
+    let mut b: Vec<SrtLine> = a
+        .iter()
+        .map(|line| cue(scale(line.start), scale(line.end), ""))
+        .collect();
+    b.insert(20, cue(1_000, 2_000, "ADDITIONAL SCENE"));
+    b
+}
+
+fn is_scene(line: &SrtLine) -> bool {
+    !line.name.is_empty()
+}
+
+fn run_test_case(test_case: &TestCase) {
+    let mut a = text_a();
+    let b = master_b(&a, test_case.ratio);
+    let refs_b: Vec<&SrtLine> = b.iter().collect();
+    let detection = FrameRateMode::Suggest.run(&mut a, &refs_b, is_scene);
+    let detected = detection.map(|d| (d.conversion.source_fps, d.conversion.target_fps));
+    assert_eq!(
+        detected, test_case.expected,
+        "Failed at case: {}",
+        test_case.name
+    );
+    assert!(
+        detection.is_none_or(|d| !d.applied),
+        "Failed at case: {}",
+        test_case.name
+    );
+    let starts = |lines: &[SrtLine]| lines.iter().map(|line| line.start).collect::<Vec<_>>();
+    assert_eq!(
+        starts(&a),
+        starts(&text_a()),
+        "Failed at case: {}",
+        test_case.name
+    );
+}
+
+#[test]
+fn test_detects_speedup() {
+    for test_case in DETECTION_CASES {
+        run_test_case(test_case);
+    }
+}
+
+#[test]
+fn test_ignores_extra_lines_in_b() {
+    let a = text_a();
+    let mut b = master_b(&a, Timestamp::FILM_FRAME_RATE / 25.0);
+    for index in (24..48).step_by(4).rev() {
+        let gap_start = b[index].end.millis() + 200;
+        b.insert(index + 1, cue(gap_start, gap_start + 500, ""));
+    }
+    let refs_a: Vec<&SrtLine> = a.iter().collect();
+    let normal_b: Vec<&SrtLine> = b.iter().filter(|line| !is_scene(line)).collect();
+    let detection = SpeedupDetector::detect(&refs_a, &normal_b);
+    let detected = detection.map(|d| (d.conversion.source_fps, d.conversion.target_fps));
+    assert_eq!(detected, Some((Timestamp::FILM_FRAME_RATE, 25.0)));
+}
+
+#[test]
+fn test_scales_around_pivot() {
+    let conversion =
+        FrameRateConversion::new(25.0, 24.0).with_pivot(Timestamp::from_millis(10_000));
+    assert_eq!(
+        conversion.apply(Timestamp::from_millis(10_000)),
+        Timestamp::from_millis(10_000)
+    );
+    assert_eq!(
+        conversion.apply(Timestamp::from_millis(20_000)),
+        Timestamp::from_millis(20_417)
+    );
+    assert_eq!(
+        conversion.apply(Timestamp::from_millis(4_000)),
+        Timestamp::from_millis(3_750)
+    );
+}
+
+#[test]
+fn test_auto_mode_converts_a() {
+    let mut a = text_a();
+    let b = master_b(&a, Timestamp::FILM_FRAME_RATE / 25.0);
+    let refs_b: Vec<&SrtLine> = b.iter().collect();
+    let detection = FrameRateMode::Auto.run(&mut a, &refs_b, is_scene);
+    assert!(detection.is_some_and(|d| d.applied));
+    let normal_b: Vec<&SrtLine> = b.iter().filter(|line| !is_scene(line)).collect();
+    for (line_a, line_b) in a.iter().zip(normal_b) {
+        let drift = line_b.start - line_a.start;
+        assert!(
+            (drift - Timestamp::from_millis(1_500)).abs() <= Timestamp::from_millis(2),
+            "unexpected drift {:?}",
+            drift
+        );
+    }
+}
@@ -0,0 +1,331 @@
+use crate::model::{
+    line::Line,
+    parse_error::{ParseRes, ParserError},
+    timestamp::Timestamp,
+};
+
+const COMMENT: char = '#';
+const BOM: char = '\u{feff}';
+
+fn header(lines: &[String]) -> String {
+    lines
+        .first()
+        .map(|line| line.trim_start_matches(BOM).trim().to_lowercase())
+        .unwrap_or_default()
+}
+
+fn entries(lines: &[String]) -> impl Iterator<Item = (usize, &str, &String)> {
+    lines.iter().enumerate().filter_map(|(i, line)| {
+        let content = line.split(COMMENT).next().unwrap_or_default().trim();
+        (!content.is_empty()).then_some((i + 1, content, line))
+    })
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrameTimes {
+    times: Vec<f64>,
+    ranges: Vec<(usize, usize, f64)>,
+    fps: f64,
+}
+
+impl FrameTimes {
+    const TIMECODES_V1: &'static str = "# timecode format v1";
+    const TIMECODES_V2: &'static str = "# timecode format v2";
+    const ASSUME: &'static str = "assume";
+    const MILLIS_PER_SECOND: f64 = 1_000.0;
+
+    pub fn new(fps: f64) -> Self {
+        Self {
+            times: Vec::new(),
+            ranges: Vec::new(),
+            fps,
+        }
+    }
+
+    fn fps(token: &str) -> ParseRes<f64> {
+        match token.trim().parse::<f64>() {
+            Ok(fps) if fps > 0.0 => Ok(fps),
+            _ => Err(ParserError::invalid_number(token.trim())),
+        }
+    }
+
+    fn frame(token: &str) -> ParseRes<usize> {
+        token
+            .trim()
+            .parse::<usize>()
+            .map_err(|_| ParserError::invalid_number(token.trim()))
+    }
+
+    fn range(content: &str) -> ParseRes<(usize, usize, f64)> {
+        match content.split(',').collect::<Vec<_>>().as_slice() {
+            [start, end, fps] => {
+                let (start, end) = (Self::frame(start)?, Self::frame(end)?);
+                match start <= end {
+                    true => Ok((start, end, Self::fps(fps)?)),
+                    false => Err(ParserError::invalid_number(content)),
+                }
+            }
+            _ => Err(ParserError::invalid_number(content)),
+        }
+    }
+
+    fn parse_v1(lines: &[String]) -> ParseRes<Self> {
+        let mut assumed = None;
+        let mut ranges = Vec::new();
+        for (n, content, line) in entries(lines) {
+            let entry = match content.to_lowercase().strip_prefix(Self::ASSUME) {
+                Some(fps) => Self::fps(fps).map(|fps| assumed = Some(fps)),
+                None => Self::range(content).map(|range| ranges.push(range)),
+            };
+            entry.map_err(|e| e.at_line(n, line))?;
+        }
+        let assumed = assumed.ok_or_else(|| {
+            ParserError::invalid_keyframes("timecode v1 file has no 'Assume' line")
+        })?;
+        ranges.sort_by_key(|range| range.0);
+        if ranges.windows(2).any(|pair| pair[1].0 <= pair[0].1) {
+            return Err(ParserError::invalid_keyframes(
+                "timecode v1 file has overlapping frame ranges",
+            ));
+        }
+        Ok(Self {
+            times: Vec::new(),
+            ranges,
+            fps: assumed,
+        })
+    }
+
+    fn parse_v2(lines: &[String]) -> ParseRes<Self> {
+        let times = entries(lines)
+            .map(|(n, content, line)| {
+                content
+                    .parse::<f64>()
+                    .map_err(|_| ParserError::invalid_number(content).at_line(n, line))
+            })
+            .collect::<ParseRes<Vec<f64>>>()?;
+        let fps = match times.as_slice() {
+            [.., previous, last] if last > previous => Self::MILLIS_PER_SECOND / (last - previous),
+            _ => {
+                return Err(ParserError::invalid_keyframes(
+                    "timecode v2 file needs at least two increasing timestamps",
+                ));
+            }
+        };
+        Ok(Self {
+            times,
+            ranges: Vec::new(),
+            fps,
+        })
+    }
+
+    pub fn parse_timecodes(lines: &[String]) -> ParseRes<Self> {
+        match header(lines) {
+            h if h.starts_with(Self::TIMECODES_V1) => Self::parse_v1(lines),
+            h if h.starts_with(Self::TIMECODES_V2) => Self::parse_v2(lines),
+            _ => Err(ParserError::invalid_keyframes(
+                "unknown timecode format, expected v1 or v2",
+            )),
+        }
+    }
+
+    fn range_time(&self, frame: usize) -> f64 {
+        let duration = |frames: usize, fps: f64| frames as f64 * Self::MILLIS_PER_SECOND / fps;
+        let mut time = 0.0;
+        let mut next = 0;
+        for &(start, end, fps) in self.ranges.iter().take_while(|range| range.0 < frame) {
+            let last = (end + 1).min(frame);
+            time += duration(start - next, self.fps) + duration(last - start, fps);
+            next = last;
+        }
+        time + duration(frame - next, self.fps)
+    }
+
+    fn time(&self, frame: usize) -> f64 {
+        match (self.times.get(frame), self.times.last()) {
+            (Some(&time), _) => time,
+            (None, Some(&last)) => {
+                let extra = (frame + 1 - self.times.len()) as f64;
+                last + extra * Self::MILLIS_PER_SECOND / self.fps
+            }
+            (None, None) => self.range_time(frame),
+        }
+    }
+
+    pub fn cut(&self, frame: usize) -> Timestamp {
+        let time = match frame {
+            0 => 0.0,
+            _ => (self.time(frame - 1) + self.time(frame)) / 2.0,
+        };
+        Timestamp::from_millis(time.round() as i64)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Keyframes {
+    frames: Vec<usize>,
+    fps: Option<f64>,
+}
+
+impl Keyframes {
+    const XVID: [&'static str; 3] = [
+        "# xvid 2pass stat file",
+        "# ffmpeg 2-pass log file, using xvid codec",
+        "# avconv 2-pass log file, using xvid codec",
+    ];
+    const DIVX: &'static str = "##map version";
+    const X264: &'static str = "#options:";
+    const X264_TYPE: &'static str = "type:";
+    const DIVX_TYPES: [char; 3] = ['I', 'P', 'B'];
+    const FPS: &'static str = "fps";
+
+    pub fn new(mut frames: Vec<usize>) -> Self {
+        frames.sort();
+        frames.dedup();
+        Self { frames, fps: None }
+    }
+
+    pub fn with_fps(mut self, fps: Option<f64>) -> Self {
+        self.fps = fps;
+        self
+    }
+
+    pub fn frames(&self) -> &[usize] {
+        &self.frames
+    }
+
+    pub fn fps(&self) -> Option<f64> {
+        self.fps
+    }
+
+    fn divx_type(line: &str) -> Option<char> {
+        Self::DIVX_TYPES.into_iter().find(|&c| line.contains(c))
+    }
+
+    fn x264_type(line: &str) -> Option<char> {
+        let (_, rest) = line.split_once(Self::X264_TYPE)?;
+        rest.chars().next()
+    }
+
+    fn count_frames(lines: &[String], frame_type: impl Fn(&str) -> Option<char>) -> Vec<usize> {
+        let mut count = 0;
+        let mut frames = Vec::new();
+        for line in lines {
+            match frame_type(line).map(|c| c.to_ascii_lowercase()) {
+                Some('i' | 'k') => {
+                    frames.push(count);
+                    count += 1;
+                }
+                Some('p' | 'b') => count += 1,
+                _ => {}
+            }
+        }
+        frames
+    }
+
+    fn list_fps(lines: &[String]) -> ParseRes<Option<f64>> {
+        let Some((n, content, line)) =
+            entries(lines).find(|(_, content, _)| content.to_lowercase().starts_with(Self::FPS))
+        else {
+            return Ok(None);
+        };
+        let value = content[Self::FPS.len()..].trim();
+        match value.parse::<f64>() {
+            Ok(fps) if fps > 0.0 => Ok(Some(fps)),
+            Ok(_) => Ok(None),
+            Err(_) => Err(ParserError::invalid_number(value).at_line(n, line)),
+        }
+    }
+
+    fn parse_list(lines: &[String]) -> ParseRes<Vec<usize>> {
+        entries(lines)
+            .filter(|(_, content, _)| !content.to_lowercase().starts_with(Self::FPS))
+            .map(|(n, content, line)| {
+                content
+                    .parse::<usize>()
+                    .map_err(|_| ParserError::invalid_number(content).at_line(n, line))
+            })
+            .collect()
+    }
+
+    pub fn parse(lines: &[String]) -> ParseRes<Self> {
+        let mut fps = None;
+        let frames = match header(lines) {
+            h if Self::XVID.iter().any(|xvid| h.starts_with(xvid)) => {
+                Self::count_frames(lines, |line| line.chars().next())
+            }
+            h if h.starts_with(Self::DIVX) => Self::count_frames(lines, Self::divx_type),
+            h if h.starts_with(Self::X264) => Self::count_frames(lines, Self::x264_type),
+            _ => {
+                fps = Self::list_fps(lines)?;
+                Self::parse_list(lines)?
+            }
+        };
+        match frames.is_empty() {
+            true => Err(ParserError::invalid_keyframes("file contains no keyframes")),
+            false => Ok(Self::new(frames).with_fps(fps)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SnapSummary {
+    pub keyframes: usize,
+    pub starts: usize,
+    pub ends: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyframeSnap {
+    cuts: Vec<Timestamp>,
+    window: Timestamp,
+}
+
+impl KeyframeSnap {
+    pub const DEFAULT_WINDOW: Timestamp = Timestamp::from_millis(250);
+
+    pub fn new(keyframes: &Keyframes, times: &FrameTimes) -> Self {
+        Self {
+            cuts: keyframes.frames().iter().map(|&f| times.cut(f)).collect(),
+            window: Self::DEFAULT_WINDOW,
+        }
+    }
+
+    pub fn with_window(mut self, window: Timestamp) -> Self {
+        self.window = window;
+        self
+    }
+
+    pub fn snap(&self, time: Timestamp) -> Timestamp {
+        let next = self.cuts.partition_point(|&cut| cut < time);
+        [next.checked_sub(1), Some(next)]
+            .into_iter()
+            .flatten()
+            .filter_map(|i| self.cuts.get(i).copied())
+            .filter(|&cut| (cut - time).abs() <= self.window)
+            .min_by_key(|&cut| (cut - time).abs())
+            .unwrap_or(time)
+    }
+
+    pub fn retime<'l, L: Line + 'l>(
+        &self,
+        lines: impl IntoIterator<Item = &'l mut L>,
+    ) -> SnapSummary {
+        let mut summary = SnapSummary {
+            keyframes: self.cuts.len(),
+            ..SnapSummary::default()
+        };
+        for line in lines {
+            let (start, end) = (self.snap(line.start()), self.snap(line.end()));
+            if end <= start {
+                continue;
+            }
+            summary.starts += (start != line.start()) as usize;
+            summary.ends += (end != line.end()) as usize;
+            line.set_times(start, end);
+        }
+        summary
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,236 @@
+use crate::model::format::srt::line_srt::SrtLine;
+
+use super::*;
+
+struct TestCase {
+    name: &'static str,
+    input: &'static [&'static str],
+    expected: Option<&'static [usize]>,
+}
+
+static PARSE_CASES: &[TestCase] = &[
+    TestCase {
+        name: "xvid pass log",
+        input: &[
+            "# XviD 2pass stat file",
+            "# frame type, quant, length",
+            "i 2 4096",
+            "p 3 812",
+            "b 4 210",
+            "i 2 3900",
+            "p 3 640",
+        ],
+        expected: Some(&[0, 3]),
+    },
+    TestCase {
+        name: "divx pass log",
+        input: &[
+            "##map version 1",
+            "frame 0 I 48211",
+            "frame 1 P 9120",
+            "frame 2 B 2048",
+            "frame 3 I 44012",
+        ],
+        expected: Some(&[0, 3]),
+    },
+    TestCase {
+        name: "x264 stats",
+        input: &[
+            "#options: 1920x1080 fps=24000/1001 timebase=1001/24000",
+            "in:0 out:0 type:I dur:2 cpbdur:2 q:20.00",
+            "in:1 out:1 type:P dur:2 cpbdur:2 q:22.00",
+            "in:3 out:2 type:b dur:2 cpbdur:2 q:24.00",
+            "in:2 out:3 type:K dur:2 cpbdur:2 q:20.00",
+            "in:4 out:4 type:i dur:2 cpbdur:2 q:20.00",
+        ],
+        expected: Some(&[0, 3, 4]),
+    },
+    TestCase {
+        name: "aegisub keyframe list",
+        input: &["# keyframe format v1", "fps 0", "0", "120", "48"],
+        expected: Some(&[0, 48, 120]),
+    },
+    TestCase {
+        name: "plain frame list with comments",
+        input: &["0", "", "250 # after the opening", "1032"],
+        expected: Some(&[0, 250, 1032]),
+    },
+    TestCase {
+        name: "list with an invalid frame",
+        input: &["0", "twelve"],
+        expected: None,
+    },
+    TestCase {
+        name: "pass log without frames",
+        input: &["# XviD 2pass stat file"],
+        expected: None,
+    },
+];
+
+struct SnapCase {
+    name: &'static str,
+    line: (i64, i64),
+    expected: (i64, i64),
+}
+
+static SNAP_CASES: &[SnapCase] = &[
+    SnapCase {
+        name: "start a few frames after a cut",
+        line: (10_100, 12_000),
+        expected: (9_979, 12_000),
+    },
+    SnapCase {
+        name: "end a few frames before a cut",
+        line: (15_000, 19_900),
+        expected: (15_000, 19_979),
+    },
+    SnapCase {
+        name: "start outside the window",
+        line: (10_300, 12_000),
+        expected: (10_300, 12_000),
+    },
+    SnapCase {
+        name: "both ends on the same cut",
+        line: (9_900, 10_050),
+        expected: (9_900, 10_050),
+    },
+];
+
+fn to_lines(lines: &[&str]) -> Vec<String> {
+    lines.iter().map(|s| s.to_string()).collect()
+}
+
+fn cue(start: i64, end: i64) -> SrtLine {
+    SrtLine {
+        start: Timestamp::from_millis(start),
+        end: Timestamp::from_millis(end),
+        name: String::new(),
+        text: "Line".to_string(),
+    }
+}
+
+fn run_test_case(test_case: &TestCase) {
+    let result = Keyframes::parse(&to_lines(test_case.input)).ok();
+    let frames = result.as_ref().map(|keyframes| keyframes.frames());
+    assert_eq!(
+        frames, test_case.expected,
+        "Failed at case: {}",
+        test_case.name
+    );
+}
+
+#[test]
+fn test_parse_keyframes() {
+    for test_case in PARSE_CASES {
+        run_test_case(test_case);
+    }
+}
+
+#[test]
+fn test_cuts_from_frame_rate() {
+    let times = FrameTimes::new(25.0);
+    assert_eq!(times.cut(0), Timestamp::ZERO);
+    assert_eq!(times.cut(25), Timestamp::from_millis(980));
+}
+
+#[test]
+fn test_cuts_from_timecodes() -> ParseRes<()> {
+    let v2 =
+        FrameTimes::parse_timecodes(&to_lines(&["# timecode format v2", "0", "40", "80", "130"]))?;
+    assert_eq!(v2.cut(3), Timestamp::from_millis(105));
+    assert_eq!(v2.cut(5), Timestamp::from_millis(205));
+    let v1 =
+        FrameTimes::parse_timecodes(&to_lines(&["# timecode format v1", "Assume 25", "2,3,50"]))?;
+    assert_eq!(v1.cut(4), Timestamp::from_millis(110));
+    assert_eq!(v1.cut(5), Timestamp::from_millis(140));
+    Ok(())
+}
+
+#[test]
+fn test_long_timecode_ranges() -> ParseRes<()> {
+    let v1 = FrameTimes::parse_timecodes(&to_lines(&[
+        "# timecode format v1",
+        "Assume 25",
+        "0,999999999,50",
+        "1000000010,1000000019,10",
+    ]))?;
+    assert_eq!(v1.cut(1), Timestamp::from_millis(10));
+    assert_eq!(
+        v1.cut(1_000_000_000),
+        Timestamp::from_millis(19_999_999_990)
+    );
+    assert_eq!(
+        v1.cut(1_000_000_012),
+        Timestamp::from_millis(20_000_000_550)
+    );
+    Ok(())
+}
+
+#[test]
+fn test_keeps_keyframe_list_fps() -> ParseRes<()> {
+    let keyframes = Keyframes::parse(&to_lines(&[
+        "# keyframe format v1",
+        "fps 23.976",
+        "0",
+        "48",
+    ]))?;
+    assert_eq!(keyframes.fps(), Some(23.976));
+    let keyframes = Keyframes::parse(&to_lines(&["# keyframe format v1", "fps 0", "0"]))?;
+    assert_eq!(keyframes.fps(), None);
+    assert!(Keyframes::parse(&to_lines(&["fps fast", "0"])).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_rejects_invalid_timecodes() {
+    let cases: &[&[&str]] = &[
+        &["# timecode format v1", "0,10,25"],
+        &["# timecode format v1", "Assume 25", "0,10,50", "5,20,30"],
+        &["# timecode format v1", "Assume 25", "10,0,50"],
+        &["# timecode format v2", "0"],
+        &["# timecode format v2", "0", "forty"],
+        &["0", "40"],
+    ];
+    for case in cases {
+        assert!(
+            FrameTimes::parse_timecodes(&to_lines(case)).is_err(),
+            "Failed at case: {:?}",
+            case
+        );
+    }
+}
+
+#[test]
+fn test_snaps_to_cuts() {
+    let snap = KeyframeSnap::new(&Keyframes::new(vec![0, 240, 480]), &FrameTimes::new(24.0));
+    for test_case in SNAP_CASES {
+        let mut lines = [cue(test_case.line.0, test_case.line.1)];
+        snap.retime(lines.iter_mut());
+        let result = (lines[0].start.millis(), lines[0].end.millis());
+        assert_eq!(
+            result, test_case.expected,
+            "Failed at case: {}",
+            test_case.name
+        );
+    }
+}
+
+#[test]
+fn test_snap_summary() {
+    let snap = KeyframeSnap::new(&Keyframes::new(vec![240, 480]), &FrameTimes::new(24.0))
+        .with_window(Timestamp::from_millis(100));
+    let mut lines = [
+        cue(10_050, 12_000),
+        cue(15_000, 19_950),
+        cue(10_300, 20_500),
+    ];
+    let summary = snap.retime(lines.iter_mut());
+    assert_eq!(
+        summary,
+        SnapSummary {
+            keyframes: 2,
+            starts: 1,
+            ends: 1,
+        }
+    );
+}
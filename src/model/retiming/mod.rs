@@ -0,0 +1,3 @@
+pub mod anchors;
+pub mod frame_rate;
+pub mod keyframes;
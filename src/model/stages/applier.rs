@@ -0,0 +1,35 @@
+use crate::model::{parse_error::ParseRes, stages::CueFormat, synchronizer::SceneMarker};
+
+pub struct SceneApplier<F> {
+    format: F,
+}
+
+impl<F: CueFormat + SceneMarker<F::Cue>> SceneApplier<F> {
+    pub fn new() -> Self
+    where
+        F: Default,
+    {
+        Self {
+            format: F::default(),
+        }
+    }
+
+    pub fn run(&self, ls: &[String], ts: &[String]) -> ParseRes<Vec<String>> {
+        let mut doc = self.format.parse(ls)?;
+        let scenes = self
+            .format
+            .cues_mut(&mut doc)
+            .filter(|cue| self.format.is_scene_line(cue));
+        for (cue, translation) in scenes.zip(ts) {
+            self.format
+                .set_text(cue, self.format.restore_line_breaks(translation));
+        }
+        Ok(self.format.to_lines(&doc))
+    }
+}
+
+impl<F: CueFormat + SceneMarker<F::Cue> + Default> Default for SceneApplier<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
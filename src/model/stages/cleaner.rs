@@ -0,0 +1,54 @@
+use crate::model::{line::Line, parse_error::ParseRes, stages::CueFormat};
+
+pub struct Cleaner<F> {
+    format: F,
+}
+
+impl<F: CueFormat> Cleaner<F> {
+    pub fn new() -> Self
+    where
+        F: Default,
+    {
+        Self {
+            format: F::default(),
+        }
+    }
+
+    pub fn should_skip_line(&self, cue: &F::Cue) -> bool {
+        let is_empty = self.format.is_text_empty(cue);
+        let too_many_tags = self.format.has_excessive_tags(cue.text());
+        is_empty || too_many_tags
+    }
+
+    fn handle_duplicate(&self, key: String, last_key: &mut Option<String>) -> bool {
+        match Some(&key) == last_key.as_ref() {
+            true => false,
+            false => {
+                *last_key = Some(key);
+                true
+            }
+        }
+    }
+
+    fn should_keep_line(&self, cue: &F::Cue, last_key: &mut Option<String>) -> bool {
+        match self.should_skip_line(cue) {
+            true => false,
+            false => self.handle_duplicate(self.format.build_line_key(cue), last_key),
+        }
+    }
+
+    pub fn run(&self, lines: &mut Vec<String>) -> ParseRes<()> {
+        let mut last_key: Option<String> = None;
+        let mut doc = self.format.parse(lines)?;
+        self.format
+            .retain_cues(&mut doc, |cue| self.should_keep_line(cue, &mut last_key));
+        *lines = self.format.to_lines(&doc);
+        Ok(())
+    }
+}
+
+impl<F: CueFormat + Default> Default for Cleaner<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
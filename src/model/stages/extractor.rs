@@ -0,0 +1,34 @@
+use crate::model::{
+    line::Line, parse_error::ParseRes, stages::CueFormat, synchronizer::SceneMarker,
+};
+
+pub struct SceneExtractor<F> {
+    format: F,
+}
+
+impl<F: CueFormat + SceneMarker<F::Cue>> SceneExtractor<F> {
+    pub fn new() -> Self
+    where
+        F: Default,
+    {
+        Self {
+            format: F::default(),
+        }
+    }
+
+    pub fn run(&self, lines: &[String]) -> ParseRes<Vec<String>> {
+        let doc = self.format.parse(lines)?;
+        Ok(self
+            .format
+            .cues(&doc)
+            .filter(|cue| self.format.is_scene_line(cue))
+            .map(|cue| self.format.to_single_line(cue.text()))
+            .collect())
+    }
+}
+
+impl<F: CueFormat + SceneMarker<F::Cue> + Default> Default for SceneExtractor<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
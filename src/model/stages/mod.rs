@@ -0,0 +1,23 @@
+use crate::model::{line::Line, parse_error::ParseRes};
+
+pub mod applier;
+pub mod cleaner;
+pub mod extractor;
+pub mod sorter;
+
+pub trait CueFormat {
+    type Cue: Line + Clone + 'static;
+    type Document;
+
+    fn parse(&self, lines: &[String]) -> ParseRes<Self::Document>;
+    fn to_lines(&self, doc: &Self::Document) -> Vec<String>;
+    fn cues<'d>(&self, doc: &'d Self::Document) -> impl Iterator<Item = &'d Self::Cue>;
+    fn cues_mut<'d>(&self, doc: &'d mut Self::Document) -> impl Iterator<Item = &'d mut Self::Cue>;
+    fn retain_cues(&self, doc: &mut Self::Document, keep: impl FnMut(&Self::Cue) -> bool);
+    fn set_text(&self, cue: &mut Self::Cue, text: String);
+    fn build_line_key(&self, cue: &Self::Cue) -> String;
+    fn is_text_empty(&self, cue: &Self::Cue) -> bool;
+    fn has_excessive_tags(&self, text: &str) -> bool;
+    fn to_single_line(&self, text: &str) -> String;
+    fn restore_line_breaks(&self, text: &str) -> String;
+}
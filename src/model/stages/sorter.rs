@@ -0,0 +1,36 @@
+use crate::model::{line::Line, parse_error::ParseRes, stages::CueFormat, timestamp::Timestamp};
+
+pub struct Sorter<F> {
+    format: F,
+}
+
+impl<F: CueFormat> Sorter<F> {
+    pub fn new() -> Self
+    where
+        F: Default,
+    {
+        Self {
+            format: F::default(),
+        }
+    }
+
+    fn cue_sort_key(cue: &F::Cue) -> Timestamp {
+        cue.start()
+    }
+
+    pub fn run(&self, lines: &[String]) -> ParseRes<Vec<String>> {
+        let mut doc = self.format.parse(lines)?;
+        let mut cues: Vec<F::Cue> = self.format.cues(&doc).cloned().collect();
+        cues.sort_by_key(Self::cue_sort_key);
+        for (slot, cue) in self.format.cues_mut(&mut doc).zip(cues) {
+            *slot = cue;
+        }
+        Ok(self.format.to_lines(&doc))
+    }
+}
+
+impl<F: CueFormat + Default> Default for Sorter<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,6 +1,7 @@
 use std::path::Path;
 
 use crate::model::repository::SubtitleRepository;
+use crate::model::repository::encoding::FileEncoding;
 use crate::model::repository::repository_error::{RepoRes, RepositoryError};
 
 pub struct SubtitlePersistence<R: SubtitleRepository> {
@@ -10,6 +11,7 @@ pub struct SubtitlePersistence<R: SubtitleRepository> {
 impl<R: SubtitleRepository> SubtitlePersistence<R> {
     const TRANSLATIONS_PATH: &'static str = "translations.txt";
     const TO_TRANSLATE_PATH: &'static str = "to_translate.txt";
+    pub const CONFIG_PATH: &'static str = "durazubs.conf";
 
     pub fn new(repository: R) -> Self {
         Self { repository }
@@ -38,11 +40,52 @@ impl<R: SubtitleRepository> SubtitlePersistence<R> {
         self.repository.save(Self::TO_TRANSLATE_PATH, lines)
     }
 
+    pub fn load_config(&self) -> RepoRes<Vec<String>> {
+        match Path::new(Self::CONFIG_PATH).is_file() {
+            true => self.repository.get_all(Self::CONFIG_PATH),
+            false => Ok(Vec::new()),
+        }
+    }
+
     pub fn load_subs(&self, path: &str) -> RepoRes<Vec<String>> {
         self.repository.get_all(path)
     }
 
-    pub fn save_subs(&self, path: &str, lines: &[String]) -> RepoRes<()> {
+    pub fn load_subs_with_encoding(&self, path: &str) -> RepoRes<(Vec<String>, FileEncoding)> {
+        self.repository.get_all_with_encoding(path)
+    }
+
+    pub fn save_subs(&self, path: &str, lines: &[String], encoding: FileEncoding) -> RepoRes<()> {
+        self.repository.save_with_encoding(path, lines, encoding)
+    }
+
+    pub fn load_anchors(&self, path: &str) -> RepoRes<Vec<String>> {
+        self.repository.get_all(path)
+    }
+
+    pub fn load_keyframes(&self, path: &str) -> RepoRes<Vec<String>> {
+        self.repository.get_all(path)
+    }
+
+    pub fn load_timecodes(&self, path: &str) -> RepoRes<Vec<String>> {
+        self.repository.get_all(path)
+    }
+
+    pub fn load_audio(&self, path: &str) -> RepoRes<Vec<u8>> {
+        self.repository.get_bytes(path)
+    }
+
+    pub fn save_report(&self, path: &str, lines: &[String]) -> RepoRes<()> {
         self.repository.save(path, lines)
     }
+
+    pub fn load_attachment(&self, path: &str) -> RepoRes<Vec<u8>> {
+        self.repository.get_bytes(path)
+    }
+
+    pub fn save_attachment(&self, dir: &str, name: &str, bytes: &[u8]) -> RepoRes<()> {
+        let file_name = Path::new(name).file_name().unwrap_or_default();
+        let path = Path::new(dir).join(file_name);
+        self.repository.save_bytes(&path.to_string_lossy(), bytes)
+    }
 }
@@ -1,9 +1,35 @@
+use crate::model::{
+    format::ass::{
+        document::attachments::Attachment, exporter::ExportTarget, importer::ImportSource,
+    },
+    retiming::{
+        anchors::Anchor,
+        keyframes::{KeyframeSnap, SnapSummary},
+    },
+    sync_result::SyncResult,
+    timestamp::Timestamp,
+};
+
 pub type ProcRes<T, E> = std::result::Result<T, E>;
 
 pub trait SubtitleProcessor {
     type Error: std::fmt::Display + std::fmt::Debug;
 
-    fn synchronize(&self, l_a: &[String], l_b: &[String]) -> ProcRes<Vec<String>, Self::Error>;
+    fn synchronize(
+        &self,
+        l_a: &[String],
+        l_b: &[String],
+    ) -> ProcRes<SyncResult<Vec<String>>, Self::Error>;
+
+    fn retime(&self, lines: &[String], anchors: &[Anchor]) -> ProcRes<Vec<String>, Self::Error>;
+
+    fn timings(&self, lines: &[String]) -> ProcRes<Vec<(Timestamp, Timestamp)>, Self::Error>;
+
+    fn snap_keyframes(
+        &self,
+        lines: &[String],
+        snap: &KeyframeSnap,
+    ) -> ProcRes<(Vec<String>, SnapSummary), Self::Error>;
 
     fn get_lines_to_translate(&self, lines: &mut Vec<String>) -> ProcRes<Vec<String>, Self::Error>;
 
@@ -17,7 +43,32 @@ pub trait SubtitleProcessor {
 
     fn preprocessing(&self, lines: &mut Vec<String>) -> ProcRes<Vec<String>, Self::Error>;
 
-    fn has_additional_scene(&self, lines: &Vec<String>) -> ProcRes<bool, Self::Error>;
+    fn has_additional_scene(&self, lines: &[String]) -> ProcRes<bool, Self::Error>;
+
+    fn apply_style(&self, lines: &[String]) -> ProcRes<Vec<String>, Self::Error>;
+
+    fn attachments(&self, _lines: &[String]) -> ProcRes<Vec<Attachment>, Self::Error> {
+        Ok(Vec::new())
+    }
+
+    fn embed_attachments(
+        &self,
+        lines: &[String],
+        _attachments: &[Attachment],
+    ) -> ProcRes<Vec<String>, Self::Error> {
+        Ok(lines.to_vec())
+    }
+
+    fn export(
+        &self,
+        lines: &[String],
+        _target: ExportTarget,
+        _styles: &[String],
+    ) -> ProcRes<Vec<String>, Self::Error> {
+        Ok(lines.to_vec())
+    }
 
-    fn apply_style(&self, lines: &Vec<String>) -> ProcRes<Vec<String>, Self::Error>;
+    fn import(&self, lines: &[String], _source: ImportSource) -> ProcRes<Vec<String>, Self::Error> {
+        Ok(lines.to_vec())
+    }
 }
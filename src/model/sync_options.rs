@@ -0,0 +1,147 @@
+use crate::model::{
+    parse_error::{ParseRes, ParserError},
+    retiming::frame_rate::FrameRateMode,
+    timestamp::Timestamp,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SyncMode {
+    Scenes,
+    Timing,
+    TimingAndText,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SyncOptions {
+    pub mode: SyncMode,
+    pub tolerance: Timestamp,
+    pub anchor_line: usize,
+    pub shift_end: bool,
+    pub frame_rate: FrameRateMode,
+}
+
+impl SyncOptions {
+    const DEFAULT_TOLERANCE: Timestamp = Timestamp::from_millis(1000);
+    const COMMENT: char = '#';
+    const SEPARATOR: char = '=';
+
+    pub fn new() -> Self {
+        Self {
+            mode: SyncMode::Scenes,
+            tolerance: Self::DEFAULT_TOLERANCE,
+            anchor_line: 0,
+            shift_end: true,
+            frame_rate: FrameRateMode::Keep,
+        }
+    }
+
+    pub fn with_mode(mut self, mode: SyncMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn with_tolerance(mut self, tolerance: Timestamp) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    pub fn with_anchor_line(mut self, anchor_line: usize) -> Self {
+        self.anchor_line = anchor_line;
+        self
+    }
+
+    pub fn with_shift_end(mut self, shift_end: bool) -> Self {
+        self.shift_end = shift_end;
+        self
+    }
+
+    pub fn with_frame_rate(mut self, frame_rate: FrameRateMode) -> Self {
+        self.frame_rate = frame_rate;
+        self
+    }
+
+    fn parse_mode(value: &str) -> Option<SyncMode> {
+        match value {
+            "scenes" => Some(SyncMode::Scenes),
+            "timing" => Some(SyncMode::Timing),
+            "timing_and_text" => Some(SyncMode::TimingAndText),
+            _ => None,
+        }
+    }
+
+    fn parse_frame_rate(value: &str) -> Option<FrameRateMode> {
+        match value {
+            "keep" => Some(FrameRateMode::Keep),
+            "suggest" => Some(FrameRateMode::Suggest),
+            "auto" => Some(FrameRateMode::Auto),
+            _ => None,
+        }
+    }
+
+    fn with_setting(self, key: &str, value: &str) -> Option<Self> {
+        match key {
+            "sync.mode" => Self::parse_mode(value).map(|mode| self.with_mode(mode)),
+            "sync.tolerance" => value
+                .parse::<f64>()
+                .ok()
+                .filter(|secs| *secs > 0.0)
+                .map(|secs| self.with_tolerance(Timestamp::from_secs_f64(secs))),
+            "sync.anchor_line" => value
+                .parse::<usize>()
+                .ok()
+                .filter(|line| *line > 0)
+                .map(|line| self.with_anchor_line(line - 1)),
+            "sync.shift_end" => value.parse().ok().map(|shift| self.with_shift_end(shift)),
+            "sync.frame_rate" => {
+                Self::parse_frame_rate(value).map(|mode| self.with_frame_rate(mode))
+            }
+            _ => None,
+        }
+    }
+
+    pub fn with_config(self, lines: &[String]) -> ParseRes<Self> {
+        let mut options = self;
+        for (i, line) in lines.iter().enumerate() {
+            let content = line.split(Self::COMMENT).next().unwrap_or_default().trim();
+            if content.is_empty() {
+                continue;
+            }
+            options = content
+                .split_once(Self::SEPARATOR)
+                .and_then(|(key, value)| options.with_setting(key.trim(), value.trim()))
+                .ok_or_else(|| ParserError::invalid_setting(content).at_line(i + 1, line))?;
+        }
+        Ok(options)
+    }
+
+    pub fn is_within_tolerance(&self, current: Timestamp, anchor: Timestamp) -> bool {
+        (current - anchor).abs() < self.tolerance
+    }
+
+    pub fn confidence(&self, drift: Timestamp) -> f64 {
+        let tolerance = self.tolerance.millis().max(1) as f64;
+        1.0 - (drift.abs().millis() as f64 / tolerance).min(1.0)
+    }
+
+    pub fn shifted(
+        &self,
+        start: Timestamp,
+        end: Timestamp,
+        delta: Timestamp,
+    ) -> (Timestamp, Timestamp) {
+        let shifted_start = start + delta;
+        match self.shift_end {
+            true => (shifted_start, end + delta),
+            false => (shifted_start, end.max(shifted_start)),
+        }
+    }
+}
+
+impl Default for SyncOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests;
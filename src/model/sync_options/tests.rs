@@ -0,0 +1,82 @@
+use super::*;
+
+struct TestCase {
+    name: &'static str,
+    input: &'static [&'static str],
+    expected: Option<SyncOptions>,
+}
+
+fn cases() -> Vec<TestCase> {
+    vec![
+        TestCase {
+            name: "empty config keeps the defaults",
+            input: &[],
+            expected: Some(SyncOptions::default()),
+        },
+        TestCase {
+            name: "every setting with comments and blank lines",
+            input: &[
+                "# durazubs settings",
+                "",
+                "sync.mode = timing",
+                "sync.tolerance = 2.5",
+                "sync.anchor_line = 3 # skip the cold open",
+                "sync.shift_end = false",
+                "sync.frame_rate = auto",
+            ],
+            expected: Some(
+                SyncOptions::new()
+                    .with_mode(SyncMode::Timing)
+                    .with_tolerance(Timestamp::from_millis(2_500))
+                    .with_anchor_line(2)
+                    .with_shift_end(false)
+                    .with_frame_rate(FrameRateMode::Auto),
+            ),
+        },
+        TestCase {
+            name: "unknown key",
+            input: &["sync.drift = 1"],
+            expected: None,
+        },
+        TestCase {
+            name: "invalid tolerance",
+            input: &["sync.tolerance = -1"],
+            expected: None,
+        },
+        TestCase {
+            name: "line without separator",
+            input: &["sync.mode timing"],
+            expected: None,
+        },
+    ]
+}
+
+fn to_lines(lines: &[&str]) -> Vec<String> {
+    lines.iter().map(|s| s.to_string()).collect()
+}
+
+#[test]
+fn test_with_config() {
+    for test_case in cases() {
+        let result = SyncOptions::default()
+            .with_config(&to_lines(test_case.input))
+            .ok();
+        assert_eq!(
+            result, test_case.expected,
+            "Failed at case: {}",
+            test_case.name
+        );
+    }
+}
+
+#[test]
+fn test_config_error_location() {
+    let lines = to_lines(&["sync.mode = timing", "sync.anchor_line = zero"]);
+    match SyncOptions::default().with_config(&lines) {
+        Err(ParserError::InvalidSetting { location, found }) => {
+            assert_eq!(location.line, 2);
+            assert_eq!(found, "sync.anchor_line = zero");
+        }
+        other => panic!("expected an invalid setting, got {:?}", other),
+    }
+}
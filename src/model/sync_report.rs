@@ -0,0 +1,182 @@
+use crate::model::{
+    retiming::frame_rate::SpeedupDetection,
+    sync_result::{BlockPlacement, PlacedBlock},
+    timestamp::Timestamp,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DeltaChange {
+    pub line: usize,
+    pub delta: Timestamp,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LineOverlap {
+    pub line: usize,
+    pub start: Timestamp,
+    pub end: Timestamp,
+    pub block_start: Timestamp,
+    pub block_end: Timestamp,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SyncReport {
+    pub initial_delta: Timestamp,
+    pub delta_changes: Vec<DeltaChange>,
+    pub blocks: Vec<PlacedBlock>,
+    pub overlaps: Vec<LineOverlap>,
+    pub frame_rate: Option<SpeedupDetection>,
+}
+
+impl SyncReport {
+    pub fn new(initial_delta: Timestamp) -> Self {
+        Self {
+            initial_delta,
+            ..Self::default()
+        }
+    }
+
+    pub fn current_delta(&self) -> Timestamp {
+        self.delta_changes
+            .last()
+            .map_or(self.initial_delta, |change| change.delta)
+    }
+
+    pub fn record_delta(&mut self, line: usize, delta: Timestamp) {
+        if delta != self.current_delta() {
+            self.delta_changes.push(DeltaChange { line, delta });
+        }
+    }
+
+    pub fn add_block(&mut self, block: PlacedBlock) {
+        self.blocks.push(block);
+    }
+
+    pub fn with_overlaps(mut self, corrected_a: &[(Timestamp, Timestamp)]) -> Self {
+        self.overlaps = corrected_a
+            .iter()
+            .enumerate()
+            .flat_map(|(line, &(start, end))| {
+                self.blocks
+                    .iter()
+                    .filter(move |block| {
+                        block.adds_lines() && start < block.end && block.start < end
+                    })
+                    .map(move |block| LineOverlap {
+                        line,
+                        start,
+                        end,
+                        block_start: block.start,
+                        block_end: block.end,
+                    })
+            })
+            .collect();
+        self
+    }
+
+    pub fn with_frame_rate(mut self, frame_rate: Option<SpeedupDetection>) -> Self {
+        self.frame_rate = frame_rate;
+        self
+    }
+
+    pub fn count(&self, placement: BlockPlacement) -> usize {
+        self.blocks
+            .iter()
+            .filter(|block| block.placement == placement)
+            .count()
+    }
+
+    fn json_array(name: &str, items: Vec<String>, last: bool) -> Vec<String> {
+        let close = match last {
+            true => "  ]",
+            false => "  ],",
+        };
+        let count = items.len();
+        let mut lines = vec![format!("  \"{}\": [", name)];
+        for (i, item) in items.into_iter().enumerate() {
+            match i + 1 == count {
+                true => lines.push(format!("    {}", item)),
+                false => lines.push(format!("    {},", item)),
+            }
+        }
+        lines.push(close.to_string());
+        lines
+    }
+
+    fn json_line(line: Option<usize>) -> String {
+        line.map_or("null".to_string(), |line| (line + 1).to_string())
+    }
+
+    fn json_frame_rate(&self) -> String {
+        let Some(detection) = self.frame_rate else {
+            return "null".to_string();
+        };
+        let measured = detection
+            .measured
+            .map_or("null".to_string(), |ratio| format!("{:.5}", ratio));
+        format!(
+            "{{ \"source_fps\": {:.3}, \"target_fps\": {:.3}, \"pivot_ms\": {}, \"measured_ratio\": {}, \"applied\": {} }}",
+            detection.conversion.source_fps,
+            detection.conversion.target_fps,
+            detection.conversion.pivot.millis(),
+            measured,
+            detection.applied
+        )
+    }
+
+    pub fn to_json(&self) -> Vec<String> {
+        let delta_changes = self
+            .delta_changes
+            .iter()
+            .map(|change| {
+                format!(
+                    "{{ \"line\": {}, \"delta_ms\": {} }}",
+                    change.line + 1,
+                    change.delta.millis()
+                )
+            })
+            .collect();
+        let blocks = self
+            .blocks
+            .iter()
+            .map(|block| {
+                format!(
+                    "{{ \"placement\": \"{}\", \"start_ms\": {}, \"end_ms\": {}, \"lines\": {}, \"after_line\": {}, \"confidence\": {:.2} }}",
+                    block.placement.name(),
+                    block.start.millis(),
+                    block.end.millis(),
+                    block.lines,
+                    Self::json_line(block.after_line),
+                    block.confidence
+                )
+            })
+            .collect();
+        let overlaps = self
+            .overlaps
+            .iter()
+            .map(|overlap| {
+                format!(
+                    "{{ \"line\": {}, \"start_ms\": {}, \"end_ms\": {}, \"block_start_ms\": {}, \"block_end_ms\": {} }}",
+                    overlap.line + 1,
+                    overlap.start.millis(),
+                    overlap.end.millis(),
+                    overlap.block_start.millis(),
+                    overlap.block_end.millis()
+                )
+            })
+            .collect();
+        let mut lines = vec![
+            "{".to_string(),
+            format!("  \"initial_delta_ms\": {},", self.initial_delta.millis()),
+            format!("  \"frame_rate\": {},", self.json_frame_rate()),
+        ];
+        lines.extend(Self::json_array("delta_changes", delta_changes, false));
+        lines.extend(Self::json_array("blocks", blocks, false));
+        lines.extend(Self::json_array("overlaps", overlaps, true));
+        lines.push("}".to_string());
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,72 @@
+use super::*;
+
+fn ms(millis: i64) -> Timestamp {
+    Timestamp::from_millis(millis)
+}
+
+fn sample_report() -> SyncReport {
+    let mut report = SyncReport::new(ms(1000));
+    report.record_delta(0, ms(1000));
+    report.record_delta(2, ms(1500));
+    report.add_block(
+        PlacedBlock::new(BlockPlacement::Inserted, ms(4000), ms(6000), 2)
+            .with_after_line(Some(1))
+            .with_confidence(0.75),
+    );
+    report.add_block(PlacedBlock::new(
+        BlockPlacement::Skipped,
+        ms(9000),
+        ms(9500),
+        1,
+    ));
+    report.with_overlaps(&[
+        (ms(2000), ms(3000)),
+        (ms(3500), ms(4500)),
+        (ms(6500), ms(7000)),
+    ])
+}
+
+#[test]
+fn test_records_only_real_delta_changes() {
+    let report = sample_report();
+    let expected = vec![DeltaChange {
+        line: 2,
+        delta: ms(1500),
+    }];
+    assert_eq!(report.delta_changes, expected);
+    assert_eq!(report.current_delta(), ms(1500));
+}
+
+#[test]
+fn test_detects_lines_overlapping_inserted_blocks() {
+    let report = sample_report();
+    let expected = vec![LineOverlap {
+        line: 1,
+        start: ms(3500),
+        end: ms(4500),
+        block_start: ms(4000),
+        block_end: ms(6000),
+    }];
+    assert_eq!(report.overlaps, expected);
+}
+
+#[test]
+fn test_serializes_to_json() {
+    let expected = [
+        "{",
+        "  \"initial_delta_ms\": 1000,",
+        "  \"frame_rate\": null,",
+        "  \"delta_changes\": [",
+        "    { \"line\": 3, \"delta_ms\": 1500 }",
+        "  ],",
+        "  \"blocks\": [",
+        "    { \"placement\": \"inserted\", \"start_ms\": 4000, \"end_ms\": 6000, \"lines\": 2, \"after_line\": 2, \"confidence\": 0.75 },",
+        "    { \"placement\": \"skipped\", \"start_ms\": 9000, \"end_ms\": 9500, \"lines\": 1, \"after_line\": null, \"confidence\": 0.00 }",
+        "  ],",
+        "  \"overlaps\": [",
+        "    { \"line\": 2, \"start_ms\": 3500, \"end_ms\": 4500, \"block_start_ms\": 4000, \"block_end_ms\": 6000 }",
+        "  ]",
+        "}",
+    ];
+    assert_eq!(sample_report().to_json(), expected);
+}
@@ -0,0 +1,80 @@
+use crate::model::{sync_report::SyncReport, timestamp::Timestamp};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BlockPlacement {
+    Inserted,
+    Fallback,
+    Skipped,
+    Unmatched,
+}
+
+impl BlockPlacement {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Inserted => "inserted",
+            Self::Fallback => "fallback",
+            Self::Skipped => "skipped",
+            Self::Unmatched => "unmatched",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlacedBlock {
+    pub placement: BlockPlacement,
+    pub start: Timestamp,
+    pub end: Timestamp,
+    pub lines: usize,
+    pub after_line: Option<usize>,
+    pub confidence: f64,
+}
+
+impl PlacedBlock {
+    pub fn new(placement: BlockPlacement, start: Timestamp, end: Timestamp, lines: usize) -> Self {
+        Self {
+            placement,
+            start,
+            end,
+            lines,
+            after_line: None,
+            confidence: 0.0,
+        }
+    }
+
+    pub fn with_after_line(mut self, after_line: Option<usize>) -> Self {
+        self.after_line = after_line;
+        self
+    }
+
+    pub fn with_confidence(mut self, confidence: f64) -> Self {
+        self.confidence = confidence;
+        self
+    }
+
+    pub fn adds_lines(&self) -> bool {
+        matches!(
+            self.placement,
+            BlockPlacement::Inserted | BlockPlacement::Fallback
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyncResult<T> {
+    pub output: T,
+    pub report: SyncReport,
+}
+
+impl<T> SyncResult<T> {
+    pub fn new(output: T, mut report: SyncReport) -> Self {
+        report.blocks.sort_by_key(|block| block.start);
+        Self { output, report }
+    }
+
+    pub fn map<U, F: FnOnce(T) -> U>(self, f: F) -> SyncResult<U> {
+        SyncResult {
+            output: f(self.output),
+            report: self.report,
+        }
+    }
+}
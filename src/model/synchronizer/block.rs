@@ -0,0 +1,65 @@
+use crate::model::{
+    line::Line,
+    sync_result::{BlockPlacement, PlacedBlock},
+    timestamp::Timestamp,
+};
+
+#[derive(Debug, Clone)]
+pub struct Block<'a, L> {
+    previous_normal: Option<&'a L>,
+    next_normal: Option<&'a L>,
+    additional_lines: Vec<&'a L>,
+}
+
+impl<'a, L: Line> Block<'a, L> {
+    pub fn new(
+        previous_normal: Option<&'a L>,
+        next_normal: Option<&'a L>,
+        additional_lines: Vec<&'a L>,
+    ) -> Self {
+        Self {
+            previous_normal,
+            next_normal,
+            additional_lines,
+        }
+    }
+
+    pub fn previous_line(&self) -> Option<&'a L> {
+        self.previous_normal
+    }
+
+    pub fn next_line(&self) -> Option<&'a L> {
+        self.next_normal
+    }
+
+    pub fn anchor_line(&self) -> Option<&'a L> {
+        self.previous_normal.or(self.next_normal)
+    }
+
+    pub fn additional_lines(&self) -> &Vec<&'a L> {
+        &self.additional_lines
+    }
+
+    pub fn first_start(&self) -> Timestamp {
+        self.additional_lines
+            .first()
+            .map_or(Timestamp::ZERO, |line| line.start())
+    }
+
+    pub fn last_end(&self) -> Timestamp {
+        self.additional_lines
+            .iter()
+            .map(|line| line.end())
+            .max()
+            .unwrap_or(Timestamp::ZERO)
+    }
+
+    pub fn placed(&self, placement: BlockPlacement) -> PlacedBlock {
+        PlacedBlock::new(
+            placement,
+            self.first_start(),
+            self.last_end(),
+            self.additional_lines.len(),
+        )
+    }
+}
@@ -0,0 +1,104 @@
+use std::collections::VecDeque;
+
+use crate::model::{
+    line::Line,
+    synchronizer::{SceneMarker, block::Block, block_state::BlockState},
+};
+
+pub struct Blocks<'a, L> {
+    queue: VecDeque<Block<'a, L>>,
+    passed: Vec<Block<'a, L>>,
+}
+
+impl<'a, L: Line> Blocks<'a, L> {
+    pub fn new(lines: &[&'a L], marker: &impl SceneMarker<L>) -> Self {
+        let queue = Self::analyze_blocks(marker, lines);
+        Self {
+            queue,
+            passed: Vec::new(),
+        }
+    }
+
+    fn process_lines(lines: &[&L], state: &mut BlockState, marker: &impl SceneMarker<L>) {
+        for (i, line) in lines.iter().enumerate() {
+            match marker.is_scene_line(line) {
+                true => state.update_start(i),
+                false => state.end_block(i),
+            }
+        }
+    }
+
+    fn get_block_indices(lines: &[&L], marker: &impl SceneMarker<L>) -> Vec<(usize, usize)> {
+        let mut state = BlockState::new();
+        Self::process_lines(lines, &mut state, marker);
+        state.close_final(lines.len());
+        state.into_ranges()
+    }
+
+    fn analyze_blocks(marker: &impl SceneMarker<L>, lines: &[&'a L]) -> VecDeque<Block<'a, L>> {
+        Self::get_block_indices(lines, marker)
+            .into_iter()
+            .map(|(start, end)| Self::create_block(marker, lines, start, end))
+            .collect()
+    }
+
+    fn create_block<'b>(
+        marker: &impl SceneMarker<L>,
+        lines: &[&'b L],
+        start: usize,
+        end: usize,
+    ) -> Block<'b, L> {
+        let additional = Self::get_lines(lines, start, end);
+        let previous_normal = Self::find_previous_normal(lines, start, marker);
+        let next_normal = Self::find_next_normal(lines, end, marker);
+        Block::new(previous_normal, next_normal, additional)
+    }
+
+    fn get_lines<'b>(lines: &[&'b L], start: usize, end: usize) -> Vec<&'b L> {
+        lines[start..=end].to_vec()
+    }
+
+    fn find_previous_normal<'b>(
+        lines: &[&'b L],
+        index: usize,
+        marker: &impl SceneMarker<L>,
+    ) -> Option<&'b L> {
+        lines[..index]
+            .iter()
+            .rev()
+            .find(|line| !marker.is_scene_line(line))
+            .copied()
+    }
+
+    fn find_next_normal<'b>(
+        lines: &[&'b L],
+        end: usize,
+        marker: &impl SceneMarker<L>,
+    ) -> Option<&'b L> {
+        lines
+            .iter()
+            .skip(end + 1)
+            .find(|line| !marker.is_scene_line(line))
+            .copied()
+    }
+
+    pub fn pop_first_block(&mut self) -> Option<Block<'a, L>> {
+        self.queue.pop_front()
+    }
+
+    pub fn reinsert_first_block(&mut self, block: Block<'a, L>) {
+        self.queue.push_front(block);
+    }
+
+    pub fn has_blocks(&self) -> bool {
+        !self.queue.is_empty()
+    }
+
+    pub fn pass_first_block(&mut self, block: Block<'a, L>) {
+        self.passed.push(block);
+    }
+
+    pub fn into_leftovers(self) -> Vec<Block<'a, L>> {
+        self.passed.into_iter().chain(self.queue).collect()
+    }
+}
@@ -0,0 +1,246 @@
+use crate::model::{
+    line::Line,
+    sync_options::SyncOptions,
+    sync_report::SyncReport,
+    sync_result::{BlockPlacement, PlacedBlock},
+    synchronizer::{SceneMarker, block::Block, blocks::Blocks, steps::Steps},
+    timestamp::Timestamp,
+};
+
+pub struct LineProcessor<'a, L, M> {
+    delta: Timestamp,
+    line: usize,
+    output_groups: &'a mut Vec<Vec<L>>,
+    marker: &'a M,
+    options: SyncOptions,
+    report: SyncReport,
+    leading: Vec<L>,
+}
+
+impl<'a, L: Line + Clone, M: SceneMarker<L>> LineProcessor<'a, L, M> {
+    pub fn new(output_groups: &'a mut Vec<Vec<L>>, marker: &'a M, options: SyncOptions) -> Self {
+        Self {
+            delta: Timestamp::ZERO,
+            line: 0,
+            output_groups,
+            marker,
+            options,
+            report: SyncReport::default(),
+            leading: Vec::new(),
+        }
+    }
+
+    fn add_line(&mut self, line: L) {
+        match self.output_groups.last_mut() {
+            Some(group) => group.push(line),
+            None => self.output_groups.push(vec![line]),
+        }
+    }
+
+    fn process_line(&mut self, blocks: &mut Blocks<L>, lines_a: &[&L], idx_a: usize) {
+        let step = Steps::new(lines_a, idx_a);
+        self.line = idx_a;
+        if !self.try_insert_block(blocks, &step) {
+            self.add_corrected_line(step.current_line());
+        }
+    }
+
+    fn try_insert_block(&mut self, blocks: &mut Blocks<L>, step: &Steps<L>) -> bool {
+        match blocks.has_blocks() {
+            true => self.process_block(blocks, step),
+            false => false,
+        }
+    }
+
+    fn corrected_line(&self, line: &L) -> L {
+        let mut corrected = line.clone();
+        let (start, end) = self.options.shifted(line.start(), line.end(), self.delta);
+        corrected.set_times(start, end);
+        corrected
+    }
+
+    fn add_corrected_line(&mut self, line: &L) {
+        let corrected = self.corrected_line(line);
+        self.output_groups.push(vec![corrected]);
+    }
+
+    fn is_difference_valid(&self, current_time: Timestamp, previous_time: Timestamp) -> bool {
+        self.options
+            .is_within_tolerance(current_time, previous_time)
+    }
+
+    fn check_start_vs_anchor(&self, step: &Steps<L>, offset: Timestamp, anchor: &L) -> bool {
+        let current_time = step.current_line().start() + offset;
+        self.is_difference_valid(current_time, anchor.start())
+    }
+
+    fn is_block_valid(&self, block: &Block<L>, step: &Steps<L>, offset: Timestamp) -> bool {
+        match block.anchor_line() {
+            Some(anchor) => self.check_start_vs_anchor(step, offset, anchor),
+            None => false,
+        }
+    }
+
+    fn is_block_passed(&self, block: &Block<L>, step: &Steps<L>) -> bool {
+        match block.anchor_line() {
+            Some(anchor) => {
+                let current_time = step.current_line().start() + self.delta;
+                current_time > anchor.start()
+                    && !self.is_difference_valid(current_time, anchor.start())
+            }
+            None => true,
+        }
+    }
+
+    fn block_confidence(&self, block: &Block<L>, step: &Steps<L>) -> f64 {
+        match block.anchor_line() {
+            Some(anchor) => {
+                let current_time = step.current_line().start() + self.delta;
+                self.options.confidence(current_time - anchor.start())
+            }
+            None => 0.0,
+        }
+    }
+
+    fn add_additional_lines(&mut self, lines: &[&L]) {
+        for line in lines {
+            self.add_line((*line).clone());
+        }
+    }
+
+    fn add_corrected_block(&mut self, block: &Block<L>, step: &Steps<L>) {
+        self.add_corrected_line(step.current_line());
+        self.add_additional_lines(block.additional_lines());
+    }
+
+    fn add_leading_block(&mut self, block: &Block<L>, step: &Steps<L>) {
+        if let Some(next_block_line) = block.next_line() {
+            self.update_delta(step.current_line(), next_block_line);
+        }
+        let lines = block.additional_lines().iter().map(|line| (*line).clone());
+        match self.output_groups.last_mut() {
+            Some(group) => group.extend(lines),
+            None => self.leading.extend(lines),
+        }
+        self.add_corrected_line(step.current_line());
+    }
+
+    fn set_delta(&mut self, delta: Timestamp) {
+        self.delta = delta;
+        self.report.record_delta(self.line, delta);
+    }
+
+    fn update_delta(&mut self, next_line: &L, next_block_line: &L) {
+        self.set_delta(next_block_line.start() - next_line.start());
+    }
+
+    fn update_block_delta(&mut self, block: &Block<L>, step: &Steps<L>) {
+        let Some(next) = step.next_line() else {
+            return;
+        };
+        match (block.previous_line(), block.next_line()) {
+            (Some(_), Some(next_block_line)) => self.update_delta(next, next_block_line),
+            (_, None) => self.set_delta(self.delta.max(block.last_end() - next.start())),
+            (None, Some(_)) => {}
+        }
+    }
+
+    fn execute_consumed_block(&mut self, block: Block<L>, step: &Steps<L>) {
+        let confidence = self.block_confidence(&block, step);
+        let after_line = match block.previous_line() {
+            Some(_) => {
+                self.add_corrected_block(&block, step);
+                Some(self.line)
+            }
+            None => {
+                self.add_leading_block(&block, step);
+                self.line.checked_sub(1)
+            }
+        };
+        let placed = block
+            .placed(BlockPlacement::Inserted)
+            .with_after_line(after_line)
+            .with_confidence(confidence);
+        self.report.add_block(placed);
+        self.update_block_delta(&block, step);
+    }
+
+    fn process_block(&mut self, blocks: &mut Blocks<L>, step: &Steps<L>) -> bool {
+        while let Some(block) = blocks.pop_first_block() {
+            if self.is_block_valid(&block, step, self.delta) {
+                self.execute_consumed_block(block, step);
+                return true;
+            }
+            if !self.is_block_passed(&block, step) {
+                blocks.reinsert_first_block(block);
+                return false;
+            }
+            blocks.pass_first_block(block);
+        }
+        false
+    }
+
+    fn fallback_position(lines: &[L], start: Timestamp) -> usize {
+        lines
+            .iter()
+            .rposition(|line| line.start() <= start)
+            .map_or(0, |idx| idx + 1)
+    }
+
+    fn place_fallback(&mut self, block: &Block<L>) -> PlacedBlock {
+        if self.output_groups.is_empty() {
+            return block.placed(BlockPlacement::Skipped);
+        }
+        let start = block.first_start();
+        let lines = block.additional_lines().iter().map(|line| (*line).clone());
+        let nearest = self
+            .output_groups
+            .iter()
+            .rposition(|group| group.first().is_some_and(|line| line.start() <= start));
+        let Some(nearest) = nearest else {
+            let position = Self::fallback_position(&self.leading, start);
+            self.leading.splice(position..position, lines);
+            return block.placed(BlockPlacement::Fallback);
+        };
+        let group = &mut self.output_groups[nearest];
+        let position = Self::fallback_position(group, start);
+        let confidence = self.options.confidence(start - group[0].end()) / 2.0;
+        group.splice(position..position, lines);
+        block
+            .placed(BlockPlacement::Fallback)
+            .with_after_line(Some(nearest))
+            .with_confidence(confidence)
+    }
+
+    fn calculate_initial_delta(&self, lines_a: &[&L], lines_b: &[&L]) -> Timestamp {
+        let normal_b: Vec<&L> = lines_b
+            .iter()
+            .filter(|line| !self.marker.is_scene_line(line))
+            .copied()
+            .collect();
+        let last = lines_a.len().min(normal_b.len()).saturating_sub(1);
+        let anchor = self.options.anchor_line.min(last);
+        match (lines_a.get(anchor), normal_b.get(anchor)) {
+            (Some(a), Some(b)) => b.start() - a.start(),
+            _ => Timestamp::ZERO,
+        }
+    }
+
+    pub fn take_leading(&mut self) -> Vec<L> {
+        std::mem::take(&mut self.leading)
+    }
+
+    pub fn run(&mut self, lines_a: &[&L], lines_b: &[&L]) -> SyncReport {
+        self.delta = self.calculate_initial_delta(lines_a, lines_b);
+        self.report = SyncReport::new(self.delta);
+        let mut blocks = Blocks::new(lines_b, self.marker);
+        for idx_a in 0..lines_a.len() {
+            self.process_line(&mut blocks, lines_a, idx_a);
+        }
+        for block in blocks.into_leftovers() {
+            let placed = self.place_fallback(&block);
+            self.report.add_block(placed);
+        }
+        std::mem::take(&mut self.report)
+    }
+}
@@ -0,0 +1,124 @@
+use crate::model::{
+    aligner::{AlignStep, Aligner},
+    line::Line,
+    sync_options::{SyncMode, SyncOptions},
+    sync_result::SyncResult,
+    synchronizer::line_processor::LineProcessor,
+    timestamp::Timestamp,
+};
+
+mod block;
+mod block_state;
+mod blocks;
+mod line_processor;
+mod steps;
+
+pub trait SceneMarker<L> {
+    fn is_scene_line(&self, line: &L) -> bool;
+    fn mark_scene(&self, line: &mut L);
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyncedLines<L> {
+    pub leading: Vec<L>,
+    pub groups: Vec<Vec<L>>,
+}
+
+impl<L> SyncedLines<L> {
+    pub fn new(leading: Vec<L>, groups: Vec<Vec<L>>) -> Self {
+        Self { leading, groups }
+    }
+
+    pub fn into_lines(self) -> Vec<L> {
+        self.leading
+            .into_iter()
+            .chain(self.groups.into_iter().flatten())
+            .collect()
+    }
+}
+
+pub struct LineSynchronizer<'m, M> {
+    marker: &'m M,
+    options: SyncOptions,
+}
+
+impl<'m, M> LineSynchronizer<'m, M> {
+    pub fn new(marker: &'m M, options: SyncOptions) -> Self {
+        Self { marker, options }
+    }
+
+    fn align_lines<L>(&self, lines_a: &[&L], lines_b: &[&L]) -> SyncResult<SyncedLines<L>>
+    where
+        L: Line + Clone,
+        M: SceneMarker<L>,
+    {
+        let aligner = Aligner::new(self.options);
+        let steps = aligner.run(lines_a, lines_b);
+        let timings = aligner.timings(&steps, lines_a, lines_b);
+        let mut leading = Vec::new();
+        let mut groups: Vec<Vec<L>> = Vec::new();
+        for step in &steps {
+            match *step {
+                AlignStep::Match(i, _) | AlignStep::OnlyA(i) => {
+                    let mut line = lines_a[i].clone();
+                    line.set_times(timings[i].0, timings[i].1);
+                    groups.push(vec![line]);
+                }
+                AlignStep::OnlyB(j) => {
+                    let mut line = lines_b[j].clone();
+                    self.marker.mark_scene(&mut line);
+                    match groups.last_mut() {
+                        Some(group) => group.push(line),
+                        None => leading.push(line),
+                    }
+                }
+            }
+        }
+        let report = aligner.report(&steps, &timings, lines_a, lines_b);
+        SyncResult::new(SyncedLines::new(leading, groups), report)
+    }
+
+    fn scene_lines<L>(&self, lines_a: &[&L], lines_b: &[&L]) -> SyncResult<SyncedLines<L>>
+    where
+        L: Line + Clone,
+        M: SceneMarker<L>,
+    {
+        let mut groups = Vec::new();
+        let mut processor = LineProcessor::new(&mut groups, self.marker, self.options);
+        let report = processor.run(lines_a, lines_b);
+        let leading = processor.take_leading();
+        let corrected: Vec<(Timestamp, Timestamp)> = groups
+            .iter()
+            .filter_map(|group| group.first())
+            .map(|line| (line.start(), line.end()))
+            .collect();
+        SyncResult::new(
+            SyncedLines::new(leading, groups),
+            report.with_overlaps(&corrected),
+        )
+    }
+
+    pub fn run<L>(&self, lines_a: &[&L], lines_b: &[&L]) -> SyncResult<SyncedLines<L>>
+    where
+        L: Line + Clone,
+        M: SceneMarker<L>,
+    {
+        let mut retimed_a: Vec<L> = lines_a.iter().map(|line| (*line).clone()).collect();
+        let frame_rate = self
+            .options
+            .frame_rate
+            .run(&mut retimed_a, lines_b, |line| {
+                self.marker.is_scene_line(line)
+            });
+        let lines_a: Vec<&L> = retimed_a.iter().collect();
+        let mut synced = match self.options.mode {
+            SyncMode::Scenes => self.scene_lines(&lines_a, lines_b),
+            SyncMode::Timing | SyncMode::TimingAndText => self.align_lines(&lines_a, lines_b),
+        };
+        synced.report = synced.report.with_frame_rate(frame_rate);
+        synced
+    }
+}
+
+#[cfg(test)]
+mod tests;
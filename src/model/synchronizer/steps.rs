@@ -0,0 +1,21 @@
+pub struct Steps<'a, L> {
+    current_line: &'a L,
+    next_line: Option<&'a L>,
+}
+
+impl<'a, L> Steps<'a, L> {
+    pub fn new(lines: &'a [&'a L], index: usize) -> Self {
+        Self {
+            current_line: lines[index],
+            next_line: lines.get(index + 1).copied(),
+        }
+    }
+
+    pub fn current_line(&self) -> &'a L {
+        self.current_line
+    }
+
+    pub fn next_line(&self) -> Option<&'a L> {
+        self.next_line
+    }
+}
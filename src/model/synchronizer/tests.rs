@@ -0,0 +1,138 @@
+use crate::model::{
+    format::srt::{line_srt::SrtLine, parser::SrtParser},
+    retiming::frame_rate::FrameRateMode,
+    sync_result::BlockPlacement,
+};
+
+use super::*;
+
+struct TestCase {
+    name: &'static str,
+    anchor_line: usize,
+    input_a: &'static [(i64, i64, &'static str, &'static str)],
+    input_b: &'static [(i64, i64, &'static str, &'static str)],
+    expected: &'static [&'static str],
+}
+
+static EARLY_LEFTOVER_CASE: TestCase = TestCase {
+    name: "places a leftover block that starts before every A line ahead of them",
+    anchor_line: 1,
+    input_a: &[(1000, 2000, "", "Uno"), (10000, 11000, "", "Dos")],
+    input_b: &[
+        (500, 900, "ADDITIONAL SCENE", "Extra"),
+        (1000, 2000, "", "One"),
+        (20000, 21000, "", "Two"),
+    ],
+    expected: &["Extra", "Uno", "Dos"],
+};
+
+static ONLY_SCENES_CASE: TestCase = TestCase {
+    name: "keeps A's timing and orders scene lines when B has no normal lines",
+    anchor_line: 0,
+    input_a: &[(1000, 2000, "", "Uno"), (5000, 6000, "", "Dos")],
+    input_b: &[(3000, 4000, "ADDITIONAL SCENE", "Extra")],
+    expected: &["Uno", "Extra", "Dos"],
+};
+
+fn to_cues(input: &[(i64, i64, &str, &str)]) -> Vec<SrtLine> {
+    input
+        .iter()
+        .map(|&(start, end, name, text)| SrtLine {
+            start: Timestamp::from_millis(start),
+            end: Timestamp::from_millis(end),
+            name: name.to_string(),
+            text: text.to_string(),
+        })
+        .collect()
+}
+
+fn run_test_case(test_case: &TestCase) -> SyncResult<SyncedLines<SrtLine>> {
+    let cues_a = to_cues(test_case.input_a);
+    let cues_b = to_cues(test_case.input_b);
+    let a: Vec<&SrtLine> = cues_a.iter().collect();
+    let b: Vec<&SrtLine> = cues_b.iter().collect();
+    let parser = SrtParser::new();
+    let options = SyncOptions::new().with_anchor_line(test_case.anchor_line);
+    let result = LineSynchronizer::new(&parser, options).run(&a, &b);
+    let texts: Vec<String> = result
+        .output
+        .clone()
+        .into_lines()
+        .into_iter()
+        .map(|cue| cue.text)
+        .collect();
+    assert_eq!(
+        texts, test_case.expected,
+        "Failed at case: {}",
+        test_case.name
+    );
+    result
+}
+
+#[test]
+fn test_places_early_leftover_before_first_line() {
+    let result = run_test_case(&EARLY_LEFTOVER_CASE);
+    assert_eq!(result.output.leading.len(), 1);
+    let [block] = result.report.blocks.as_slice() else {
+        panic!("expected a single block, got {:?}", result.report.blocks);
+    };
+    assert_eq!(block.placement, BlockPlacement::Fallback);
+    assert_eq!(block.after_line, None);
+}
+
+#[test]
+fn test_syncs_when_b_has_only_scenes() {
+    let result = run_test_case(&ONLY_SCENES_CASE);
+    assert_eq!(result.report.initial_delta, Timestamp::ZERO);
+}
+
+#[test]
+fn test_skips_blocks_without_a_lines() {
+    let cues_b = to_cues(ONLY_SCENES_CASE.input_b);
+    let b: Vec<&SrtLine> = cues_b.iter().collect();
+    let parser = SrtParser::new();
+    let result = LineSynchronizer::new(&parser, SyncOptions::default()).run(&[], &b);
+    assert!(result.output.into_lines().is_empty());
+    assert_eq!(result.report.count(BlockPlacement::Skipped), 1);
+}
+
+#[test]
+fn test_frame_rate_conversion_keeps_b_timeline() {
+    let ratio = Timestamp::FILM_FRAME_RATE / 25.0;
+    let scale = |t: i64| (t as f64 * ratio).round() as i64 + 1_500;
+    let input_a: Vec<(i64, i64, &str, &str)> = (0..40)
+        .map(|i| (5_000 + i * 3_100, 7_000 + i * 3_100, "", "Línea"))
+        .collect();
+    let mut input_b: Vec<(i64, i64, &str, &str)> = input_a
+        .iter()
+        .map(|&(start, end, _, _)| (scale(start), scale(end), "", "Line"))
+        .collect();
+    let scene = (
+        input_b[19].1 + 100,
+        input_b[20].0 - 100,
+        "ADDITIONAL SCENE",
+        "Extra",
+    );
+    input_b.insert(20, scene);
+    let cues_a = to_cues(&input_a);
+    let cues_b = to_cues(&input_b);
+    let a: Vec<&SrtLine> = cues_a.iter().collect();
+    let b: Vec<&SrtLine> = cues_b.iter().collect();
+    let parser = SrtParser::new();
+    let options = SyncOptions::new().with_frame_rate(FrameRateMode::Auto);
+    let result = LineSynchronizer::new(&parser, options).run(&a, &b);
+    assert!(result.report.frame_rate.is_some_and(|d| d.applied));
+    let output = result.output.into_lines();
+    assert_eq!(output.len(), cues_b.len());
+    for (line, expected) in output.iter().zip(&cues_b) {
+        assert!(
+            (line.start - expected.start).abs() <= Timestamp::from_millis(2),
+            "{:?} drifted from {:?}",
+            line.start,
+            expected.start
+        );
+        assert!((line.end - expected.end).abs() <= Timestamp::from_millis(2));
+    }
+    assert_eq!(output[20].start, Timestamp::from_millis(scene.0));
+    assert_eq!(output[20].end, Timestamp::from_millis(scene.1));
+}
@@ -0,0 +1,231 @@
+use std::ops::{Add, AddAssign, Neg, Sub, SubAssign};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timestamp(i64);
+
+impl Timestamp {
+    pub const ZERO: Timestamp = Timestamp(0);
+    pub const FILM_FRAME_RATE: f64 = 24_000.0 / 1_001.0;
+
+    const MILLIS_PER_CENTI: i64 = 10;
+    const MILLIS_PER_SECOND: i64 = 1_000;
+    const MILLIS_PER_MINUTE: i64 = 60_000;
+    const MILLIS_PER_HOUR: i64 = 3_600_000;
+    const FRACTION_DIGITS: usize = 3;
+
+    pub const fn from_millis(millis: i64) -> Self {
+        Self(millis)
+    }
+
+    pub const fn from_centis(centis: i64) -> Self {
+        Self(centis * Self::MILLIS_PER_CENTI)
+    }
+
+    pub fn from_secs_f64(seconds: f64) -> Self {
+        Self((seconds * Self::MILLIS_PER_SECOND as f64).round() as i64)
+    }
+
+    pub fn from_frame(frame: i64, fps: f64) -> Self {
+        Self((frame as f64 * Self::MILLIS_PER_SECOND as f64 / fps).round() as i64)
+    }
+
+    pub fn to_frame(self, fps: f64) -> i64 {
+        (self.0 as f64 * fps / Self::MILLIS_PER_SECOND as f64).round() as i64
+    }
+
+    pub fn millis(self) -> i64 {
+        self.0
+    }
+
+    pub fn centis(self) -> i64 {
+        let half = Self::MILLIS_PER_CENTI / 2;
+        match self.0 >= 0 {
+            true => (self.0 + half) / Self::MILLIS_PER_CENTI,
+            false => (self.0 - half) / Self::MILLIS_PER_CENTI,
+        }
+    }
+
+    pub fn as_secs_f64(self) -> f64 {
+        self.0 as f64 / Self::MILLIS_PER_SECOND as f64
+    }
+
+    pub fn abs(self) -> Self {
+        Self(self.0.abs())
+    }
+
+    fn clock(millis: i64) -> (i64, i64, i64, i64) {
+        let millis = millis.max(0);
+        (
+            millis / Self::MILLIS_PER_HOUR,
+            (millis % Self::MILLIS_PER_HOUR) / Self::MILLIS_PER_MINUTE,
+            (millis % Self::MILLIS_PER_MINUTE) / Self::MILLIS_PER_SECOND,
+            millis % Self::MILLIS_PER_SECOND,
+        )
+    }
+
+    pub fn to_ass(self) -> String {
+        let rounded = Self::from_centis(self.centis());
+        let (h, m, s, ms) = Self::clock(rounded.0);
+        let cs = ms / Self::MILLIS_PER_CENTI;
+        format!("{h}:{m:02}:{s:02}.{cs:02}")
+    }
+
+    pub fn to_srt(self) -> String {
+        let (h, m, s, ms) = Self::clock(self.0);
+        format!("{h:02}:{m:02}:{s:02},{ms:03}")
+    }
+
+    pub fn to_vtt(self) -> String {
+        let (h, m, s, ms) = Self::clock(self.0);
+        format!("{h:02}:{m:02}:{s:02}.{ms:03}")
+    }
+
+    pub fn to_subviewer(self) -> String {
+        let rounded = Self::from_centis(self.centis());
+        let (h, m, s, ms) = Self::clock(rounded.0);
+        let cs = ms / Self::MILLIS_PER_CENTI;
+        format!("{h:02}:{m:02}:{s:02}.{cs:02}")
+    }
+
+    pub fn to_smpte(self, fps: f64) -> String {
+        let snapped = Self::from_frame(self.to_frame(fps), fps);
+        let (h, m, s, ms) = Self::clock(snapped.0);
+        let max_frame = fps.ceil() as i64 - 1;
+        let ff = ((ms as f64 * fps / Self::MILLIS_PER_SECOND as f64).round() as i64).min(max_frame);
+        format!("{h:02}:{m:02}:{s:02}:{ff:02}")
+    }
+
+    fn parse_number(value: &str) -> Option<i64> {
+        match !value.is_empty() && value.chars().all(|c| c.is_ascii_digit()) {
+            true => value.parse().ok(),
+            false => None,
+        }
+    }
+
+    fn parse_fraction(value: &str) -> Option<i64> {
+        Self::parse_number(value)?;
+        let digits: String = value
+            .chars()
+            .chain(std::iter::repeat('0'))
+            .take(Self::FRACTION_DIGITS)
+            .collect();
+        digits.parse().ok()
+    }
+
+    fn from_clock(h: &str, m: &str, s: &str, fraction: &str) -> Option<Self> {
+        let h = Self::parse_number(h)?;
+        let m = Self::parse_number(m)?;
+        let s = Self::parse_number(s)?;
+        let ms = Self::parse_fraction(fraction)?;
+        h.checked_mul(Self::MILLIS_PER_HOUR)?
+            .checked_add(m.checked_mul(Self::MILLIS_PER_MINUTE)?)?
+            .checked_add(s.checked_mul(Self::MILLIS_PER_SECOND)?)?
+            .checked_add(ms)
+            .map(Self)
+    }
+
+    pub fn parse_ass(t: &str) -> Option<Self> {
+        let parts: Vec<&str> = t.trim().split(':').collect();
+        let [h, m, rest] = parts.as_slice() else {
+            return None;
+        };
+        let (s, fraction) = rest.split_once('.')?;
+        Self::from_clock(h, m, s, fraction)
+    }
+
+    pub fn parse_srt(t: &str) -> Option<Self> {
+        let parts: Vec<&str> = t.trim().split(':').collect();
+        let [h, m, rest] = parts.as_slice() else {
+            return None;
+        };
+        let (s, fraction) = rest.split_once([',', '.'])?;
+        Self::from_clock(h, m, s, fraction)
+    }
+
+    fn parse_offset(value: &str, unit: &str, fps: f64, tick_rate: f64) -> Option<Self> {
+        let valid = !value.is_empty() && value.chars().all(|c| c.is_ascii_digit() || c == '.');
+        let value: f64 = value.parse().ok().filter(|_| valid)?;
+        let seconds = match unit {
+            "h" => value * 3_600.0,
+            "m" => value * 60.0,
+            "s" => value,
+            "ms" => value / Self::MILLIS_PER_SECOND as f64,
+            "f" => value / fps,
+            "t" => value / tick_rate,
+            _ => return None,
+        };
+        Some(Self::from_secs_f64(seconds))
+    }
+
+    pub fn parse_ttml(t: &str, fps: f64, tick_rate: f64) -> Option<Self> {
+        let t = t.trim();
+        if let Some(idx) = t.find(|c: char| c.is_ascii_alphabetic()) {
+            return Self::parse_offset(&t[..idx], &t[idx..], fps, tick_rate);
+        }
+        let parts: Vec<&str> = t.split(':').collect();
+        match parts.as_slice() {
+            [h, m, rest] => match rest.split_once('.') {
+                Some((s, fraction)) => Self::from_clock(h, m, s, fraction),
+                None => Self::from_clock(h, m, rest, "0"),
+            },
+            [h, m, s, frames] => {
+                let base = Self::from_clock(h, m, s, "0")?;
+                let offset = Self::parse_offset(frames, "f", fps, tick_rate)?;
+                base.0.checked_add(offset.0).map(Self)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn parse_vtt(t: &str) -> Option<Self> {
+        let (clock, fraction) = t
+            .trim()
+            .split_once('.')
+            .filter(|(_, fraction)| fraction.len() == Self::FRACTION_DIGITS)?;
+        let parts: Vec<&str> = clock.split(':').collect();
+        match parts.as_slice() {
+            [m, s] => Self::from_clock("0", m, s, fraction),
+            [h, m, s] => Self::from_clock(h, m, s, fraction),
+            _ => None,
+        }
+    }
+}
+
+impl Add for Timestamp {
+    type Output = Timestamp;
+
+    fn add(self, other: Timestamp) -> Timestamp {
+        Timestamp(self.0 + other.0)
+    }
+}
+
+impl Sub for Timestamp {
+    type Output = Timestamp;
+
+    fn sub(self, other: Timestamp) -> Timestamp {
+        Timestamp(self.0 - other.0)
+    }
+}
+
+impl Neg for Timestamp {
+    type Output = Timestamp;
+
+    fn neg(self) -> Timestamp {
+        Timestamp(-self.0)
+    }
+}
+
+impl AddAssign for Timestamp {
+    fn add_assign(&mut self, other: Timestamp) {
+        self.0 += other.0;
+    }
+}
+
+impl SubAssign for Timestamp {
+    fn sub_assign(&mut self, other: Timestamp) {
+        self.0 -= other.0;
+    }
+}
+
+#[cfg(test)]
+mod tests;
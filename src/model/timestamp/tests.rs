@@ -0,0 +1,187 @@
+use super::*;
+
+struct TestCase {
+    name: &'static str,
+    input: &'static str,
+    millis: Option<i64>,
+}
+
+static ASS_CASES: &[TestCase] = &[
+    TestCase {
+        name: "standard centiseconds",
+        input: "0:00:01.50",
+        millis: Some(1_500),
+    },
+    TestCase {
+        name: "single fraction digit is tenths",
+        input: "0:00:01.5",
+        millis: Some(1_500),
+    },
+    TestCase {
+        name: "padded hours",
+        input: "01:02:03.04",
+        millis: Some(3_723_040),
+    },
+    TestCase {
+        name: "missing fraction",
+        input: "0:00:01",
+        millis: None,
+    },
+    TestCase {
+        name: "comma separator",
+        input: "0:00:1,00",
+        millis: None,
+    },
+    TestCase {
+        name: "letters in minutes",
+        input: "0:XX:01.00",
+        millis: None,
+    },
+    TestCase {
+        name: "hours overflow",
+        input: "99999999999999:00:00.00",
+        millis: None,
+    },
+];
+
+static SRT_CASES: &[TestCase] = &[
+    TestCase {
+        name: "standard milliseconds",
+        input: "00:01:02,345",
+        millis: Some(62_345),
+    },
+    TestCase {
+        name: "dot separator",
+        input: "00:00:01.200",
+        millis: Some(1_200),
+    },
+    TestCase {
+        name: "digits past milliseconds are truncated",
+        input: "00:00:01,2349",
+        millis: Some(1_234),
+    },
+    TestCase {
+        name: "missing hours",
+        input: "01:02,345",
+        millis: None,
+    },
+    TestCase {
+        name: "hours overflow",
+        input: "99999999999999:00:00,000",
+        millis: None,
+    },
+];
+
+static VTT_CASES: &[TestCase] = &[
+    TestCase {
+        name: "short form",
+        input: "01:02.345",
+        millis: Some(62_345),
+    },
+    TestCase {
+        name: "long form",
+        input: "01:00:00.001",
+        millis: Some(3_600_001),
+    },
+    TestCase {
+        name: "comma separator",
+        input: "00:00:01,000",
+        millis: None,
+    },
+    TestCase {
+        name: "two fraction digits",
+        input: "00:01.50",
+        millis: None,
+    },
+    TestCase {
+        name: "four fraction digits",
+        input: "00:00:01.2345",
+        millis: None,
+    },
+    TestCase {
+        name: "minutes overflow",
+        input: "999999999999999999:00.000",
+        millis: None,
+    },
+];
+
+fn run_cases(cases: &[TestCase], parse: fn(&str) -> Option<Timestamp>) {
+    for case in cases {
+        let result = parse(case.input).map(Timestamp::millis);
+        assert_eq!(result, case.millis, "Failed at case: {}", case.name);
+    }
+}
+
+#[test]
+fn test_parse_ass() {
+    run_cases(ASS_CASES, Timestamp::parse_ass);
+}
+
+#[test]
+fn test_parse_srt() {
+    run_cases(SRT_CASES, Timestamp::parse_srt);
+}
+
+#[test]
+fn test_parse_vtt() {
+    run_cases(VTT_CASES, Timestamp::parse_vtt);
+}
+
+#[test]
+fn test_ass_rounding_carries_into_seconds() {
+    assert_eq!(Timestamp::from_millis(1_999).to_ass(), "0:00:02.00");
+    assert_eq!(Timestamp::from_millis(1_994).to_ass(), "0:00:01.99");
+    assert_eq!(Timestamp::from_millis(1_995).to_ass(), "0:00:02.00");
+    assert_eq!(Timestamp::from_millis(3_599_999).to_ass(), "1:00:00.00");
+}
+
+#[test]
+fn test_format_srt_and_vtt() {
+    let t = Timestamp::from_millis(3_723_045);
+    assert_eq!(t.to_srt(), "01:02:03,045");
+    assert_eq!(t.to_vtt(), "01:02:03.045");
+}
+
+#[test]
+fn test_negative_times_clamp_to_zero() {
+    let t = Timestamp::from_millis(500) - Timestamp::from_millis(1_500);
+    assert_eq!(t.millis(), -1_000);
+    assert_eq!(t.to_ass(), "0:00:00.00");
+    assert_eq!(t.to_srt(), "00:00:00,000");
+}
+
+#[test]
+fn test_centis_round_half_away_from_zero() {
+    assert_eq!(Timestamp::from_millis(15).centis(), 2);
+    assert_eq!(Timestamp::from_millis(14).centis(), 1);
+    assert_eq!(Timestamp::from_millis(-15).centis(), -2);
+}
+
+#[test]
+fn test_frames_round_trip() {
+    let fps = 24_000.0 / 1_001.0;
+    assert_eq!(Timestamp::from_frame(24, fps).millis(), 1_001);
+    assert_eq!(Timestamp::from_millis(1_001).to_frame(fps), 24);
+    assert_eq!(Timestamp::from_frame(25, 25.0).to_frame(25.0), 25);
+    assert_eq!(Timestamp::from_millis(62_345).to_subviewer(), "00:01:02.35");
+}
+
+#[test]
+fn test_ttml_times() {
+    let parse = |t| Timestamp::parse_ttml(t, 25.0, 10_000_000.0).map(Timestamp::millis);
+    assert_eq!(parse("00:00:01.5"), Some(1_500));
+    assert_eq!(parse("00:01:02"), Some(62_000));
+    assert_eq!(parse("00:00:01:12"), Some(1_480));
+    assert_eq!(parse("2.5s"), Some(2_500));
+    assert_eq!(parse("1500ms"), Some(1_500));
+    assert_eq!(parse("50f"), Some(2_000));
+    assert_eq!(parse("15000000t"), Some(1_500));
+    assert_eq!(parse("-1s"), None);
+    assert_eq!(parse("1x"), None);
+    assert_eq!(parse("99999999999999:00:00:00"), None);
+    assert_eq!(Timestamp::from_millis(1_480).to_smpte(25.0), "00:00:01:12");
+    assert_eq!(
+        Timestamp::from_millis(3_599_990).to_smpte(25.0),
+        "01:00:00:00"
+    );
+}
@@ -16,7 +16,7 @@ impl Instructor {
         Self {}
     }
 
-    pub fn run(&self, lines: &Vec<String>) -> Vec<String> {
+    pub fn run(&self, lines: &[String]) -> Vec<String> {
         let chunk_count = (lines.len() as f64 / Self::CHUNK_SIZE as f64).ceil() as usize;
         let total_capacity = chunk_count * (Self::EXTRA_ELEMENTS_PER_CHUNK + Self::CHUNK_SIZE);
         let mut result = Vec::with_capacity(total_capacity);
@@ -33,3 +33,9 @@ impl Instructor {
         result
     }
 }
+
+impl Default for Instructor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,2 +1,3 @@
 pub mod instructor;
+#[allow(clippy::module_inception)]
 pub mod translator;
@@ -86,3 +86,9 @@ impl Translator {
         results
     }
 }
+
+impl Default for Translator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
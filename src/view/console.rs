@@ -1,9 +1,23 @@
 use super::*;
+use crate::model::{
+    audio::offset_finder::AudioSyncResult,
+    format::ass::document::attachments::AttachmentKind,
+    retiming::{
+        frame_rate::{FrameRateConversion, FrameRateMode},
+        keyframes::{KeyframeSnap, SnapSummary},
+    },
+    sync_options::{SyncMode, SyncOptions},
+    sync_report::SyncReport,
+    sync_result::BlockPlacement,
+    timestamp::Timestamp,
+};
 use std::io::{self, Write};
 
 pub struct Console;
 
 impl Console {
+    const UNKNOWN_PATH: &str = "<input>";
+
     pub fn new() -> Self {
         Self
     }
@@ -40,6 +54,320 @@ impl Console {
         input_string.trim().to_string()
     }
 
+    fn show_location(&self, location: &SourceLocation) {
+        let gutter = " ".repeat(location.line.to_string().len());
+        let path = match location.path.is_empty() {
+            true => Self::UNKNOWN_PATH,
+            false => &location.path,
+        };
+        eprintln!(
+            "{} --> {}:{}:{}",
+            gutter,
+            path,
+            location.line,
+            location.column + 1
+        );
+        eprintln!("{} |", gutter);
+        eprintln!("{} | {}", location.line, location.source);
+        eprintln!(
+            "{} | {}{}",
+            gutter,
+            " ".repeat(location.column),
+            "^".repeat(location.width.max(1))
+        );
+    }
+
+    fn get_attachment_options(&self) -> (Option<String>, Vec<String>) {
+        print!("\n❯ Manage embedded fonts and graphics? (y/n): ");
+        if self.read_input().to_lowercase() != "y" {
+            return (None, Vec::new());
+        }
+        let action = self.select_option(
+            "Attachment Action",
+            &["List attachments", "Extract to disk", "Embed files"],
+        );
+        let paths = match action.as_str() {
+            "1" => Vec::new(),
+            "2" => {
+                print!("❯ Enter output directory (default: current): ");
+                vec![self.read_input()]
+            }
+            _ => {
+                print!("❯ Enter files to embed (comma separated): ");
+                self.read_input()
+                    .split(',')
+                    .map(|path| path.trim().to_string())
+                    .filter(|path| !path.is_empty())
+                    .collect()
+            }
+        };
+        (Some(action), paths)
+    }
+
+    fn get_anchor_options(&self) -> (Option<String>, Vec<String>) {
+        print!("\n❯ Retime File A with anchor points (A line or time -> B time)? (y/n): ");
+        if self.read_input().to_lowercase() != "y" {
+            return (None, Vec::new());
+        }
+        let source = self.select_option(
+            "Anchor Source",
+            &["Enter anchors here", "Load anchors from a file"],
+        );
+        let entries = match source.as_str() {
+            "1" => {
+                println!(
+                    "  One anchor per line, e.g. '12 0:01:23.450' or '0:01:20.000 -> 0:01:23.450'."
+                );
+                let mut entries = Vec::new();
+                loop {
+                    print!("❯ Anchor {} (empty to finish): ", entries.len() + 1);
+                    let input = self.read_input();
+                    if input.is_empty() {
+                        break entries;
+                    }
+                    entries.push(input);
+                }
+            }
+            _ => loop {
+                print!("❯ Enter anchor file path: ");
+                let path = self.read_input();
+                if !path.is_empty() {
+                    break vec![path];
+                }
+            },
+        };
+        (Some(source), entries)
+    }
+
+    fn get_audio_path(&self) -> Option<String> {
+        print!("\n❯ Sync File A to the episode audio (PCM WAV)? (y/n): ");
+        if self.read_input().to_lowercase() != "y" {
+            return None;
+        }
+        println!("  Tip: ffmpeg -i episode.mkv -vn -ac 1 -ar 16000 episode.wav");
+        loop {
+            print!("❯ Enter WAV file path: ");
+            let path = self.read_input();
+            if !path.is_empty() {
+                return Some(path);
+            }
+        }
+    }
+
+    fn get_snap_window(&self) -> Timestamp {
+        loop {
+            print!(
+                "❯ Snap window in milliseconds (empty for {}): ",
+                KeyframeSnap::DEFAULT_WINDOW.millis()
+            );
+            let input = self.read_input();
+            if input.is_empty() {
+                return KeyframeSnap::DEFAULT_WINDOW;
+            }
+            match input.parse::<i64>() {
+                Ok(millis) if millis >= 0 => return Timestamp::from_millis(millis),
+                _ => println!("  '{}' is not a valid number of milliseconds.", input),
+            }
+        }
+    }
+
+    fn get_keyframe_options(&self) -> (Option<String>, Option<String>, f64, Timestamp) {
+        print!("\n❯ Snap line starts and ends to keyframes (scene cuts)? (y/n): ");
+        if self.read_input().to_lowercase() != "y" {
+            return (
+                None,
+                None,
+                Timestamp::FILM_FRAME_RATE,
+                KeyframeSnap::DEFAULT_WINDOW,
+            );
+        }
+        println!("  Supported: Aegisub keyframes, XviD/DivX pass logs, x264 stats, frame lists.");
+        let path = loop {
+            print!("❯ Enter keyframe file path: ");
+            let path = self.read_input();
+            if !path.is_empty() {
+                break path;
+            }
+        };
+        let (timecodes, fps) = match self
+            .select_option(
+                "Frame Timing",
+                &["Constant frame rate", "Timecode file (v1 or v2)"],
+            )
+            .as_str()
+        {
+            "1" => (None, self.read_frame_rate("❯ Video frame rate")),
+            _ => {
+                print!("❯ Enter timecode file path: ");
+                (Some(self.read_input()), Timestamp::FILM_FRAME_RATE)
+            }
+        };
+        (Some(path), timecodes, fps, self.get_snap_window())
+    }
+
+    fn get_tolerance(&self, default: Timestamp) -> Timestamp {
+        loop {
+            print!(
+                "❯ Maximum drift in seconds before a block is inserted (empty for {}): ",
+                default.as_secs_f64()
+            );
+            let input = self.read_input();
+            if input.is_empty() {
+                return default;
+            }
+            match input.replace(',', ".").parse::<f64>() {
+                Ok(secs) if secs > 0.0 => return Timestamp::from_secs_f64(secs),
+                _ => println!("  '{}' is not a valid number of seconds.", input),
+            }
+        }
+    }
+
+    fn get_anchor_line(&self, default: usize) -> usize {
+        loop {
+            print!(
+                "❯ Dialogue line that anchors the initial offset (empty for {}): ",
+                default + 1
+            );
+            let input = self.read_input();
+            if input.is_empty() {
+                return default;
+            }
+            match input.parse::<usize>() {
+                Ok(line) if line > 0 => return line - 1,
+                _ => println!("  '{}' is not a valid line number.", input),
+            }
+        }
+    }
+
+    fn read_frame_rate(&self, prompt: &str) -> f64 {
+        loop {
+            print!("{} (empty for 23.976): ", prompt);
+            let input = self.read_input();
+            if input.is_empty() {
+                return Timestamp::FILM_FRAME_RATE;
+            }
+            match input.replace(',', ".").parse::<f64>() {
+                Ok(fps) if fps > 0.0 => return fps,
+                _ => println!("  '{}' is not a valid frame rate.", input),
+            }
+        }
+    }
+
+    fn get_pivot(&self) -> Timestamp {
+        loop {
+            print!("❯ Pivot time in seconds that stays fixed (empty for 0): ");
+            let input = self.read_input();
+            if input.is_empty() {
+                return Timestamp::ZERO;
+            }
+            match input.replace(',', ".").parse::<f64>() {
+                Ok(secs) if secs >= 0.0 => return Timestamp::from_secs_f64(secs),
+                _ => println!("  '{}' is not a valid number of seconds.", input),
+            }
+        }
+    }
+
+    fn get_frame_rate_mode(&self) -> FrameRateMode {
+        match self
+            .select_option(
+                "Frame Rate of File B",
+                &[
+                    "Keep A timing",
+                    "Detect PAL speedup and suggest a conversion",
+                    "Detect PAL speedup and convert automatically",
+                    "Convert between explicit frame rates",
+                ],
+            )
+            .as_str()
+        {
+            "1" => FrameRateMode::Keep,
+            "2" => FrameRateMode::Suggest,
+            "3" => FrameRateMode::Auto,
+            _ => {
+                let source = self.read_frame_rate("❯ Frame rate of file A");
+                let target = self.read_frame_rate("❯ Frame rate of file B");
+                let pivot = self.get_pivot();
+                FrameRateMode::Convert(FrameRateConversion::new(source, target).with_pivot(pivot))
+            }
+        }
+    }
+
+    fn get_sync_options(&self, defaults: SyncOptions) -> SyncOptions {
+        print!("\n❯ Customize synchronization settings? (y/n): ");
+        if self.read_input().to_lowercase() != "y" {
+            return defaults;
+        }
+        let mode = match self
+            .select_option(
+                "Sync Mode",
+                &[
+                    "Tagged extra scenes",
+                    "Timing alignment (untagged scenes)",
+                    "Timing and text alignment (same language)",
+                ],
+            )
+            .as_str()
+        {
+            "1" => SyncMode::Scenes,
+            "2" => SyncMode::Timing,
+            _ => SyncMode::TimingAndText,
+        };
+        let tolerance = self.get_tolerance(defaults.tolerance);
+        let anchor_line = self.get_anchor_line(defaults.anchor_line);
+        print!("❯ Shift end times along with start times? (y/n): ");
+        let shift_end = match self.read_input().to_lowercase().as_str() {
+            "" => defaults.shift_end,
+            answer => answer != "n",
+        };
+        let frame_rate = self.get_frame_rate_mode();
+        SyncOptions::new()
+            .with_mode(mode)
+            .with_tolerance(tolerance)
+            .with_anchor_line(anchor_line)
+            .with_shift_end(shift_end)
+            .with_frame_rate(frame_rate)
+    }
+
+    fn get_sync_report_enabled(&self) -> bool {
+        print!("❯ Write a JSON sync report next to the output? (y/n): ");
+        self.read_input().to_lowercase() == "y"
+    }
+
+    fn get_keep_comments(&self) -> bool {
+        print!("\n❯ Keep Comment events in the output? (y/n): ");
+        self.read_input().to_lowercase() != "n"
+    }
+
+    fn get_export_options(&self) -> (Option<String>, Vec<String>, bool) {
+        print!("\n❯ Also export a copy in another format? (y/n): ");
+        if self.read_input().to_lowercase() != "y" {
+            return (None, Vec::new(), false);
+        }
+        let target = self.select_option(
+            "Export Format",
+            &[
+                "SRT",
+                "VTT",
+                "MicroDVD (.sub)",
+                "SubViewer (.sub)",
+                "TTML / DFXP",
+                "EBU-TT",
+            ],
+        );
+        let frames = match target.as_str() {
+            "5" | "6" => self.select_option("TTML Timing", &["Clock time", "Frames"]) == "2",
+            _ => false,
+        };
+        print!("❯ Styles to include (comma separated, empty for all): ");
+        let styles = self
+            .read_input()
+            .split(',')
+            .map(|style| style.trim().to_string())
+            .filter(|style| !style.is_empty())
+            .collect();
+        (Some(target), styles, frames)
+    }
+
     fn select_option(&self, title: &str, options: &[&str]) -> String {
         loop {
             println!("\n❯ {}", title);
@@ -48,24 +376,66 @@ impl Console {
             }
             print!("  Select choice [1-{}]: ", options.len());
             let input = self.read_input();
-            if let Ok(val) = input.parse::<usize>() {
-                if val > 0 && val <= options.len() {
-                    return input;
-                }
+            if let Ok(val) = input.parse::<usize>()
+                && val > 0
+                && val <= options.len()
+            {
+                return input;
             }
             println!("  '{}' is not valid.", input);
         }
     }
 }
 
+impl Default for Console {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl View for Console {
     fn get_format(&self) -> String {
-        match self.select_option("Format", &["ASS", "SRT"]).as_str() {
+        match self
+            .select_option("Format", &["ASS", "SRT", "VTT"])
+            .as_str()
+        {
             "1" => "ass".to_string(),
-            _ => "srt".to_string(),
+            "2" => "srt".to_string(),
+            _ => "vtt".to_string(),
+        }
+    }
+
+    fn get_source_format(&self, ext: &str) -> String {
+        if ext != "ass" {
+            return ext.to_string();
+        }
+        match self
+            .select_option(
+                "File A Format",
+                &[
+                    "ASS",
+                    "SRT (import)",
+                    "VTT (import)",
+                    "MicroDVD / SubViewer (import)",
+                    "TTML (import)",
+                    "DFXP (import)",
+                ],
+            )
+            .as_str()
+        {
+            "2" => "srt".to_string(),
+            "3" => "vtt".to_string(),
+            "4" => "sub".to_string(),
+            "5" => "ttml".to_string(),
+            "6" => "dfxp".to_string(),
+            _ => "ass".to_string(),
         }
     }
 
+    fn get_frame_rate(&self) -> f64 {
+        self.read_frame_rate("\n❯ Frame rate for frame-based subtitles")
+    }
+
     fn request_path_a(&self, ext: &str) -> String {
         loop {
             print!("\n❯ Enter path for file A (Texts): ");
@@ -111,6 +481,27 @@ impl View for Console {
             AppStatus::ReadingA => {
                 println!("[    READ    ] Extracting DIALOGUE TEXTS from Source (A)...")
             }
+            AppStatus::Importing => {
+                println!("[   IMPORT   ] Converting Source (A) into an ASS script...")
+            }
+            AppStatus::Retiming => {
+                println!("[   RETIME   ] Interpolating Source (A) timestamps between anchors...")
+            }
+            AppStatus::Snapping => {
+                println!("[    SNAP    ] Snapping line starts and ends to keyframes...")
+            }
+            AppStatus::KeyframeFrameRate => {
+                println!("[    INFO    ] Using the frame rate stored in the keyframe file.")
+            }
+            AppStatus::ReadingAudio => {
+                println!("[    READ    ] Loading the episode audio track (WAV)...")
+            }
+            AppStatus::AnalyzingAudio => {
+                println!("[  ANALYZE   ] Detecting speech and matching Source (A) timing...")
+            }
+            AppStatus::LowAudioConfidence => {
+                println!("[    INFO    ] Audio match is not reliable; keeping Source (A) timing.")
+            }
             AppStatus::ReadingB => {
                 println!("[    READ    ] Extracting TIMESTAMPS from Source (B)...")
             }
@@ -123,6 +514,12 @@ impl View for Console {
                 println!("[    INFO    ] No missing lines detected; skipping translation step.")
             }
             AppStatus::Styling => println!("[   STYLE    ] Applying visual profiles..."),
+            AppStatus::Attachments => {
+                println!("[ ATTACHMENT ] Processing embedded fonts and graphics...")
+            }
+            AppStatus::Exporting => {
+                println!("[   EXPORT   ] Writing plain subtitle copy for non-ASS players...")
+            }
             AppStatus::Writing => println!("[   EXPORT   ] Saving output to disk..."),
             AppStatus::AskTranslation => {
                 println!("[  AI-TASK   ] Process via AI and save as 'translations.txt'.\n")
@@ -139,7 +536,24 @@ impl View for Console {
         self.read_input().to_lowercase() == "y"
     }
 
-    fn get_options(&self, output_path: &str, ext: &str, sync_enabled: bool) -> AppOptions {
+    fn get_options(
+        &self,
+        output_path: &str,
+        ext: &str,
+        sync_enabled: bool,
+        sync: SyncOptions,
+    ) -> AppOptions {
+        let (sync, sync_report) = match sync_enabled {
+            true => (self.get_sync_options(sync), self.get_sync_report_enabled()),
+            false => (sync, false),
+        };
+        let (retime, anchor_entries) = self.get_anchor_options();
+        let audio_path = match sync_enabled {
+            true => None,
+            false => self.get_audio_path(),
+        };
+        let (keyframes_path, timecodes_path, keyframe_fps, snap_window) =
+            self.get_keyframe_options();
         let mut translation_enabled = false;
         let mut ai_type = None;
         print!("\n❯ Enable translation engine? (y/n): ");
@@ -153,13 +567,57 @@ impl View for Console {
         if self.read_input().to_lowercase() == "y" {
             style = Some(self.select_option("Style Profile", &["Main", "Second"]));
         }
+        let mut encoding = None;
+        print!("\n❯ Convert output encoding (default keeps the source encoding)? (y/n): ");
+        if self.read_input().to_lowercase() == "y" {
+            encoding = Some(self.select_option(
+                "Output Encoding",
+                &[
+                    "UTF-8",
+                    "UTF-8 with BOM",
+                    "UTF-16 LE",
+                    "UTF-16 BE",
+                    "Windows-1252",
+                ],
+            ));
+        }
+        let keep_comments = match ext {
+            "ass" => self.get_keep_comments(),
+            _ => true,
+        };
+        let (attachments, attachment_paths) = match ext {
+            "ass" => self.get_attachment_options(),
+            _ => (None, Vec::new()),
+        };
+        let (export, export_styles, export_frames) = match ext {
+            "ass" => self.get_export_options(),
+            _ => (None, Vec::new(), false),
+        };
         AppOptions {
             output_path: output_path.to_string(),
             format_type: ext.to_string(),
             sync_enabled,
+            sync,
+            sync_report,
+            retime,
+            anchor_entries,
+            audio_path,
+            keyframes_path,
+            timecodes_path,
+            keyframe_fps,
+            snap_window,
             style,
             translation_enabled,
+            keep_comments,
             ai_type,
+            encoding,
+            attachments,
+            attachment_paths,
+            export,
+            export_styles,
+            export_frames,
+            import_format: None,
+            frame_rate: None,
         }
     }
 
@@ -168,7 +626,134 @@ impl View for Console {
         self.read_input().to_lowercase() != "n"
     }
 
-    fn display_error(&self, message: &str) {
-        eprintln!("\n[   ERROR    ] {}\n", message);
+    fn display_attachments(&self, attachments: &[Attachment]) {
+        if attachments.is_empty() {
+            println!("[    INFO    ] No embedded fonts or graphics found.");
+        }
+        for attachment in attachments {
+            let kind = match attachment.kind {
+                AttachmentKind::Font => "Font",
+                AttachmentKind::Graphic => "Graphic",
+            };
+            println!(
+                "[ ATTACHMENT ] {:<8} {} ({} bytes)",
+                kind,
+                attachment.name,
+                attachment.size()
+            );
+        }
+    }
+
+    fn display_sync_report(&self, report: &SyncReport) {
+        println!(
+            "[   REPORT   ] Initial offset {} ms, {} offset changes, {} inserted, {} by nearest time, {} skipped, {} only in A, {} overlaps",
+            report.initial_delta.millis(),
+            report.delta_changes.len(),
+            report.count(BlockPlacement::Inserted),
+            report.count(BlockPlacement::Fallback),
+            report.count(BlockPlacement::Skipped),
+            report.count(BlockPlacement::Unmatched),
+            report.overlaps.len()
+        );
+        if let Some(detection) = report.frame_rate {
+            let conversion = detection.conversion;
+            let measured = detection.measured.map_or(String::new(), |ratio| {
+                format!(" (measured ratio {:.5})", ratio)
+            });
+            match detection.applied {
+                true => println!(
+                    "[   RETIME   ] File A converted from {:.3} to {:.3} fps{}",
+                    conversion.source_fps, conversion.target_fps, measured
+                ),
+                false => println!(
+                    "[ SUGGESTION ] File A looks like {:.3} fps against {:.3} fps in file B{}; enable the conversion in the sync settings",
+                    conversion.source_fps, conversion.target_fps, measured
+                ),
+            }
+        }
+        for change in &report.delta_changes {
+            println!(
+                "[   OFFSET   ] Line {}: {} ms",
+                change.line + 1,
+                change.delta.millis()
+            );
+        }
+        for block in &report.blocks {
+            let label = match block.placement {
+                BlockPlacement::Inserted => "[   SCENE    ] Inserted",
+                BlockPlacement::Fallback => "[  WARNING   ] Placed by nearest time",
+                BlockPlacement::Skipped => "[  WARNING   ] Skipped, nothing to attach to",
+                BlockPlacement::Unmatched => "[    INFO    ] Only in A, retimed by neighbour",
+            };
+            let position = block
+                .after_line
+                .map_or("start".to_string(), |line| format!("line {}", line + 1));
+            println!(
+                "{}: {} --> {} ({} lines, after {}, confidence {:.0}%)",
+                label,
+                block.start.to_vtt(),
+                block.end.to_vtt(),
+                block.lines,
+                position,
+                block.confidence * 100.0
+            );
+        }
+        for overlap in &report.overlaps {
+            println!(
+                "[  WARNING   ] Line {} ({} --> {}) overlaps inserted lines at {} --> {}",
+                overlap.line + 1,
+                overlap.start.to_vtt(),
+                overlap.end.to_vtt(),
+                overlap.block_start.to_vtt(),
+                overlap.block_end.to_vtt()
+            );
+        }
+    }
+
+    fn display_progress(&self, done: usize, total: usize) {
+        print!("\r[  ANALYZE   ] Searching offsets: {}/{}", done, total);
+        if done >= total {
+            println!();
+        }
+        let _ = io::stdout().flush();
+    }
+
+    fn display_audio_sync(&self, result: &AudioSyncResult) {
+        println!(
+            "[   AUDIO    ] Offset {:+.3} s (confidence {:.0}%, speech in {:.0}% of the track)",
+            result.offset.as_secs_f64(),
+            result.confidence * 100.0,
+            result.speech_ratio * 100.0
+        );
+        for segment in &result.segments {
+            let source = match segment.refined {
+                true => "refined",
+                false => "global offset kept",
+            };
+            println!(
+                "[  SEGMENT   ] {} --> {} ({} lines): {:+.3} s, confidence {:.0}%, {}",
+                segment.start.to_vtt(),
+                segment.end.to_vtt(),
+                segment.lines,
+                segment.offset.as_secs_f64(),
+                segment.confidence * 100.0,
+                source
+            );
+        }
+    }
+
+    fn display_snap_summary(&self, summary: &SnapSummary) {
+        println!(
+            "[    SNAP    ] {} starts and {} ends moved onto {} keyframes",
+            summary.starts, summary.ends, summary.keyframes
+        );
+    }
+
+    fn display_error(&self, message: &str, location: Option<&SourceLocation>) {
+        eprintln!("\n[   ERROR    ] {}", message);
+        if let Some(location) = location {
+            self.show_location(location);
+        }
+        eprintln!();
     }
 }
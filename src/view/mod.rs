@@ -1,15 +1,30 @@
+use crate::model::{
+    audio::offset_finder::AudioSyncResult, format::ass::document::attachments::Attachment,
+    parse_error::SourceLocation, retiming::keyframes::SnapSummary, sync_options::SyncOptions,
+    sync_report::SyncReport, timestamp::Timestamp,
+};
+
 pub mod console;
 
 pub enum AppStatus {
     Welcome,
     Reading,
     ReadingA,
+    Importing,
+    Retiming,
+    ReadingAudio,
+    AnalyzingAudio,
+    LowAudioConfidence,
     ReadingB,
     Preprocessing,
     Processing,
+    Snapping,
+    KeyframeFrameRate,
     Translating,
     NoLinesToTranslate,
     Styling,
+    Attachments,
+    Exporting,
     Writing,
     AskTranslation,
     TranslationFileFound,
@@ -25,9 +40,27 @@ pub struct AppOptions {
     pub output_path: String,
     pub format_type: String,
     pub sync_enabled: bool,
+    pub sync: SyncOptions,
+    pub sync_report: bool,
+    pub retime: Option<String>,
+    pub anchor_entries: Vec<String>,
+    pub audio_path: Option<String>,
+    pub keyframes_path: Option<String>,
+    pub timecodes_path: Option<String>,
+    pub keyframe_fps: f64,
+    pub snap_window: Timestamp,
     pub style: Option<String>,
     pub translation_enabled: bool,
+    pub keep_comments: bool,
     pub ai_type: Option<String>,
+    pub encoding: Option<String>,
+    pub attachments: Option<String>,
+    pub attachment_paths: Vec<String>,
+    pub export: Option<String>,
+    pub export_styles: Vec<String>,
+    pub export_frames: bool,
+    pub import_format: Option<String>,
+    pub frame_rate: Option<f64>,
 }
 
 pub struct AppConfig {
@@ -37,12 +70,25 @@ pub struct AppConfig {
 
 pub trait View {
     fn get_format(&self) -> String;
+    fn get_source_format(&self, ext: &str) -> String;
+    fn get_frame_rate(&self) -> f64;
     fn request_path_a(&self, ext: &str) -> String;
     fn request_path_b(&self, ext: &str) -> String;
     fn request_path_result(&self, ext: &str) -> String;
     fn display_status(&self, status: AppStatus);
-    fn display_error(&self, message: &str);
+    fn display_error(&self, message: &str, location: Option<&SourceLocation>);
+    fn display_attachments(&self, attachments: &[Attachment]);
+    fn display_sync_report(&self, report: &SyncReport);
+    fn display_progress(&self, done: usize, total: usize);
+    fn display_audio_sync(&self, result: &AudioSyncResult);
+    fn display_snap_summary(&self, summary: &SnapSummary);
     fn get_sync_enabled(&self) -> bool;
-    fn get_options(&self, output_path: &str, ext: &str, sync_enabled: bool) -> AppOptions;
+    fn get_options(
+        &self,
+        output_path: &str,
+        ext: &str,
+        sync_enabled: bool,
+        sync: SyncOptions,
+    ) -> AppOptions;
     fn confirm_translation_ready(&self) -> bool;
 }
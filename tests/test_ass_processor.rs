@@ -1,11 +1,14 @@
 #[cfg(test)]
 mod tests {
     use durazubs::model::{
-        format::ass::{
-            ass_processor::AssProcessor,
-            parser::parser_error::{ParseRes, ParserError},
+        format::ass::{ass_processor::AssProcessor, importer::ImportSource},
+        parse_error::{ParseRes, ParserError},
+        retiming::{
+            anchors::{Anchor, AnchorSource},
+            keyframes::{FrameTimes, KeyframeSnap, Keyframes},
         },
         subtitle_processor::SubtitleProcessor,
+        timestamp::Timestamp,
     };
 
     struct TestCase {
@@ -76,8 +79,8 @@ mod tests {
         expected_output: &[
             "[Events]",
             "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text",
-            "Dialogue: 0,0:00:01.50,0:00:03.50,OtherStyle,,0,0,0,,Line 1 text B",
-            "Dialogue: 0,0:00:04.00,0:00:06.50,OtherStyle,,0,0,0,,Line 2 Text B",
+            "Dialogue: 0,0:00:01.50,0:00:03.50,OtherStyle,,0000,0000,0000,,Line 1 text B",
+            "Dialogue: 0,0:00:04.00,0:00:06.50,OtherStyle,,0000,0000,0000,,Line 2 Text B",
         ],
     };
 
@@ -100,11 +103,60 @@ mod tests {
         ],
     };
 
+    static ATTACHMENTS_CASE: TestCase = TestCase {
+        name: "keeps fonts placed after the events through sync and preprocessing",
+        input_a: &[
+            "[Events]",
+            "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text",
+            "Dialogue: 0,0:00:00.50,0:00:02.50,Default,,0,0,0,,Line 1 text B",
+            "",
+            "[Fonts]",
+            "fontname: Sign_0.ttf",
+            "[3'EB]",
+        ],
+        input_b: &[
+            "[Events]",
+            "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text",
+            "Dialogue: 0,0:00:01.50,0:00:03.00,Default,,0,0,0,,Line 1 text A",
+            "",
+            "[Fonts]",
+            "fontname: Other_0.ttf",
+            "[3'EB]",
+        ],
+        expected_fields: None,
+        expected_output: &["", "[Fonts]", "fontname: Sign_0.ttf", "[3'EB]"],
+    };
+
+    static IMPORT_CASE: TestCase = TestCase {
+        name: "imports an SRT translation and syncs it against a BD script",
+        input_a: &[
+            "1",
+            "00:00:00,500 --> 00:00:02,500",
+            "<i>Línea 1</i>",
+            "",
+            "2",
+            "00:00:03,000 --> 00:00:05,500",
+            "Línea 2",
+        ],
+        input_b: &[
+            "[Events]",
+            "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text",
+            "Dialogue: 10,0:00:01.50,0:00:03.00,Default,Actor,0,0,0,,Line 1 text A",
+            "Dialogue: 10,0:00:04.00,0:00:07.00,Default,Actor,0,0,0,,Line 2 text A",
+        ],
+        expected_fields: None,
+        expected_output: &[
+            "Dialogue: 0,0:00:01.50,0:00:03.50,Main,,0,0,0,,{\\i1}Línea 1{\\i0}",
+            "Dialogue: 0,0:00:04.00,0:00:06.50,Main,,0,0,0,,Línea 2",
+        ],
+    };
+
     fn run_test_case(test_case: &TestCase) -> ParseRes<Vec<String>> {
         let proc = AssProcessor::new();
-        let mut lines_a: Vec<String> = test_case.input_a.iter().map(|s| s.to_string()).collect();
+        let lines_a: Vec<String> = test_case.input_a.iter().map(|s| s.to_string()).collect();
         let lines_b: Vec<String> = test_case.input_b.iter().map(|s| s.to_string()).collect();
-        proc.synchronize(&mut lines_a, &lines_b)
+        proc.synchronize(&lines_a, &lines_b)
+            .map(|result| result.output)
     }
 
     #[test]
@@ -123,9 +175,9 @@ mod tests {
     #[test]
     fn test_generic_synchronization_logic() -> ParseRes<()> {
         let proc = AssProcessor::new();
-        let mut lines_a: Vec<String> = SYNC_CASE.input_a.iter().map(|s| s.to_string()).collect();
+        let lines_a: Vec<String> = SYNC_CASE.input_a.iter().map(|s| s.to_string()).collect();
         let lines_b: Vec<String> = SYNC_CASE.input_b.iter().map(|s| s.to_string()).collect();
-        let synced = proc.synchronize(&mut lines_a, &lines_b)?;
+        let synced = proc.synchronize(&lines_a, &lines_b)?.output;
         for (i, expected_line) in SYNC_CASE.expected_output.iter().enumerate() {
             assert_eq!(
                 synced[i], *expected_line,
@@ -137,12 +189,125 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_attachments_are_preserved() -> ParseRes<()> {
+        let proc = AssProcessor::new();
+        let mut lines_b: Vec<String> = ATTACHMENTS_CASE
+            .input_b
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let cleaned_b = proc.preprocessing(&mut lines_b)?;
+        assert_eq!(cleaned_b[4..], ATTACHMENTS_CASE.input_b[4..]);
+
+        let synced = run_test_case(&ATTACHMENTS_CASE)?;
+        assert_eq!(
+            synced[3..],
+            *ATTACHMENTS_CASE.expected_output,
+            "Case failed: {}",
+            ATTACHMENTS_CASE.name
+        );
+        let names: Vec<String> = proc
+            .attachments(&synced)?
+            .into_iter()
+            .map(|a| a.name)
+            .collect();
+        assert_eq!(names, vec!["Sign_0.ttf"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_import_then_sync() -> ParseRes<()> {
+        let proc = AssProcessor::new();
+        let lines_a: Vec<String> = IMPORT_CASE.input_a.iter().map(|s| s.to_string()).collect();
+        let lines_b: Vec<String> = IMPORT_CASE.input_b.iter().map(|s| s.to_string()).collect();
+        let imported = proc.import(&lines_a, ImportSource::Srt)?;
+        let synced = proc.synchronize(&imported, &lines_b)?.output;
+        let dialogues: Vec<&String> = synced
+            .iter()
+            .filter(|l| l.starts_with("Dialogue:"))
+            .collect();
+        assert_eq!(
+            dialogues, IMPORT_CASE.expected_output,
+            "Case failed: {}",
+            IMPORT_CASE.name
+        );
+        assert!(synced.iter().any(|l| l.starts_with("Style: Main,")));
+        Ok(())
+    }
+
+    static EVENTS_INPUT: &[&str] = &[
+        "[Events]",
+        "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text",
+        "Dialogue: 0,0:00:01.00,0:00:02.00,Default,,0,0,0,,Uno",
+        "Comment: 0,0:00:03.10,0:00:04.00,Default,,0,0,0,,Nota",
+        "Dialogue: 0,0:00:05.00,0:00:06.00,Default,,0,0,0,,Dos",
+    ];
+
+    fn event_lines(lines: &[String]) -> Vec<&str> {
+        lines
+            .iter()
+            .filter(|l| l.starts_with("Dialogue:") || l.starts_with("Comment:"))
+            .map(|l| l.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_retime_moves_every_event() -> ParseRes<()> {
+        let lines: Vec<String> = EVENTS_INPUT.iter().map(|s| s.to_string()).collect();
+        let anchors = [
+            Anchor::new(AnchorSource::Line(0), Timestamp::from_millis(11_000)),
+            Anchor::new(AnchorSource::Line(1), Timestamp::from_millis(15_000)),
+        ];
+        let retimed = AssProcessor::new().retime(&lines, &anchors)?;
+        assert_eq!(
+            event_lines(&retimed),
+            vec![
+                "Dialogue: 0,0:00:11.00,0:00:12.00,Default,,0,0,0,,Uno",
+                "Comment: 0,0:00:13.10,0:00:14.00,Default,,0,0,0,,Nota",
+                "Dialogue: 0,0:00:15.00,0:00:16.00,Default,,0,0,0,,Dos",
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_snap_moves_every_event() -> ParseRes<()> {
+        let lines: Vec<String> = EVENTS_INPUT.iter().map(|s| s.to_string()).collect();
+        let snap = KeyframeSnap::new(&Keyframes::new(vec![75]), &FrameTimes::new(25.0));
+        let (snapped, summary) = AssProcessor::new().snap_keyframes(&lines, &snap)?;
+        assert_eq!(
+            event_lines(&snapped)[1],
+            "Comment: 0,0:00:02.98,0:00:04.00,Default,,0,0,0,,Nota"
+        );
+        assert_eq!(summary.starts, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_preprocessing_comment_choice() -> ParseRes<()> {
+        let mut lines: Vec<String> = EVENTS_INPUT.iter().map(|s| s.to_string()).collect();
+        let kept = AssProcessor::new().preprocessing(&mut lines)?;
+        let dropped = AssProcessor::new()
+            .with_comments(false)
+            .preprocessing(&mut lines)?;
+        assert_eq!(event_lines(&kept).len(), 3);
+        assert_eq!(
+            event_lines(&dropped),
+            vec![
+                "Dialogue: 0,0:00:01.00,0:00:02.00,Default,,0,0,0,,Uno",
+                "Dialogue: 0,0:00:05.00,0:00:06.00,Default,,0,0,0,,Dos",
+            ]
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_error_propagation_short() {
         let result = run_test_case(&CORRUPT_A_CASE);
         let expected = CORRUPT_A_CASE.expected_fields.unwrap();
         match result {
-            Err(ParserError::MissingFields { found }) => {
+            Err(ParserError::MissingFields { found, .. }) => {
                 assert_eq!(
                     found, expected,
                     "Field count mismatch in: {}",
@@ -161,7 +326,7 @@ mod tests {
         let result = run_test_case(&INCOMPLETE_A_CASE);
         let expected = INCOMPLETE_A_CASE.expected_fields.unwrap();
         match result {
-            Err(ParserError::MissingFields { found }) => {
+            Err(ParserError::MissingFields { found, .. }) => {
                 assert_eq!(
                     found, expected,
                     "Field count mismatch in: {}",
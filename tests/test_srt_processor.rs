@@ -0,0 +1,224 @@
+#[cfg(test)]
+mod tests {
+    use durazubs::model::{
+        format::srt::srt_processor::SrtProcessor,
+        parse_error::ParseRes,
+        retiming::{
+            anchors::Anchor,
+            keyframes::{FrameTimes, KeyframeSnap, Keyframes},
+        },
+        subtitle_processor::SubtitleProcessor,
+    };
+
+    struct TestCase {
+        name: &'static str,
+        input_a: &'static [&'static str],
+        input_b: &'static [&'static str],
+        expected_output: &'static [&'static str],
+    }
+
+    static PREPROCESS_CASE: TestCase = TestCase {
+        name: "cleans and sorts a raw SRT file",
+        input_a: &[
+            "3",
+            "00:00:05,000 --> 00:00:06,000",
+            "Third",
+            "",
+            "1",
+            "00:00:01,000 --> 00:00:02,000",
+            "First",
+            "",
+            "2",
+            "00:00:01,000 --> 00:00:02,000",
+            "First",
+            "",
+            "4",
+            "00:00:03,000 --> 00:00:04,000",
+            "<b></b>",
+        ],
+        input_b: &[],
+        expected_output: &[
+            "1",
+            "00:00:01,000 --> 00:00:02,000",
+            "First",
+            "",
+            "2",
+            "00:00:05,000 --> 00:00:06,000",
+            "Third",
+        ],
+    };
+
+    static SYNC_AND_TRANSLATE_CASE: TestCase = TestCase {
+        name: "sync inserts the extra scene and translation only touches it",
+        input_a: &[
+            "1",
+            "00:00:00,500 --> 00:00:02,500",
+            "Línea 1",
+            "",
+            "2",
+            "00:00:10,000 --> 00:00:12,000",
+            "Línea 2",
+        ],
+        input_b: &[
+            "1",
+            "00:00:01,500 --> 00:00:03,000",
+            "Line 1",
+            "",
+            "2",
+            "00:00:04,000 --> 00:00:06,000",
+            "[ADDITIONAL SCENE] Extra line",
+            "",
+            "3",
+            "00:00:15,000 --> 00:00:17,000",
+            "Line 2",
+        ],
+        expected_output: &[
+            "1",
+            "00:00:01,500 --> 00:00:03,500",
+            "Línea 1",
+            "",
+            "2",
+            "00:00:04,000 --> 00:00:06,000",
+            "[ADDITIONAL SCENE] Línea extra",
+            "",
+            "3",
+            "00:00:15,000 --> 00:00:17,000",
+            "Línea 2",
+        ],
+    };
+
+    static ANCHOR_RETIME_CASE: TestCase = TestCase {
+        name: "retimes every cue between a line anchor and a time anchor",
+        input_a: &[
+            "1",
+            "00:00:10,000 --> 00:00:12,000",
+            "Recap",
+            "",
+            "2",
+            "00:00:20,000 --> 00:00:22,000",
+            "Before the eyecatch",
+            "",
+            "3",
+            "00:00:40,000 --> 00:00:42,000",
+            "After the eyecatch",
+        ],
+        input_b: &[
+            "# anchors",
+            "1 -> 00:00:11,000",
+            "00:00:40,000 -> 00:00:45,000",
+        ],
+        expected_output: &[
+            "1",
+            "00:00:11,000 --> 00:00:13,267",
+            "Recap",
+            "",
+            "2",
+            "00:00:22,333 --> 00:00:24,600",
+            "Before the eyecatch",
+            "",
+            "3",
+            "00:00:45,000 --> 00:00:47,000",
+            "After the eyecatch",
+        ],
+    };
+
+    static KEYFRAME_SNAP_CASE: TestCase = TestCase {
+        name: "snaps cue starts and ends near scene cuts",
+        input_a: &[
+            "1",
+            "00:00:10,120 --> 00:00:12,000",
+            "Starts just after the cut",
+            "",
+            "2",
+            "00:00:15,000 --> 00:00:20,100",
+            "Ends a few frames late",
+            "",
+            "3",
+            "00:00:30,000 --> 00:00:31,000",
+            "Far from any cut",
+        ],
+        input_b: &["# keyframe format v1", "fps 0", "0", "250", "500"],
+        expected_output: &[
+            "1",
+            "00:00:09,980 --> 00:00:12,000",
+            "Starts just after the cut",
+            "",
+            "2",
+            "00:00:15,000 --> 00:00:19,980",
+            "Ends a few frames late",
+            "",
+            "3",
+            "00:00:30,000 --> 00:00:31,000",
+            "Far from any cut",
+        ],
+    };
+
+    fn to_lines(lines: &[&str]) -> Vec<String> {
+        lines.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_preprocessing() -> ParseRes<()> {
+        let proc = SrtProcessor::new();
+        let mut lines = to_lines(PREPROCESS_CASE.input_a);
+        let result = proc.preprocessing(&mut lines)?;
+        assert_eq!(
+            result,
+            to_lines(PREPROCESS_CASE.expected_output),
+            "Case failed: {}",
+            PREPROCESS_CASE.name
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_sync_and_apply_translation() -> ParseRes<()> {
+        let proc = SrtProcessor::new();
+        let lines_a = to_lines(SYNC_AND_TRANSLATE_CASE.input_a);
+        let lines_b = to_lines(SYNC_AND_TRANSLATE_CASE.input_b);
+        let mut synced = proc.synchronize(&lines_a, &lines_b)?.output;
+        assert!(proc.has_additional_scene(&synced)?);
+        let to_translate = proc.get_lines_to_translate(&mut synced)?;
+        assert_eq!(to_translate.last().map(String::as_str), Some("Extra line"));
+        let result = proc.apply_translation(&mut synced, &["Línea extra".to_string()])?;
+        assert_eq!(
+            result,
+            to_lines(SYNC_AND_TRANSLATE_CASE.expected_output),
+            "Case failed: {}",
+            SYNC_AND_TRANSLATE_CASE.name
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_retime_with_anchors() -> ParseRes<()> {
+        let proc = SrtProcessor::new();
+        let lines_a = to_lines(ANCHOR_RETIME_CASE.input_a);
+        let anchors = Anchor::parse_lines(&to_lines(ANCHOR_RETIME_CASE.input_b))?;
+        let result = proc.retime(&lines_a, &anchors)?;
+        assert_eq!(
+            result,
+            to_lines(ANCHOR_RETIME_CASE.expected_output),
+            "Case failed: {}",
+            ANCHOR_RETIME_CASE.name
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_snap_to_keyframes() -> ParseRes<()> {
+        let proc = SrtProcessor::new();
+        let lines_a = to_lines(KEYFRAME_SNAP_CASE.input_a);
+        let keyframes = Keyframes::parse(&to_lines(KEYFRAME_SNAP_CASE.input_b))?;
+        let snap = KeyframeSnap::new(&keyframes, &FrameTimes::new(25.0));
+        let (result, summary) = proc.snap_keyframes(&lines_a, &snap)?;
+        assert_eq!(
+            result,
+            to_lines(KEYFRAME_SNAP_CASE.expected_output),
+            "Case failed: {}",
+            KEYFRAME_SNAP_CASE.name
+        );
+        assert_eq!((summary.starts, summary.ends), (1, 1));
+        Ok(())
+    }
+}
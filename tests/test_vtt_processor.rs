@@ -0,0 +1,65 @@
+#[cfg(test)]
+mod tests {
+    use durazubs::model::{
+        format::vtt::vtt_processor::VttProcessor, parse_error::ParseRes,
+        subtitle_processor::SubtitleProcessor,
+    };
+
+    struct TestCase {
+        name: &'static str,
+        input: &'static [&'static str],
+        expected_output: &'static [&'static str],
+    }
+
+    static WORKFLOW_CASE: TestCase = TestCase {
+        name: "cleans, sorts and styles a WebVTT file",
+        input: &[
+            "WEBVTT",
+            "",
+            "00:00:05.000 --> 00:00:06.000",
+            "<v ADDITIONAL SCENE>Extra",
+            "",
+            "00:00:01.000 --> 00:00:02.000",
+            "First",
+            "",
+            "00:00:03.000 --> 00:00:04.000",
+            "<i></i>",
+        ],
+        expected_output: &[
+            "WEBVTT",
+            "",
+            "STYLE",
+            "::cue {",
+            "  font-family: \"Roboto\", sans-serif;",
+            "  color: #FFFFFF;",
+            "  text-shadow: 0 0 1.5px #000000;",
+            "}",
+            "",
+            "00:00:01.000 --> 00:00:02.000",
+            "First",
+            "",
+            "00:00:05.000 --> 00:00:06.000",
+            "<v ADDITIONAL SCENE>Extra",
+        ],
+    };
+
+    fn to_lines(lines: &[&str]) -> Vec<String> {
+        lines.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_preprocess_and_style() -> ParseRes<()> {
+        let proc = VttProcessor::new().with_style(Some("2".to_string()));
+        let mut lines = to_lines(WORKFLOW_CASE.input);
+        let cleaned = proc.preprocessing(&mut lines)?;
+        assert!(proc.has_additional_scene(&cleaned)?);
+        let result = proc.apply_style(&cleaned)?;
+        assert_eq!(
+            result,
+            to_lines(WORKFLOW_CASE.expected_output),
+            "Case failed: {}",
+            WORKFLOW_CASE.name
+        );
+        Ok(())
+    }
+}